@@ -15,6 +15,7 @@ use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{console, MediaStream, MediaStreamConstraints};
 
+use crate::error::ReachyError;
 use crate::sleep;
 
 /// Default WebSocket host
@@ -60,14 +61,14 @@ impl WebSocketAudioStream {
         console::log_1(&format!("Connecting to audio stream: {}", url).into());
 
         let ws = WebSocket::open(&url)
-            .map_err(|e| JsValue::from_str(&format!("Audio WebSocket open failed: {:?}", e)))?;
+            .map_err(|e| ReachyError::ConnectionFailed(format!("Audio WebSocket open failed: {:?}", e)))?;
 
         // Wait for connection
         loop {
             match ws.state() {
                 gloo::net::websocket::State::Connecting => sleep(10).await?,
                 gloo::net::websocket::State::Open => break,
-                _ => return Err(JsValue::from_str("Audio WebSocket connection failed")),
+                _ => return Err(ReachyError::ConnectionFailed("Audio WebSocket connection failed".into()).into()),
             }
         }
 
@@ -91,11 +92,11 @@ impl MicrophoneStream {
             "WebSocket failed, falling back to browser microphone...",
         ));
 
-        let window = web_sys::window().ok_or("No window")?;
+        let window = web_sys::window().ok_or_else(|| ReachyError::Media("No window".into()))?;
         let navigator = window.navigator();
         let media_devices = navigator
             .media_devices()
-            .map_err(|_| "No media devices")?;
+            .map_err(|_| ReachyError::Media("No media devices".into()))?;
 
         // Request microphone access
         let constraints = MediaStreamConstraints::new();
@@ -104,18 +105,18 @@ impl MicrophoneStream {
 
         let promise = media_devices
             .get_user_media_with_constraints(&constraints)
-            .map_err(|e| JsValue::from_str(&format!("getUserMedia failed: {:?}", e)))?;
+            .map_err(|e| ReachyError::Media(format!("getUserMedia failed: {:?}", e)))?;
 
         let media_stream: MediaStream = JsFuture::from(promise).await?.dyn_into()?;
 
         // Create AudioContext
         let audio_context = web_sys::AudioContext::new()
-            .map_err(|e| JsValue::from_str(&format!("AudioContext failed: {:?}", e)))?;
+            .map_err(|e| ReachyError::Media(format!("AudioContext failed: {:?}", e)))?;
 
         // Create source from microphone stream
         let source = audio_context
             .create_media_stream_source(&media_stream)
-            .map_err(|e| JsValue::from_str(&format!("createMediaStreamSource failed: {:?}", e)))?;
+            .map_err(|e| ReachyError::Media(format!("createMediaStreamSource failed: {:?}", e)))?;
 
         // Create ScriptProcessorNode for capturing audio data
         // Buffer size of 4096 samples, mono input/output
@@ -123,16 +124,16 @@ impl MicrophoneStream {
             .create_script_processor_with_buffer_size_and_number_of_input_channels_and_number_of_output_channels(
                 4096, 1, 1,
             )
-            .map_err(|e| JsValue::from_str(&format!("createScriptProcessor failed: {:?}", e)))?;
+            .map_err(|e| ReachyError::Media(format!("createScriptProcessor failed: {:?}", e)))?;
 
         // Connect source -> script processor -> destination (to keep it running)
         source
             .connect_with_audio_node(&script_processor)
-            .map_err(|e| JsValue::from_str(&format!("connect source failed: {:?}", e)))?;
+            .map_err(|e| ReachyError::Media(format!("connect source failed: {:?}", e)))?;
 
         script_processor
             .connect_with_audio_node(&audio_context.destination())
-            .map_err(|e| JsValue::from_str(&format!("connect destination failed: {:?}", e)))?;
+            .map_err(|e| ReachyError::Media(format!("connect destination failed: {:?}", e)))?;
 
         // Set up audio processing callback
         let latest_audio: Arc<Mutex<Option<Vec<f32>>>> = Arc::new(Mutex::new(None));
@@ -292,9 +293,7 @@ pub async fn read_audio_chunk() -> Result<Option<Vec<f32>>, JsValue> {
     });
 
     match source_type {
-        None => Err(JsValue::from_str(
-            "Not connected to audio stream. Call connect_audio_stream() first.",
-        )),
+        None => Err(ReachyError::StreamNotConnected("audio").into()),
         Some("websocket") => {
             let (receiver, latest_audio) = AUDIO_STREAM
                 .with_borrow(|s| {
@@ -304,11 +303,11 @@ pub async fn read_audio_chunk() -> Result<Option<Vec<f32>>, JsValue> {
                         None
                     }
                 })
-                .ok_or_else(|| JsValue::from_str("WebSocket stream not available"))?;
+                .ok_or_else(|| ReachyError::InvalidState("WebSocket stream not available".into()))?;
 
             let mut rx = receiver
                 .try_lock()
-                .map_err(|e| JsValue::from_str(&format!("Lock failed: {:?}", e)))?;
+                .map_err(|e| ReachyError::Transport(format!("Lock failed: {:?}", e)))?;
 
             match rx.try_next().await {
                 Ok(Some(Message::Bytes(bytes))) => {
@@ -329,8 +328,8 @@ pub async fn read_audio_chunk() -> Result<Option<Vec<f32>>, JsValue> {
                     Ok(Some(samples))
                 }
                 Ok(Some(_)) => Ok(None),
-                Ok(None) => Err(JsValue::from_str("Audio stream closed")),
-                Err(e) => Err(JsValue::from_str(&format!("Read error: {:?}", e))),
+                Ok(None) => Err(ReachyError::TransportClosed.into()),
+                Err(e) => Err(ReachyError::Transport(format!("Read error: {:?}", e)).into()),
             }
         }
         Some("microphone") => {
@@ -343,7 +342,7 @@ pub async fn read_audio_chunk() -> Result<Option<Vec<f32>>, JsValue> {
                 }
             })
         }
-        Some(_) => Err(JsValue::from_str("Unknown audio source")),
+        Some(_) => Err(ReachyError::InvalidState("Unknown audio source".into()).into()),
     }
 }
 
@@ -400,8 +399,8 @@ pub async fn send_audio_chunk(samples: Vec<f32>) -> Result<(), JsValue> {
             }
         })
         .ok_or_else(|| {
-            JsValue::from_str(
-                "Cannot send audio: not connected via WebSocket (microphone fallback is receive-only)",
+            ReachyError::InvalidState(
+                "Cannot send audio: not connected via WebSocket (microphone fallback is receive-only)".into(),
             )
         })?;
 
@@ -410,10 +409,10 @@ pub async fn send_audio_chunk(samples: Vec<f32>) -> Result<(), JsValue> {
 
     sender
         .try_lock()
-        .map_err(|e| JsValue::from_str(&format!("Lock failed: {:?}", e)))?
+        .map_err(|e| ReachyError::Transport(format!("Lock failed: {:?}", e)))?
         .send(Message::Bytes(bytes))
         .await
-        .map_err(|e| JsValue::from_str(&format!("Send failed: {:?}", e)))?;
+        .map_err(|e| ReachyError::Transport(format!("Send failed: {:?}", e)))?;
 
     Ok(())
 }
@@ -0,0 +1,310 @@
+//! Local stand-in for the Reachy Mini desktop app's WebSocket server.
+//!
+//! Serves the endpoints the wasm client and the WebSocket integration tests
+//! connect to, without a robot:
+//! - `/api/move/ws/raw/write`: raw Dynamixel packets, answered by a
+//!   [`SimulatedBus`] running in real time
+//! - `/api/video/ws`: synthetic JPEG frames
+//! - `/api/audio/ws`: a 440 Hz sine wave as Float32 LE samples; incoming
+//!   audio is discarded
+//!
+//! ```text
+//! cargo run --features sim-server --bin reachy-mini-sim-server [--keep-state] [ADDRESS]
+//! ```
+//!
+//! `ADDRESS` defaults to `127.0.0.1:8000`. Each connection to the motor
+//! endpoint starts from freshly powered motors (all at their middle
+//! position, torque off), so tests don't depend on what earlier clients
+//! did; `--keep-state` keeps the motor state across connections instead,
+//! like a real robot.
+
+use std::f32::consts::TAU;
+use std::io;
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use jpeg_encoder::{ColorType, Encoder};
+use reachy_mini::error::ReachyError;
+use reachy_mini::sim::SimulatedBus;
+use tungstenite::error::ProtocolError;
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::http::StatusCode;
+use tungstenite::{Error, HandshakeError, Message, WebSocket};
+
+/// Default listening address, matching the desktop app
+const DEFAULT_ADDRESS: &str = "127.0.0.1:8000";
+
+const MOVE_WS_PATH: &str = "/api/move/ws/raw/write";
+const VIDEO_WS_PATH: &str = "/api/video/ws";
+const AUDIO_WS_PATH: &str = "/api/audio/ws";
+
+/// Interval at which the simulated motors are stepped
+const TICK_MS: u64 = 5;
+
+const FRAME_WIDTH: u16 = 320;
+const FRAME_HEIGHT: u16 = 240;
+const FRAME_INTERVAL_MS: u64 = 66;
+const JPEG_QUALITY: u8 = 80;
+
+const SAMPLE_RATE: u32 = 16_000;
+const TONE_HZ: f32 = 440.0;
+const TONE_AMPLITUDE: f32 = 0.2;
+const AUDIO_CHUNK_SAMPLES: u32 = 1024;
+
+/// Flag keeping the motor state across connections
+const KEEP_STATE_FLAG: &str = "--keep-state";
+
+type Bus = Arc<Mutex<SimulatedBus>>;
+
+fn main() {
+    let mut keep_state = false;
+    let mut address = DEFAULT_ADDRESS.to_string();
+    for arg in std::env::args().skip(1) {
+        if arg == KEEP_STATE_FLAG {
+            keep_state = true;
+        } else {
+            address = arg;
+        }
+    }
+    let listener = TcpListener::bind(&address).unwrap_or_else(|e| {
+        eprintln!("Could not listen on {}: {}", address, e);
+        std::process::exit(1);
+    });
+
+    let bus: Bus = Arc::new(Mutex::new(SimulatedBus::new()));
+    spawn_clock(bus.clone());
+
+    println!("Simulated Reachy Mini listening on ws://{}", address);
+    println!(
+        "  {}  motors {:?}, {}",
+        MOVE_WS_PATH,
+        bus.lock().unwrap().motor_ids(),
+        if keep_state {
+            "state kept across connections"
+        } else {
+            "reset on each connection"
+        }
+    );
+    println!("  {}  {}x{} JPEG", VIDEO_WS_PATH, FRAME_WIDTH, FRAME_HEIGHT);
+    println!(
+        "  {}  {} Hz sine, {} Hz",
+        AUDIO_WS_PATH, TONE_HZ, SAMPLE_RATE
+    );
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let bus = bus.clone();
+                thread::spawn(move || handle_connection(stream, bus, keep_state));
+            }
+            Err(e) => eprintln!("Accept failed: {}", e),
+        }
+    }
+}
+
+/// Advance the simulation with wall-clock time, so motors move between requests.
+fn spawn_clock(bus: Bus) {
+    thread::spawn(move || {
+        let mut last = Instant::now();
+        loop {
+            thread::sleep(Duration::from_millis(TICK_MS));
+            let now = Instant::now();
+            let elapsed = now.duration_since(last).as_secs_f64() * 1000.0;
+            last = now;
+            if let Ok(bus) = bus.lock() {
+                bus.advance(elapsed);
+            }
+        }
+    });
+}
+
+/// Perform the WebSocket handshake and dispatch on the request path.
+// The handshake callback's error type is fixed by tungstenite
+#[allow(clippy::result_large_err)]
+fn handle_connection(stream: TcpStream, bus: Bus, keep_state: bool) {
+    let peer = stream
+        .peer_addr()
+        .map(|a| a.to_string())
+        .unwrap_or_default();
+    let mut path = String::new();
+
+    let callback = |request: &Request, response: Response| {
+        path = request.uri().path().to_string();
+        match path.as_str() {
+            MOVE_WS_PATH | VIDEO_WS_PATH | AUDIO_WS_PATH => Ok(response),
+            _ => {
+                let mut error = ErrorResponse::new(Some(format!("No endpoint at {}", path)));
+                *error.status_mut() = StatusCode::NOT_FOUND;
+                Err(error)
+            }
+        }
+    };
+
+    let socket = match tungstenite::accept_hdr(stream, callback) {
+        Ok(socket) => socket,
+        // Plain TCP probes (e.g. the integration tests' availability check)
+        Err(HandshakeError::Failure(Error::Protocol(ProtocolError::HandshakeIncomplete))) => return,
+        Err(e) => {
+            eprintln!("{} handshake failed: {}", peer, e);
+            return;
+        }
+    };
+
+    println!("{} connected to {}", peer, path);
+    if path == MOVE_WS_PATH && !keep_state {
+        if let Ok(mut bus) = bus.lock() {
+            *bus = SimulatedBus::new();
+        }
+    }
+    let result = match path.as_str() {
+        MOVE_WS_PATH => serve_motors(socket, &bus),
+        VIDEO_WS_PATH => serve_video(socket),
+        _ => serve_audio(socket),
+    };
+    match result {
+        Ok(()) | Err(ReachyError::TransportClosed) => {
+            println!("{} disconnected from {}", peer, path)
+        }
+        Err(e) => eprintln!("{} dropped from {}: {}", peer, path, e),
+    }
+}
+
+// ============================================================================
+// Endpoints
+// ============================================================================
+
+/// Answer each binary message with the simulated motors' status packets.
+fn serve_motors(mut socket: WebSocket<TcpStream>, bus: &Bus) -> Result<(), ReachyError> {
+    loop {
+        match socket.read().map_err(transport_error)? {
+            Message::Binary(packet) => {
+                let response = bus
+                    .lock()
+                    .map(|bus| bus.handle(&packet))
+                    .unwrap_or_default();
+                if !response.is_empty() {
+                    socket
+                        .send(Message::Binary(response))
+                        .map_err(transport_error)?;
+                }
+            }
+            Message::Close(_) => return Ok(()),
+            _ => {}
+        }
+    }
+}
+
+/// Stream a moving test pattern at about 15 frames per second.
+fn serve_video(mut socket: WebSocket<TcpStream>) -> Result<(), ReachyError> {
+    let start = Instant::now();
+    loop {
+        let frame = test_pattern(start.elapsed().as_secs_f32());
+        socket
+            .send(Message::Binary(frame))
+            .map_err(transport_error)?;
+        if !drain_until(&mut socket, Duration::from_millis(FRAME_INTERVAL_MS))? {
+            return Ok(());
+        }
+    }
+}
+
+/// Stream a sine wave in real-time chunks, discarding audio sent by the client.
+fn serve_audio(mut socket: WebSocket<TcpStream>) -> Result<(), ReachyError> {
+    let chunk_duration = Duration::from_secs_f64(AUDIO_CHUNK_SAMPLES as f64 / SAMPLE_RATE as f64);
+    let mut sample_index: u64 = 0;
+    loop {
+        let bytes: Vec<u8> = (0..AUDIO_CHUNK_SAMPLES as u64)
+            .flat_map(|i| {
+                let t = (sample_index + i) as f32 / SAMPLE_RATE as f32;
+                (TONE_AMPLITUDE * (TAU * TONE_HZ * t).sin()).to_le_bytes()
+            })
+            .collect();
+        sample_index = (sample_index + AUDIO_CHUNK_SAMPLES as u64) % SAMPLE_RATE as u64;
+
+        socket
+            .send(Message::Binary(bytes))
+            .map_err(transport_error)?;
+        if !drain_until(&mut socket, chunk_duration)? {
+            return Ok(());
+        }
+    }
+}
+
+/// Read and discard incoming messages for `duration`.
+///
+/// Returns `false` if the client closed the connection.
+fn drain_until(socket: &mut WebSocket<TcpStream>, duration: Duration) -> Result<bool, ReachyError> {
+    let deadline = Instant::now() + duration;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Ok(true);
+        }
+        socket
+            .get_mut()
+            .set_read_timeout(Some(remaining))
+            .map_err(|e| ReachyError::Transport(format!("Set timeout failed: {}", e)))?;
+
+        match socket.read() {
+            Ok(Message::Close(_)) => return Ok(false),
+            Ok(_) => {}
+            Err(Error::Io(e))
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) => {}
+            Err(e) => return Err(transport_error(e)),
+        }
+    }
+}
+
+fn transport_error(e: Error) -> ReachyError {
+    match e {
+        Error::ConnectionClosed
+        | Error::AlreadyClosed
+        | Error::Protocol(ProtocolError::ResetWithoutClosingHandshake) => {
+            ReachyError::TransportClosed
+        }
+        e => ReachyError::Transport(e.to_string()),
+    }
+}
+
+// ============================================================================
+// Video
+// ============================================================================
+
+/// Render a scrolling color gradient with a bouncing square, as JPEG.
+fn test_pattern(t: f32) -> Vec<u8> {
+    let (width, height) = (FRAME_WIDTH as usize, FRAME_HEIGHT as usize);
+    let offset = (t * 60.0) as usize;
+
+    let size = 40;
+    let square_x = ((t * 1.3).sin() * 0.5 + 0.5) * (width - size) as f32;
+    let square_y = ((t * 0.9).cos() * 0.5 + 0.5) * (height - size) as f32;
+    let (square_x, square_y) = (square_x as usize, square_y as usize);
+
+    let mut rgb = Vec::with_capacity(width * height * 3);
+    for y in 0..height {
+        for x in 0..width {
+            let in_square = (square_x..square_x + size).contains(&x)
+                && (square_y..square_y + size).contains(&y);
+            if in_square {
+                rgb.extend_from_slice(&[255, 255, 255]);
+            } else {
+                rgb.extend_from_slice(&[
+                    ((x + offset) % width * 255 / width) as u8,
+                    (y * 255 / height) as u8,
+                    128,
+                ]);
+            }
+        }
+    }
+
+    let mut jpeg = Vec::new();
+    Encoder::new(&mut jpeg, JPEG_QUALITY)
+        .encode(&rgb, FRAME_WIDTH, FRAME_HEIGHT, ColorType::Rgb)
+        .expect("test pattern matches the frame size");
+    jpeg
+}
@@ -0,0 +1,499 @@
+//! Transport-agnostic access to the Dynamixel motor bus.
+//!
+//! The robot logic talks to the motors through the [`MotorBus`] trait, which
+//! only needs a way to write bytes, read bytes and wait. Request/response
+//! exchanges (framing, timeouts, retries) are provided on top of that, so
+//! every backend behaves the same:
+//!
+//! - WebSerial and WebSocket in the browser (`GenericPort`)
+//! - Serial port and WebSocket on native targets (the `native` feature)
+//!
+//! A bus shared by concurrent tasks provides an [`Arbiter`]: each exchange
+//! then waits for its turn instead of interleaving with another, and write
+//! commands go before pending telemetry reads.
+
+use std::cell::RefCell;
+use std::future::poll_fn;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::task::{Poll, Waker};
+
+use futures_util::future::Either;
+
+use crate::dynamixel::{self, instruction, PacketFramer};
+use crate::error::ReachyError;
+
+/// Default time to wait for all expected motor responses in milliseconds
+const RESPONSE_TIMEOUT_MS: u32 = 100;
+
+/// Default delay before the first retry in milliseconds (doubled on each retry)
+const RETRY_BACKOFF_MS: u32 = 20;
+
+/// Timeout and retry policy for request/response exchanges on a [`MotorBus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IoPolicy {
+    /// Time to wait for responses per attempt, in milliseconds
+    pub timeout_ms: u32,
+    /// Number of retries after the first attempt
+    pub retries: u32,
+    /// Delay before the first retry in milliseconds, doubled on each retry
+    pub backoff_ms: u32,
+}
+
+impl IoPolicy {
+    /// Delay before retry number `attempt` (starting at 1), in milliseconds.
+    pub fn backoff(&self, attempt: u32) -> u32 {
+        let shift = attempt.saturating_sub(1).min(16);
+        self.backoff_ms.saturating_mul(1 << shift)
+    }
+}
+
+impl Default for IoPolicy {
+    /// 100 ms timeout, no retries.
+    fn default() -> Self {
+        Self {
+            timeout_ms: RESPONSE_TIMEOUT_MS,
+            retries: 0,
+            backoff_ms: RETRY_BACKOFF_MS,
+        }
+    }
+}
+
+/// A connection to the Dynamixel motor bus.
+///
+/// Implementors provide raw byte I/O and a clock; [`write_read`](Self::write_read)
+/// and [`try_write_read`](Self::try_write_read) are built on top.
+///
+/// Futures are not required to be `Send`: the browser backends hold JS
+/// handles and run on a single thread.
+#[allow(async_fn_in_trait)]
+pub trait MotorBus {
+    /// Write raw bytes to the bus.
+    async fn write(&self, packet: &[u8]) -> Result<(), ReachyError>;
+
+    /// Read the next chunk of bytes, waiting as long as needed.
+    async fn read(&self) -> Result<Vec<u8>, ReachyError>;
+
+    /// Wait for `ms` milliseconds.
+    async fn sleep(&self, ms: u32);
+
+    /// Current time in milliseconds, from an arbitrary origin.
+    fn now_ms(&self) -> f64;
+
+    /// Current timeout and retry policy.
+    fn io_policy(&self) -> IoPolicy;
+
+    /// Replace the timeout and retry policy used by [`write_read`](Self::write_read).
+    fn set_io_policy(&self, policy: IoPolicy);
+
+    /// Arbiter serializing the exchanges of concurrent callers, if any.
+    ///
+    /// [`send`](Self::send), [`write_read`](Self::write_read) and
+    /// [`try_write_read`](Self::try_write_read) wait for their turn on it;
+    /// [`write`](Self::write) and [`read`](Self::read) are raw and don't.
+    fn arbiter(&self) -> Option<&Arbiter> {
+        None
+    }
+
+    /// Write a packet that gets no response, waiting for the bus's
+    /// [`Arbiter`] if it has one.
+    async fn send(&self, packet: &[u8]) -> Result<(), ReachyError> {
+        let _turn = take_turn(self, packet).await;
+        self.write(packet).await
+    }
+
+    /// Read the next chunk of bytes, giving up after `ms` milliseconds.
+    ///
+    /// Returns `Ok(None)` on timeout. The default implementation races
+    /// [`read`](Self::read) against [`sleep`](Self::sleep), so a chunk
+    /// arriving after the timeout may be lost; backends with native read
+    /// timeouts should override it.
+    async fn read_timeout(&self, ms: u32) -> Result<Option<Vec<u8>>, ReachyError> {
+        let read = self.read();
+        let timer = self.sleep(ms);
+        futures_util::pin_mut!(read, timer);
+
+        match futures_util::future::select(read, timer).await {
+            Either::Left((data, _)) => data.map(Some),
+            Either::Right(((), _)) => Ok(None),
+        }
+    }
+
+    /// Write data and read response.
+    ///
+    /// Reads until every status packet expected for `packet` has arrived, or
+    /// until `timeout` milliseconds (default: the bus's [`IoPolicy`]) have
+    /// elapsed. Chunks are reassembled with a [`PacketFramer`], so responses
+    /// split over several reads or concatenated in one are handled; leftover
+    /// bytes are discarded, as are status packets from motors `packet` didn't
+    /// address (e.g. late replies to an earlier packet).
+    ///
+    /// If responses are missing, the packet is re-sent up to
+    /// `IoPolicy::retries` times with exponential backoff, keeping the attempt
+    /// that got the most responses.
+    ///
+    /// # Returns
+    /// The concatenated CRC-checked status packets received (possibly fewer
+    /// than expected if some motors didn't answer in time).
+    ///
+    /// # Errors
+    /// `ReachyError::Timeout` if responses were expected but none arrived on any
+    /// attempt, or if the write itself did not complete in time.
+    async fn write_read(
+        &self,
+        packet: &[u8],
+        timeout: Option<u32>,
+    ) -> Result<Vec<u8>, ReachyError> {
+        let _turn = take_turn(self, packet).await;
+        let policy = self.io_policy();
+        let timeout = timeout.unwrap_or(policy.timeout_ms);
+        let expected = dynamixel::expected_responses(packet);
+
+        let (received, response) = exchange_with_retries(self, packet, timeout).await?;
+
+        if received == 0 && expected.is_some_and(|n| n > 0) {
+            return Err(ReachyError::Timeout {
+                timeout_ms: timeout,
+                attempts: policy.retries + 1,
+            });
+        }
+
+        Ok(response)
+    }
+
+    /// Write data and read response, without failing if nothing answers.
+    ///
+    /// Same as [`write_read`](Self::write_read), but returns an empty response
+    /// instead of a timeout error. Use it when silence is a valid answer, e.g.
+    /// to check whether a motor ID is free.
+    async fn try_write_read(
+        &self,
+        packet: &[u8],
+        timeout: Option<u32>,
+    ) -> Result<Vec<u8>, ReachyError> {
+        let _turn = take_turn(self, packet).await;
+        let timeout = timeout.unwrap_or(self.io_policy().timeout_ms);
+        let (_, response) = exchange_with_retries(self, packet, timeout).await?;
+        Ok(response)
+    }
+}
+
+/// Forward [`MotorBus`] through shared pointers, so a bus can be shared by
+/// tasks (`Rc` in the browser, `Arc` natively).
+macro_rules! impl_shared_bus {
+    ($($pointer:ident),*) => {$(
+        impl<B: MotorBus + ?Sized> MotorBus for $pointer<B> {
+            async fn write(&self, packet: &[u8]) -> Result<(), ReachyError> {
+                (**self).write(packet).await
+            }
+
+            async fn read(&self) -> Result<Vec<u8>, ReachyError> {
+                (**self).read().await
+            }
+
+            async fn sleep(&self, ms: u32) {
+                (**self).sleep(ms).await
+            }
+
+            fn now_ms(&self) -> f64 {
+                (**self).now_ms()
+            }
+
+            fn io_policy(&self) -> IoPolicy {
+                (**self).io_policy()
+            }
+
+            fn set_io_policy(&self, policy: IoPolicy) {
+                (**self).set_io_policy(policy)
+            }
+
+            async fn read_timeout(&self, ms: u32) -> Result<Option<Vec<u8>>, ReachyError> {
+                (**self).read_timeout(ms).await
+            }
+
+            fn arbiter(&self) -> Option<&Arbiter> {
+                (**self).arbiter()
+            }
+        }
+    )*};
+}
+
+impl_shared_bus!(Arc, Rc);
+
+// ============================================================================
+// Arbitration
+// ============================================================================
+
+/// Order in which waiting exchanges get the bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    /// Reads and pings, e.g. polling the head pose
+    Telemetry,
+    /// Writes, reboots and other commands changing the motors' state
+    Command,
+}
+
+impl Priority {
+    /// Priority of an instruction packet: [`Command`](Self::Command) for
+    /// instructions changing the motors' state, [`Telemetry`](Self::Telemetry)
+    /// otherwise.
+    pub fn of(packet: &[u8]) -> Self {
+        match packet.get(7).copied() {
+            Some(
+                instruction::WRITE
+                | instruction::REG_WRITE
+                | instruction::ACTION
+                | instruction::FACTORY_RESET
+                | instruction::REBOOT
+                | instruction::SYNC_WRITE
+                | instruction::BULK_WRITE,
+            ) => Priority::Command,
+            _ => Priority::Telemetry,
+        }
+    }
+}
+
+/// An async lock granting the bus to one exchange at a time.
+///
+/// Waiting exchanges are served by [`Priority`], then in arrival order, so
+/// a command issued while telemetry is being polled only waits for the
+/// exchange in progress. Commands issued faster than the bus can serve them
+/// delay telemetry indefinitely.
+///
+/// Single-threaded, like the browser backends.
+#[derive(Debug, Default)]
+pub struct Arbiter {
+    state: RefCell<ArbiterState>,
+}
+
+#[derive(Debug, Default)]
+struct ArbiterState {
+    busy: bool,
+    next_ticket: u64,
+    waiting: Vec<Waiter>,
+}
+
+#[derive(Debug)]
+struct Waiter {
+    ticket: u64,
+    priority: Priority,
+    waker: Option<Waker>,
+}
+
+impl ArbiterState {
+    /// Ticket of the waiter to serve next.
+    fn next_up(&self) -> Option<u64> {
+        self.waiting
+            .iter()
+            .max_by_key(|w| (w.priority, std::cmp::Reverse(w.ticket)))
+            .map(|w| w.ticket)
+    }
+
+    /// Waker of the waiter to serve next, to call once the state is released.
+    fn next_waker(&mut self) -> Option<Waker> {
+        let ticket = self.next_up()?;
+        self.waiting
+            .iter_mut()
+            .find(|w| w.ticket == ticket)
+            .and_then(|w| w.waker.take())
+    }
+}
+
+impl Arbiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether an exchange currently holds the bus.
+    pub fn is_busy(&self) -> bool {
+        self.state.borrow().busy
+    }
+
+    /// Number of exchanges waiting for their turn.
+    pub fn waiting(&self) -> usize {
+        self.state.borrow().waiting.len()
+    }
+
+    /// Wait for the bus; it is released when the returned guard is dropped.
+    pub async fn acquire(&self, priority: Priority) -> ArbiterGuard<'_> {
+        let ticket = {
+            let mut state = self.state.borrow_mut();
+            let ticket = state.next_ticket;
+            state.next_ticket += 1;
+            state.waiting.push(Waiter {
+                ticket,
+                priority,
+                waker: None,
+            });
+            ticket
+        };
+        let mut pending = PendingTurn {
+            arbiter: self,
+            ticket,
+            granted: false,
+        };
+
+        poll_fn(|cx| {
+            let mut state = self.state.borrow_mut();
+            if !state.busy && state.next_up() == Some(ticket) {
+                state.busy = true;
+                state.waiting.retain(|w| w.ticket != ticket);
+                return Poll::Ready(());
+            }
+            if let Some(waiter) = state.waiting.iter_mut().find(|w| w.ticket == ticket) {
+                waiter.waker = Some(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await;
+
+        pending.granted = true;
+        ArbiterGuard { arbiter: self }
+    }
+}
+
+/// Removes a cancelled waiter from the queue.
+struct PendingTurn<'a> {
+    arbiter: &'a Arbiter,
+    ticket: u64,
+    granted: bool,
+}
+
+impl Drop for PendingTurn<'_> {
+    fn drop(&mut self) {
+        if self.granted {
+            return;
+        }
+        let waker = {
+            let mut state = self.arbiter.state.borrow_mut();
+            state.waiting.retain(|w| w.ticket != self.ticket);
+            if state.busy {
+                None
+            } else {
+                state.next_waker()
+            }
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// Exclusive use of the bus, released on drop.
+#[derive(Debug)]
+pub struct ArbiterGuard<'a> {
+    arbiter: &'a Arbiter,
+}
+
+impl Drop for ArbiterGuard<'_> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut state = self.arbiter.state.borrow_mut();
+            state.busy = false;
+            state.next_waker()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// Wait for the bus's turn to send `packet`, if it has an [`Arbiter`].
+async fn take_turn<'a, B: MotorBus + ?Sized>(
+    bus: &'a B,
+    packet: &[u8],
+) -> Option<ArbiterGuard<'a>> {
+    match bus.arbiter() {
+        Some(arbiter) => Some(arbiter.acquire(Priority::of(packet)).await),
+        None => None,
+    }
+}
+
+/// Run [`exchange`], retrying with backoff while responses are missing and
+/// keeping the attempt that got the most.
+async fn exchange_with_retries<B: MotorBus + ?Sized>(
+    bus: &B,
+    packet: &[u8],
+    timeout: u32,
+) -> Result<(usize, Vec<u8>), ReachyError> {
+    let policy = bus.io_policy();
+    let expected = dynamixel::expected_responses(packet);
+    let responders = dynamixel::expected_responders(packet);
+
+    let mut best = (0, Vec::new());
+    for attempt in 0..=policy.retries {
+        if attempt > 0 {
+            bus.sleep(policy.backoff(attempt)).await;
+        }
+
+        let (received, response) =
+            exchange(bus, packet, expected, responders.as_deref(), timeout).await?;
+        if received > best.0 || attempt == 0 {
+            best = (received, response);
+        }
+        if expected.is_none_or(|n| best.0 >= n) {
+            break;
+        }
+    }
+
+    Ok(best)
+}
+
+/// Run one write/read attempt, returning the number of motors that answered
+/// and their concatenated status packets.
+///
+/// Status packets from IDs outside `responders` (e.g. late replies to an
+/// earlier exchange) are dropped; `None` accepts any ID.
+async fn exchange<B: MotorBus + ?Sized>(
+    bus: &B,
+    packet: &[u8],
+    expected: Option<usize>,
+    responders: Option<&[u8]>,
+    timeout: u32,
+) -> Result<(usize, Vec<u8>), ReachyError> {
+    let deadline = bus.now_ms() + timeout as f64;
+
+    {
+        let write = bus.write(packet);
+        let timer = bus.sleep(timeout);
+        futures_util::pin_mut!(write, timer);
+        match futures_util::future::select(write, timer).await {
+            Either::Left((result, _)) => result?,
+            Either::Right(((), _)) => {
+                return Err(ReachyError::Timeout {
+                    timeout_ms: timeout,
+                    attempts: 1,
+                })
+            }
+        }
+    }
+
+    let mut framer = PacketFramer::new();
+    let mut response = Vec::new();
+    let mut answered: Vec<u8> = Vec::new();
+
+    while expected.is_none_or(|n| answered.len() < n) {
+        let remaining = deadline - bus.now_ms();
+        if remaining <= 0.0 {
+            break;
+        }
+
+        match bus.read_timeout(remaining.ceil() as u32).await? {
+            Some(chunk) => {
+                for status in framer.push(&chunk) {
+                    let id = status[4];
+                    if responders.is_some_and(|ids| !ids.contains(&id)) {
+                        continue;
+                    }
+                    response.extend(status);
+                    if !answered.contains(&id) {
+                        answered.push(id);
+                    }
+                }
+            }
+            None => break,
+        }
+    }
+
+    Ok((answered.len(), response))
+}
@@ -0,0 +1,428 @@
+//! Recording and replay of motor bus traffic.
+//!
+//! A [`Capture`] stores every chunk of bytes written to (TX) or read from
+//! (RX) the motor bus, with a timestamp. It exports to JSON Lines, one chunk
+//! per line:
+//!
+//! ```text
+//! {"t_ms":0.0,"dir":"tx","data":"fffffd00fe0700820000840004000b0c..."}
+//! {"t_ms":1.5,"dir":"rx","data":"fffffd000b080055000008000000..."}
+//! ```
+//!
+//! Captures can be pretty-printed with [`Capture::describe`], and fed back to
+//! the robot logic with [`ReplayBus`] to turn a user's bug report into a
+//! regression test. [`TapBus`] records any [`MotorBus`]; in the browser, use
+//! `start_bus_capture()` and `export_bus_capture()`.
+
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::bus::{Arbiter, IoPolicy, MotorBus};
+use crate::dynamixel::{decode_packets, describe_packets, PacketDescriber};
+use crate::error::ReachyError;
+
+/// Default maximum number of chunks kept by a [`Capture`]
+pub const DEFAULT_CAPTURE_LIMIT: usize = 100_000;
+
+/// Direction of a captured chunk, seen from the host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    /// Written to the bus
+    Tx,
+    /// Read from the bus
+    Rx,
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Direction::Tx => "TX",
+            Direction::Rx => "RX",
+        })
+    }
+}
+
+/// One chunk of captured bytes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaptureRecord {
+    /// Time since the first chunk of the capture, in milliseconds
+    pub t_ms: f64,
+    pub dir: Direction,
+    /// Bytes as sent or received, hex-encoded in JSON
+    #[serde(with = "hex_bytes")]
+    pub data: Vec<u8>,
+}
+
+/// A bounded buffer of captured chunks; the oldest are dropped when full.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Capture {
+    records: VecDeque<CaptureRecord>,
+    limit: usize,
+    /// Bus time of the first chunk
+    origin_ms: Option<f64>,
+    dropped: usize,
+}
+
+impl Default for Capture {
+    fn default() -> Self {
+        Self::with_limit(DEFAULT_CAPTURE_LIMIT)
+    }
+}
+
+impl Capture {
+    /// Empty capture keeping up to [`DEFAULT_CAPTURE_LIMIT`] chunks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Empty capture keeping up to `limit` chunks.
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            records: VecDeque::new(),
+            limit: limit.max(1),
+            origin_ms: None,
+            dropped: 0,
+        }
+    }
+
+    /// Record a chunk. `now_ms` is the bus clock ([`MotorBus::now_ms`]).
+    pub fn record(&mut self, dir: Direction, now_ms: f64, data: &[u8]) {
+        let origin = *self.origin_ms.get_or_insert(now_ms);
+        if self.records.len() == self.limit {
+            self.records.pop_front();
+            self.dropped += 1;
+        }
+        self.records.push_back(CaptureRecord {
+            t_ms: now_ms - origin,
+            dir,
+            data: data.to_vec(),
+        });
+    }
+
+    /// Captured chunks, oldest first.
+    pub fn records(&self) -> impl Iterator<Item = &CaptureRecord> {
+        self.records.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Number of chunks dropped because the capture was full.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Export as JSON Lines, one chunk per line.
+    pub fn to_jsonl(&self) -> String {
+        self.records
+            .iter()
+            .filter_map(|record| serde_json::to_string(record).ok())
+            .map(|line| line + "\n")
+            .collect()
+    }
+
+    /// Load a capture exported with [`to_jsonl`](Self::to_jsonl).
+    ///
+    /// # Errors
+    /// `ReachyError::InvalidArgument` naming the first invalid line.
+    pub fn from_jsonl(text: &str) -> Result<Self, ReachyError> {
+        let mut capture = Self::default();
+        for (n, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: CaptureRecord = serde_json::from_str(line).map_err(|e| {
+                ReachyError::InvalidArgument(format!("Capture line {}: {}", n + 1, e))
+            })?;
+            capture.origin_ms.get_or_insert(0.0);
+            capture.records.push_back(record);
+        }
+        capture.limit = capture.limit.max(capture.records.len());
+        Ok(capture)
+    }
+
+    /// Decode the capture into one line per packet, e.g.
+    /// `    12.500 ms  TX  SYNC_READ Present Position from [11, 12]`.
+    ///
+    /// Consecutive RX chunks are joined before decoding, so status packets
+    /// split across reads are shown once, at the time of their first chunk.
+    pub fn describe(&self) -> String {
+        let mut describer = PacketDescriber::new();
+        let mut lines = Vec::new();
+
+        let mut records = self.records.iter().peekable();
+        while let Some(first) = records.next() {
+            let mut data = first.data.clone();
+            if first.dir == Direction::Rx {
+                while let Some(next) = records.next_if(|r| r.dir == Direction::Rx) {
+                    data.extend_from_slice(&next.data);
+                }
+            }
+
+            let packets = decode_packets(&data);
+            if packets.is_empty() {
+                lines.push(format!(
+                    "{:>10.3} ms  {}  {} bytes, no valid packet",
+                    first.t_ms,
+                    first.dir,
+                    data.len()
+                ));
+            }
+            for packet in &packets {
+                lines.push(format!(
+                    "{:>10.3} ms  {}  {}",
+                    first.t_ms,
+                    first.dir,
+                    describer.describe(packet)
+                ));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+// ============================================================================
+// Tap
+// ============================================================================
+
+/// A [`MotorBus`] wrapper recording all traffic of the bus it wraps.
+pub struct TapBus<B> {
+    inner: B,
+    capture: RefCell<Capture>,
+}
+
+impl<B: MotorBus> TapBus<B> {
+    /// Record the traffic of `inner` into a new [`Capture`].
+    pub fn new(inner: B) -> Self {
+        Self::with_capture(inner, Capture::new())
+    }
+
+    /// Record the traffic of `inner` into `capture`.
+    pub fn with_capture(inner: B, capture: Capture) -> Self {
+        Self {
+            inner,
+            capture: RefCell::new(capture),
+        }
+    }
+
+    /// The wrapped bus.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// A copy of the capture so far.
+    pub fn capture(&self) -> Capture {
+        self.capture.borrow().clone()
+    }
+
+    /// Take the capture so far, starting a new one.
+    pub fn take_capture(&self) -> Capture {
+        self.capture.take()
+    }
+
+    fn record(&self, dir: Direction, data: &[u8]) {
+        self.capture
+            .borrow_mut()
+            .record(dir, self.inner.now_ms(), data);
+    }
+}
+
+impl<B: MotorBus> MotorBus for TapBus<B> {
+    async fn write(&self, packet: &[u8]) -> Result<(), ReachyError> {
+        self.record(Direction::Tx, packet);
+        self.inner.write(packet).await
+    }
+
+    async fn read(&self) -> Result<Vec<u8>, ReachyError> {
+        let data = self.inner.read().await?;
+        self.record(Direction::Rx, &data);
+        Ok(data)
+    }
+
+    async fn read_timeout(&self, ms: u32) -> Result<Option<Vec<u8>>, ReachyError> {
+        let data = self.inner.read_timeout(ms).await?;
+        if let Some(data) = data.as_ref().filter(|d| !d.is_empty()) {
+            self.record(Direction::Rx, data);
+        }
+        Ok(data)
+    }
+
+    async fn sleep(&self, ms: u32) {
+        self.inner.sleep(ms).await
+    }
+
+    fn now_ms(&self) -> f64 {
+        self.inner.now_ms()
+    }
+
+    fn io_policy(&self) -> IoPolicy {
+        self.inner.io_policy()
+    }
+
+    fn set_io_policy(&self, policy: IoPolicy) {
+        self.inner.set_io_policy(policy)
+    }
+
+    fn arbiter(&self) -> Option<&Arbiter> {
+        self.inner.arbiter()
+    }
+}
+
+// ============================================================================
+// Replay
+// ============================================================================
+
+/// A [`MotorBus`] answering from a [`Capture`], for regression tests.
+///
+/// Each write must match the next TX chunk of the capture; the RX chunks
+/// recorded after it are then returned by reads, with their recorded delays.
+/// Time is virtual, like on [`SimulatedBus`](crate::sim::SimulatedBus), so
+/// recorded timeouts happen again without waiting.
+pub struct ReplayBus {
+    records: Vec<CaptureRecord>,
+    /// Index of the next record to replay
+    cursor: Cell<usize>,
+    /// Chunks waiting to be read, with the virtual time they become readable
+    pending: RefCell<VecDeque<(f64, Vec<u8>)>>,
+    clock: Cell<f64>,
+    policy: Cell<IoPolicy>,
+}
+
+impl ReplayBus {
+    pub fn new(capture: &Capture) -> Self {
+        Self {
+            records: capture.records().cloned().collect(),
+            cursor: Cell::new(0),
+            pending: RefCell::new(VecDeque::new()),
+            clock: Cell::new(0.0),
+            policy: Cell::new(IoPolicy::default()),
+        }
+    }
+
+    /// Number of recorded writes not replayed yet.
+    pub fn remaining_writes(&self) -> usize {
+        self.records[self.cursor.get()..]
+            .iter()
+            .filter(|r| r.dir == Direction::Tx)
+            .count()
+    }
+
+    /// Pop the next chunk if it arrives within `ms` milliseconds, advancing
+    /// virtual time to its arrival.
+    fn next_chunk(&self, ms: f64) -> Option<Vec<u8>> {
+        let now = self.clock.get();
+        let ready = self.pending.borrow().front().map(|(t, _)| *t)?;
+        if ready > now + ms {
+            return None;
+        }
+        self.clock.set(now.max(ready));
+        self.pending
+            .borrow_mut()
+            .pop_front()
+            .map(|(_, chunk)| chunk)
+    }
+}
+
+impl MotorBus for ReplayBus {
+    /// # Errors
+    /// `ReachyError::InvalidState` if `packet` differs from the next recorded
+    /// write, or if the capture has no writes left.
+    async fn write(&self, packet: &[u8]) -> Result<(), ReachyError> {
+        let start = self.cursor.get();
+        let Some(offset) = self.records[start..]
+            .iter()
+            .position(|r| r.dir == Direction::Tx)
+        else {
+            return Err(ReachyError::InvalidState(format!(
+                "Replay: capture has no more writes, got {:?}",
+                describe_packets(packet)
+            )));
+        };
+
+        let tx = &self.records[start + offset];
+        if tx.data != packet {
+            return Err(ReachyError::InvalidState(format!(
+                "Replay: expected {:?}, got {:?}",
+                describe_packets(&tx.data),
+                describe_packets(packet)
+            )));
+        }
+
+        let end = self.records[start + offset + 1..]
+            .iter()
+            .position(|r| r.dir == Direction::Tx)
+            .map_or(self.records.len(), |i| start + offset + 1 + i);
+
+        let now = self.clock.get();
+        let mut pending = self.pending.borrow_mut();
+        for rx in &self.records[start + offset + 1..end] {
+            pending.push_back((now + (rx.t_ms - tx.t_ms).max(0.0), rx.data.clone()));
+        }
+        self.cursor.set(end);
+        Ok(())
+    }
+
+    async fn read(&self) -> Result<Vec<u8>, ReachyError> {
+        self.next_chunk(f64::INFINITY)
+            .ok_or_else(|| ReachyError::Transport("No data left in the capture".into()))
+    }
+
+    async fn read_timeout(&self, ms: u32) -> Result<Option<Vec<u8>>, ReachyError> {
+        let chunk = self.next_chunk(ms as f64);
+        if chunk.is_none() {
+            self.clock.set(self.clock.get() + ms as f64);
+        }
+        Ok(chunk)
+    }
+
+    async fn sleep(&self, ms: u32) {
+        self.clock.set(self.clock.get() + ms as f64);
+    }
+
+    fn now_ms(&self) -> f64 {
+        self.clock.get()
+    }
+
+    fn io_policy(&self) -> IoPolicy {
+        self.policy.get()
+    }
+
+    fn set_io_policy(&self, policy: IoPolicy) {
+        self.policy.set(policy);
+    }
+}
+
+/// Serialize bytes as a lowercase hex string.
+mod hex_bytes {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        let hex: String = data.iter().map(|b| format!("{:02x}", b)).collect();
+        serializer.serialize_str(&hex)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        if hex.len() % 2 != 0 {
+            return Err(de::Error::custom("odd number of hex digits"));
+        }
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| {
+                hex.get(i..i + 2)
+                    .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                    .ok_or_else(|| de::Error::custom("invalid hex digit"))
+            })
+            .collect()
+    }
+}
@@ -11,8 +11,15 @@
 //! | 126     | Present Load        | 2    | R      |
 //! | 132     | Present Position    | 4    | R      |
 //! | 146     | Present Temperature | 1    | R      |
+//!
+//! The full table is available as typed registers in [`register`] (and as
+//! runtime metadata in [`CONTROL_TABLE`]), which can be used with the generic
+//! [`build_sync_read`], [`build_sync_write`] and [`parse_register_packets`].
+
+use std::fmt;
+use std::sync::atomic::{AtomicU32, Ordering};
 
-use wasm_bindgen::JsValue;
+use crate::error::ReachyError;
 
 // ============================================================================
 // Constants
@@ -21,6 +28,13 @@ use wasm_bindgen::JsValue;
 /// Broadcast ID (all motors)
 pub const BROADCAST_ID: u8 = 0xFE;
 
+/// Highest assignable motor ID (0xFD-0xFF are reserved)
+pub const MAX_ID: u8 = 0xFC;
+
+/// Delay per ID before a motor answers a broadcast PING: motor `n` replies
+/// about `3 * n` ms after the instruction, so the replies don't collide
+pub const BROADCAST_PING_INTERVAL_MS: u32 = 3;
+
 /// XL330 control table addresses
 pub mod address {
     pub const TORQUE_ENABLE: u16 = 64;
@@ -32,14 +46,209 @@ pub mod address {
 }
 
 /// Dynamixel Protocol 2.0 instruction codes
-mod instruction {
+pub mod instruction {
+    pub const PING: u8 = 0x01;
     pub const READ: u8 = 0x02;
+    pub const WRITE: u8 = 0x03;
+    pub const REG_WRITE: u8 = 0x04;
+    pub const ACTION: u8 = 0x05;
+    pub const FACTORY_RESET: u8 = 0x06;
     pub const REBOOT: u8 = 0x08;
     pub const SYNC_READ: u8 = 0x82;
     pub const SYNC_WRITE: u8 = 0x83;
+    pub const FAST_SYNC_READ: u8 = 0x8A;
+    pub const BULK_READ: u8 = 0x92;
+    pub const BULK_WRITE: u8 = 0x93;
     pub const STATUS: u8 = 0x55;
 }
 
+// ============================================================================
+// Control Table
+// ============================================================================
+
+/// Register access mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    ReadOnly,
+    ReadWrite,
+}
+
+/// Metadata describing a single control table register.
+///
+/// Raw values are converted to physical units as `(raw - offset) * scale`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RegisterInfo {
+    pub name: &'static str,
+    pub address: u16,
+    /// Size in bytes (1, 2 or 4)
+    pub size: u16,
+    pub signed: bool,
+    pub access: Access,
+    pub scale: f32,
+    pub offset: i32,
+    pub unit: &'static str,
+}
+
+impl RegisterInfo {
+    /// Decode a little-endian register value, sign-extending if needed.
+    #[inline]
+    pub fn decode(&self, bytes: &[u8]) -> i32 {
+        match (self.size, self.signed) {
+            (1, false) => bytes[0] as i32,
+            (1, true) => bytes[0] as i8 as i32,
+            (2, false) => u16::from_le_bytes([bytes[0], bytes[1]]) as i32,
+            (2, true) => i16::from_le_bytes([bytes[0], bytes[1]]) as i32,
+            _ => i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        }
+    }
+
+    /// Encode a raw value as `size` little-endian bytes.
+    #[inline]
+    pub fn encode(&self, raw: i32) -> Vec<u8> {
+        raw.to_le_bytes()[..self.size as usize].to_vec()
+    }
+
+    /// Convert a raw value to physical units.
+    #[inline]
+    pub fn to_unit(&self, raw: i32) -> f32 {
+        (raw - self.offset) as f32 * self.scale
+    }
+
+    /// Convert a value in physical units to the nearest raw value.
+    #[inline]
+    pub fn from_unit(&self, value: f32) -> i32 {
+        self.offset + (value / self.scale).round() as i32
+    }
+
+    /// Whether the register is in the EEPROM area, which can only be written
+    /// with torque disabled.
+    #[inline]
+    pub fn is_eeprom(&self) -> bool {
+        self.address < address::TORQUE_ENABLE
+    }
+}
+
+/// A typed control table register.
+///
+/// # Example
+/// ```ignore
+/// let packet = build_sync_read::<register::PresentVelocity>(&[11, 12]);
+/// ```
+pub trait Register {
+    const INFO: RegisterInfo;
+}
+
+macro_rules! control_table {
+    ($(
+        $(#[$meta:meta])*
+        $ty:ident = ($name:literal, $addr:expr, $size:expr, $signed:expr, $access:ident, $scale:expr, $offset:expr, $unit:literal);
+    )*) => {
+        /// Typed XL330 control table registers
+        pub mod register {
+            use super::*;
+
+            $(
+                $(#[$meta])*
+                #[derive(Debug, Clone, Copy)]
+                pub struct $ty;
+
+                impl Register for $ty {
+                    const INFO: RegisterInfo = RegisterInfo {
+                        name: $name,
+                        address: $addr,
+                        size: $size,
+                        signed: $signed,
+                        access: Access::$access,
+                        scale: $scale,
+                        offset: $offset,
+                        unit: $unit,
+                    };
+                }
+            )*
+        }
+
+        /// Full XL330 control table, ordered by address.
+        pub const CONTROL_TABLE: &[RegisterInfo] = &[$(<register::$ty as Register>::INFO),*];
+    };
+}
+
+control_table! {
+    // EEPROM area (only writable with torque disabled)
+    ModelNumber = ("Model Number", 0, 2, false, ReadOnly, 1.0, 0, "");
+    ModelInformation = ("Model Information", 2, 4, false, ReadOnly, 1.0, 0, "");
+    FirmwareVersion = ("Firmware Version", 6, 1, false, ReadOnly, 1.0, 0, "");
+    Id = ("ID", 7, 1, false, ReadWrite, 1.0, 0, "");
+    BaudRate = ("Baud Rate", 8, 1, false, ReadWrite, 1.0, 0, "");
+    /// Return delay in microseconds (2 µs per tick)
+    ReturnDelayTime = ("Return Delay Time", 9, 1, false, ReadWrite, 2.0, 0, "us");
+    DriveMode = ("Drive Mode", 10, 1, false, ReadWrite, 1.0, 0, "");
+    OperatingMode = ("Operating Mode", 11, 1, false, ReadWrite, 1.0, 0, "");
+    SecondaryId = ("Secondary ID", 12, 1, false, ReadWrite, 1.0, 0, "");
+    ProtocolType = ("Protocol Type", 13, 1, false, ReadWrite, 1.0, 0, "");
+    HomingOffset = ("Homing Offset", 20, 4, true, ReadWrite, RAD_PER_TICK, 0, "rad");
+    MovingThreshold = ("Moving Threshold", 24, 4, false, ReadWrite, RPM_PER_TICK, 0, "rpm");
+    TemperatureLimit = ("Temperature Limit", 31, 1, false, ReadWrite, 1.0, 0, "C");
+    MaxVoltageLimit = ("Max Voltage Limit", 32, 2, false, ReadWrite, 0.1, 0, "V");
+    MinVoltageLimit = ("Min Voltage Limit", 34, 2, false, ReadWrite, 0.1, 0, "V");
+    PwmLimit = ("PWM Limit", 36, 2, false, ReadWrite, 0.113, 0, "%");
+    CurrentLimit = ("Current Limit", 38, 2, false, ReadWrite, 1.0, 0, "mA");
+    VelocityLimit = ("Velocity Limit", 44, 4, false, ReadWrite, RPM_PER_TICK, 0, "rpm");
+    MaxPositionLimit = ("Max Position Limit", 48, 4, false, ReadWrite, RAD_PER_TICK, 2048, "rad");
+    MinPositionLimit = ("Min Position Limit", 52, 4, false, ReadWrite, RAD_PER_TICK, 2048, "rad");
+    StartupConfiguration = ("Startup Configuration", 60, 1, false, ReadWrite, 1.0, 0, "");
+    PwmSlope = ("PWM Slope", 62, 1, false, ReadWrite, 1.0, 0, "");
+    Shutdown = ("Shutdown", 63, 1, false, ReadWrite, 1.0, 0, "");
+
+    // RAM area
+    TorqueEnable = ("Torque Enable", 64, 1, false, ReadWrite, 1.0, 0, "");
+    Led = ("LED", 65, 1, false, ReadWrite, 1.0, 0, "");
+    StatusReturnLevel = ("Status Return Level", 68, 1, false, ReadWrite, 1.0, 0, "");
+    RegisteredInstruction = ("Registered Instruction", 69, 1, false, ReadOnly, 1.0, 0, "");
+    HardwareErrorStatus = ("Hardware Error Status", 70, 1, false, ReadOnly, 1.0, 0, "");
+    VelocityIGain = ("Velocity I Gain", 76, 2, false, ReadWrite, 1.0, 0, "");
+    VelocityPGain = ("Velocity P Gain", 78, 2, false, ReadWrite, 1.0, 0, "");
+    PositionDGain = ("Position D Gain", 80, 2, false, ReadWrite, 1.0, 0, "");
+    PositionIGain = ("Position I Gain", 82, 2, false, ReadWrite, 1.0, 0, "");
+    PositionPGain = ("Position P Gain", 84, 2, false, ReadWrite, 1.0, 0, "");
+    Feedforward2ndGain = ("Feedforward 2nd Gain", 88, 2, false, ReadWrite, 1.0, 0, "");
+    Feedforward1stGain = ("Feedforward 1st Gain", 90, 2, false, ReadWrite, 1.0, 0, "");
+    /// Bus watchdog timeout (20 ms per tick, 0 disables)
+    BusWatchdog = ("Bus Watchdog", 98, 1, false, ReadWrite, 20.0, 0, "ms");
+    GoalPwm = ("Goal PWM", 100, 2, true, ReadWrite, 0.113, 0, "%");
+    GoalCurrent = ("Goal Current", 102, 2, true, ReadWrite, 1.0, 0, "mA");
+    GoalVelocity = ("Goal Velocity", 104, 4, true, ReadWrite, RPM_PER_TICK, 0, "rpm");
+    /// Profile acceleration (velocity-based profile units: 214.577 rev/min²)
+    ProfileAcceleration = ("Profile Acceleration", 108, 4, false, ReadWrite, 214.577, 0, "rev/min^2");
+    /// Profile velocity (velocity-based profile units: 0.229 rpm)
+    ProfileVelocity = ("Profile Velocity", 112, 4, false, ReadWrite, RPM_PER_TICK, 0, "rpm");
+    GoalPosition = ("Goal Position", 116, 4, true, ReadWrite, RAD_PER_TICK, 2048, "rad");
+    RealtimeTick = ("Realtime Tick", 120, 2, false, ReadOnly, 1.0, 0, "ms");
+    Moving = ("Moving", 122, 1, false, ReadOnly, 1.0, 0, "");
+    MovingStatus = ("Moving Status", 123, 1, false, ReadOnly, 1.0, 0, "");
+    PresentPwm = ("Present PWM", 124, 2, true, ReadOnly, 0.113, 0, "%");
+    /// Present Current read as a load (percentage of max torque × 10)
+    ///
+    /// Shares its address with [`PresentCurrent`]; this is the unit used by
+    /// the load APIs.
+    PresentLoad = ("Present Load", 126, 2, true, ReadOnly, 0.1, 0, "%");
+    /// Present Current in mA (same address as [`PresentLoad`])
+    PresentCurrent = ("Present Current", 126, 2, true, ReadOnly, 1.0, 0, "mA");
+    PresentVelocity = ("Present Velocity", 128, 4, true, ReadOnly, RPM_PER_TICK, 0, "rpm");
+    PresentPosition = ("Present Position", 132, 4, true, ReadOnly, RAD_PER_TICK, 2048, "rad");
+    VelocityTrajectory = ("Velocity Trajectory", 136, 4, true, ReadOnly, RPM_PER_TICK, 0, "rpm");
+    PositionTrajectory = ("Position Trajectory", 140, 4, true, ReadOnly, RAD_PER_TICK, 2048, "rad");
+    PresentInputVoltage = ("Present Input Voltage", 144, 2, false, ReadOnly, 0.1, 0, "V");
+    PresentTemperature = ("Present Temperature", 146, 1, false, ReadOnly, 1.0, 0, "C");
+    BackupReady = ("Backup Ready", 147, 1, false, ReadOnly, 1.0, 0, "");
+}
+
+/// Look up a register by address.
+///
+/// Address 126 resolves to `Present Load`, the unit used elsewhere in this crate.
+pub fn register_at(addr: u16) -> Option<&'static RegisterInfo> {
+    CONTROL_TABLE.iter().find(|r| r.address == addr)
+}
+
 // ============================================================================
 // CRC Calculation
 // ============================================================================
@@ -72,13 +281,58 @@ static CRC_TABLE: [u16; 256] = [
 
 /// Calculate CRC16 for Dynamixel Protocol 2.0
 #[inline]
-fn crc16(data: &[u8]) -> u16 {
+pub fn crc16(data: &[u8]) -> u16 {
     data.iter().fold(0u16, |crc, &byte| {
         let idx = ((crc >> 8) ^ byte as u16) as u8;
         (crc << 8) ^ CRC_TABLE[idx as usize]
     })
 }
 
+/// Number of status packets rejected because of a CRC mismatch
+static REJECTED_PACKETS: AtomicU32 = AtomicU32::new(0);
+
+/// Get the number of status packets rejected because of a CRC mismatch.
+pub fn rejected_packet_count() -> u32 {
+    REJECTED_PACKETS.load(Ordering::Relaxed)
+}
+
+/// Reset the rejected packet counter.
+pub fn reset_rejected_packet_count() {
+    REJECTED_PACKETS.store(0, Ordering::Relaxed);
+}
+
+// ============================================================================
+// Byte Stuffing
+// ============================================================================
+
+/// Apply Protocol 2.0 byte stuffing (FF FF FD -> FF FF FD FD).
+///
+/// Prevents the header pattern from appearing inside the packet body.
+fn add_stuffing(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    for &byte in data {
+        out.push(byte);
+        if out.ends_with(&[0xFF, 0xFF, 0xFD]) {
+            out.push(0xFD);
+        }
+    }
+    out
+}
+
+/// Remove Protocol 2.0 byte stuffing (FF FF FD FD -> FF FF FD).
+fn remove_stuffing(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    let mut i = 0;
+    while i < data.len() {
+        out.push(data[i]);
+        if out.ends_with(&[0xFF, 0xFF, 0xFD]) && data.get(i + 1) == Some(&0xFD) {
+            i += 1;
+        }
+        i += 1;
+    }
+    out
+}
+
 // ============================================================================
 // Packet Builder
 // ============================================================================
@@ -124,16 +378,6 @@ impl PacketBuilder {
         self
     }
 
-    /// Add an i32 parameter (little-endian)
-    #[inline]
-    fn i32_le(mut self, val: i32) -> Self {
-        self.buf.push((val & 0xFF) as u8);
-        self.buf.push(((val >> 8) & 0xFF) as u8);
-        self.buf.push(((val >> 16) & 0xFF) as u8);
-        self.buf.push(((val >> 24) & 0xFF) as u8);
-        self
-    }
-
     /// Add raw bytes
     #[inline]
     fn bytes(mut self, data: &[u8]) -> Self {
@@ -141,9 +385,19 @@ impl PacketBuilder {
         self
     }
 
-    /// Finalize packet by appending CRC
+    /// Finalize packet by applying byte stuffing and appending CRC
     #[inline]
     fn build(mut self) -> Vec<u8> {
+        // Stuffing covers instruction + parameters; the length field counts stuffed bytes
+        let stuffed = add_stuffing(&self.buf[7..]);
+        if stuffed.len() != self.buf.len() - 7 {
+            let len = stuffed.len() as u16 + 2; // instr + params + crc(2)
+            self.buf.truncate(5);
+            self.buf.push((len & 0xFF) as u8);
+            self.buf.push((len >> 8) as u8);
+            self.buf.extend_from_slice(&stuffed);
+        }
+
         let crc = crc16(&self.buf);
         self.buf.push((crc & 0xFF) as u8);
         self.buf.push((crc >> 8) as u8);
@@ -155,6 +409,24 @@ impl PacketBuilder {
 // Packet Building Functions
 // ============================================================================
 
+/// Build PING packet.
+///
+/// Use [`BROADCAST_ID`] to ping every motor on the bus: each one answers with
+/// its own status packet (see [`parse_ping_responses`]).
+#[inline]
+pub fn build_ping_packet(motor_id: u8) -> Vec<u8> {
+    PacketBuilder::new(motor_id, 10)
+        .instruction(instruction::PING, 0)
+        .build()
+}
+
+/// Time in ms until every motor up to `last_id` has answered a broadcast
+/// PING, not counting transport latency.
+#[inline]
+pub fn broadcast_ping_window_ms(last_id: u8) -> u32 {
+    BROADCAST_PING_INTERVAL_MS * (last_id as u32 + 1)
+}
+
 /// Build READ packet for a single motor.
 ///
 /// # Example
@@ -170,6 +442,163 @@ pub fn build_read_packet(motor_id: u8, addr: u16, length: u16) -> Vec<u8> {
         .build()
 }
 
+/// Build WRITE packet for a single motor.
+///
+/// # Example
+/// ```ignore
+/// let packet = build_write_packet(11, address::TORQUE_ENABLE, &[0]);
+/// ```
+pub fn build_write_packet(motor_id: u8, addr: u16, data: &[u8]) -> Vec<u8> {
+    PacketBuilder::new(motor_id, 12 + data.len())
+        .instruction(instruction::WRITE, 2 + data.len() as u16)
+        .u16_le(addr)
+        .bytes(data)
+        .build()
+}
+
+/// Build WRITE for any register of a single motor.
+///
+/// # Example
+/// ```ignore
+/// let packet = build_write::<register::ReturnDelayTime>(1, 0);
+/// ```
+#[inline]
+pub fn build_write<R: Register>(motor_id: u8, raw: i32) -> Vec<u8> {
+    build_write_info(motor_id, &R::INFO, raw)
+}
+
+/// Build WRITE for a register described at runtime.
+pub fn build_write_info(motor_id: u8, reg: &RegisterInfo, raw: i32) -> Vec<u8> {
+    build_write_packet(motor_id, reg.address, &reg.encode(raw))
+}
+
+/// Build the combined status packet answering a FAST_SYNC_READ.
+///
+/// Takes `(id, error, data)` segments in answer order; see
+/// [`parse_fast_sync_read`] for the layout. Mostly useful to emulate motors.
+pub fn build_fast_sync_status_packet(segments: &[(u8, u8, &[u8])]) -> Vec<u8> {
+    // instr + n*(err + id + data + crc(2)); the last segment's CRC is the packet CRC
+    let length = 1 + segments
+        .iter()
+        .map(|(_, _, data)| data.len() + 4)
+        .sum::<usize>();
+    let mut packet = vec![
+        0xFF,
+        0xFF,
+        0xFD,
+        0x00,
+        BROADCAST_ID,
+        (length & 0xFF) as u8,
+        (length >> 8) as u8,
+        instruction::STATUS,
+    ];
+
+    for (id, error, data) in segments {
+        packet.push(*error);
+        packet.push(*id);
+        packet.extend_from_slice(data);
+        let crc = crc16(&packet);
+        packet.extend_from_slice(&crc.to_le_bytes());
+    }
+
+    packet
+}
+
+/// Build REG_WRITE packet for a single motor.
+///
+/// The motor acknowledges and stores the write without applying it, until an
+/// ACTION packet (see [`build_action_packet`]) triggers it. Only one write can
+/// be registered per motor; a new REG_WRITE replaces the previous one.
+///
+/// # Example
+/// ```ignore
+/// let packet = build_reg_write_packet(11, address::GOAL_POSITION, &2048i32.to_le_bytes());
+/// ```
+pub fn build_reg_write_packet(motor_id: u8, addr: u16, data: &[u8]) -> Vec<u8> {
+    PacketBuilder::new(motor_id, 12 + data.len())
+        .instruction(instruction::REG_WRITE, 2 + data.len() as u16)
+        .u16_le(addr)
+        .bytes(data)
+        .build()
+}
+
+/// Build REG_WRITE for any register of a single motor.
+#[inline]
+pub fn build_reg_write<R: Register>(motor_id: u8, raw: i32) -> Vec<u8> {
+    build_reg_write_info(motor_id, &R::INFO, raw)
+}
+
+/// Build REG_WRITE for a register described at runtime.
+pub fn build_reg_write_info(motor_id: u8, reg: &RegisterInfo, raw: i32) -> Vec<u8> {
+    build_reg_write_packet(motor_id, reg.address, &reg.encode(raw))
+}
+
+/// Build ACTION packet, applying writes registered with REG_WRITE.
+///
+/// Use [`BROADCAST_ID`] to trigger every motor at once; broadcast ACTION gets
+/// no response.
+#[inline]
+pub fn build_action_packet(motor_id: u8) -> Vec<u8> {
+    PacketBuilder::new(motor_id, 10)
+        .instruction(instruction::ACTION, 0)
+        .build()
+}
+
+/// Build FACTORY_RESET packet for a single motor.
+///
+/// Motors ignore a broadcast FACTORY_RESET in [`FactoryResetMode::All`] mode.
+#[inline]
+pub fn build_factory_reset_packet(motor_id: u8, mode: FactoryResetMode) -> Vec<u8> {
+    PacketBuilder::new(motor_id, 11)
+        .instruction(instruction::FACTORY_RESET, 1)
+        .u8(mode as u8)
+        .build()
+}
+
+/// Build STATUS packet (motor response) for a single motor.
+///
+/// Mostly useful to emulate motors, e.g. in tests.
+pub fn build_status_packet(motor_id: u8, error: u8, params: &[u8]) -> Vec<u8> {
+    PacketBuilder::new(motor_id, 11 + params.len())
+        .instruction(instruction::STATUS, 1 + params.len() as u16)
+        .u8(error)
+        .bytes(params)
+        .build()
+}
+
+/// Which settings FACTORY_RESET restores to their factory defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FactoryResetMode {
+    /// Everything, including ID (back to 1) and baud rate (back to 57600)
+    All = 0xFF,
+    /// Everything except the ID
+    ExceptId = 0x01,
+    /// Everything except the ID and baud rate
+    ExceptIdAndBaudRate = 0x02,
+}
+
+impl FactoryResetMode {
+    /// Parse a mode name: `"all"`, `"except_id"` or `"except_id_and_baud"`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "all" => Some(FactoryResetMode::All),
+            "except_id" => Some(FactoryResetMode::ExceptId),
+            "except_id_and_baud" => Some(FactoryResetMode::ExceptIdAndBaudRate),
+            _ => None,
+        }
+    }
+
+    /// Whether the motor keeps its current ID after the reset.
+    pub fn keeps_id(self) -> bool {
+        self != FactoryResetMode::All
+    }
+
+    /// Whether the motor keeps its current baud rate after the reset.
+    pub fn keeps_baud_rate(self) -> bool {
+        self == FactoryResetMode::ExceptIdAndBaudRate
+    }
+}
+
 /// Build REBOOT packet for a single motor.
 #[inline]
 pub fn build_reboot_packet(motor_id: u8) -> Vec<u8> {
@@ -178,51 +607,96 @@ pub fn build_reboot_packet(motor_id: u8) -> Vec<u8> {
         .build()
 }
 
-/// Build SYNC_READ for Present Position (address 132, 4 bytes).
-pub fn build_sync_current_position(motor_ids: &[u8]) -> Vec<u8> {
+/// Build SYNC_READ for any register from multiple motors.
+///
+/// # Example
+/// ```ignore
+/// let packet = build_sync_read::<register::PresentVelocity>(&[11, 12, 13]);
+/// ```
+pub fn build_sync_read<R: Register>(motor_ids: &[u8]) -> Vec<u8> {
+    build_sync_read_info(&R::INFO, motor_ids)
+}
+
+/// Build SYNC_READ for a register described at runtime.
+pub fn build_sync_read_info(reg: &RegisterInfo, motor_ids: &[u8]) -> Vec<u8> {
     let param_len = 4 + motor_ids.len() as u16; // addr(2) + data_len(2) + ids
 
     PacketBuilder::new(BROADCAST_ID, 14 + motor_ids.len())
         .instruction(instruction::SYNC_READ, param_len)
-        .u16_le(address::PRESENT_POSITION)
-        .u16_le(4)
+        .u16_le(reg.address)
+        .u16_le(reg.size)
         .bytes(motor_ids)
         .build()
 }
 
-/// Build SYNC_WRITE for Torque Enable (address 64, 1 byte).
-pub fn build_sync_write_torque(motor_ids: &[u8], enable: bool) -> Vec<u8> {
-    let param_len = 4 + (2 * motor_ids.len()) as u16; // addr(2) + data_len(2) + n*(id + val)
-    let val = if enable { 1u8 } else { 0u8 };
+/// Build SYNC_WRITE for any writable register, one raw value per motor.
+///
+/// # Example
+/// ```ignore
+/// let packet = build_sync_write::<register::ProfileVelocity>(&[11, 12], &[100, 100]);
+/// ```
+pub fn build_sync_write<R: Register>(motor_ids: &[u8], values: &[i32]) -> Vec<u8> {
+    build_sync_write_info(&R::INFO, motor_ids, values)
+}
+
+/// Build SYNC_WRITE for a register described at runtime.
+pub fn build_sync_write_info(reg: &RegisterInfo, motor_ids: &[u8], values: &[i32]) -> Vec<u8> {
+    debug_assert_eq!(motor_ids.len(), values.len());
+    debug_assert_eq!(reg.access, Access::ReadWrite, "{} is read-only", reg.name);
 
-    let mut builder = PacketBuilder::new(BROADCAST_ID, 14 + 2 * motor_ids.len())
+    let size = reg.size as usize;
+    // addr(2) + data_len(2) + n*(id + size)
+    let param_len = 4 + ((1 + size) * motor_ids.len()) as u16;
+
+    let mut builder = PacketBuilder::new(BROADCAST_ID, 14 + (1 + size) * motor_ids.len())
         .instruction(instruction::SYNC_WRITE, param_len)
-        .u16_le(address::TORQUE_ENABLE)
-        .u16_le(1);
+        .u16_le(reg.address)
+        .u16_le(reg.size);
 
-    for &id in motor_ids {
-        builder = builder.u8(id).u8(val);
+    for (&id, &val) in motor_ids.iter().zip(values.iter()) {
+        builder = builder.u8(id).bytes(&reg.encode(val));
     }
 
     builder.build()
 }
 
-/// Build SYNC_WRITE for Goal Position (address 116, 4 bytes).
-pub fn build_sync_write_position(motor_ids: &[u8], positions: &[i32]) -> Vec<u8> {
-    debug_assert_eq!(motor_ids.len(), positions.len());
+/// Build FAST_SYNC_READ for any register from multiple motors.
+///
+/// Same request layout as SYNC_READ, but the motors answer with a single
+/// combined status packet (see [`parse_fast_sync_read`]).
+pub fn build_fast_sync_read<R: Register>(motor_ids: &[u8]) -> Vec<u8> {
+    build_fast_sync_read_info(&R::INFO, motor_ids)
+}
+
+/// Build FAST_SYNC_READ for a register described at runtime.
+pub fn build_fast_sync_read_info(reg: &RegisterInfo, motor_ids: &[u8]) -> Vec<u8> {
+    let param_len = 4 + motor_ids.len() as u16; // addr(2) + data_len(2) + ids
 
-    let param_len = 4 + (5 * motor_ids.len()) as u16; // addr(2) + data_len(2) + n*(id + 4)
+    PacketBuilder::new(BROADCAST_ID, 14 + motor_ids.len())
+        .instruction(instruction::FAST_SYNC_READ, param_len)
+        .u16_le(reg.address)
+        .u16_le(reg.size)
+        .bytes(motor_ids)
+        .build()
+}
 
-    let mut builder = PacketBuilder::new(BROADCAST_ID, 14 + 5 * motor_ids.len())
-        .instruction(instruction::SYNC_WRITE, param_len)
-        .u16_le(address::GOAL_POSITION)
-        .u16_le(4);
+/// Build SYNC_READ for Present Position (address 132, 4 bytes).
+#[inline]
+pub fn build_sync_current_position(motor_ids: &[u8]) -> Vec<u8> {
+    build_sync_read::<register::PresentPosition>(motor_ids)
+}
 
-    for (&id, &pos) in motor_ids.iter().zip(positions.iter()) {
-        builder = builder.u8(id).i32_le(pos);
-    }
+/// Build SYNC_WRITE for Torque Enable (address 64, 1 byte).
+#[inline]
+pub fn build_sync_write_torque(motor_ids: &[u8], enable: bool) -> Vec<u8> {
+    let values = vec![enable as i32; motor_ids.len()];
+    build_sync_write::<register::TorqueEnable>(motor_ids, &values)
+}
 
-    builder.build()
+/// Build SYNC_WRITE for Goal Position (address 116, 4 bytes).
+#[inline]
+pub fn build_sync_write_position(motor_ids: &[u8], positions: &[i32]) -> Vec<u8> {
+    build_sync_write::<register::GoalPosition>(motor_ids, positions)
 }
 
 /// Build SYNC_WRITE for positions in radians.
@@ -233,39 +707,115 @@ pub fn build_sync_write_position_radians(motor_ids: &[u8], radians: &[f32]) -> V
 }
 
 /// Build SYNC_READ for temperature from multiple motors.
+#[inline]
 pub fn build_sync_read_temperature(motor_ids: &[u8]) -> Vec<u8> {
-    let param_len = 4 + motor_ids.len() as u16;
-
-    PacketBuilder::new(BROADCAST_ID, 14 + motor_ids.len())
-        .instruction(instruction::SYNC_READ, param_len)
-        .u16_le(address::PRESENT_TEMPERATURE)
-        .u16_le(1)
-        .bytes(motor_ids)
-        .build()
+    build_sync_read::<register::PresentTemperature>(motor_ids)
 }
 
 /// Build SYNC_READ for load from multiple motors.
+#[inline]
 pub fn build_sync_read_load(motor_ids: &[u8]) -> Vec<u8> {
-    let param_len = 4 + motor_ids.len() as u16;
-
-    PacketBuilder::new(BROADCAST_ID, 14 + motor_ids.len())
-        .instruction(instruction::SYNC_READ, param_len)
-        .u16_le(address::PRESENT_LOAD)
-        .u16_le(2)
-        .bytes(motor_ids)
-        .build()
+    build_sync_read::<register::PresentLoad>(motor_ids)
 }
 
 /// Build SYNC_READ for hardware error status from multiple motors.
+#[inline]
 pub fn build_sync_read_hardware_error(motor_ids: &[u8]) -> Vec<u8> {
-    let param_len = 4 + motor_ids.len() as u16;
+    build_sync_read::<register::HardwareErrorStatus>(motor_ids)
+}
 
-    PacketBuilder::new(BROADCAST_ID, 14 + motor_ids.len())
-        .instruction(instruction::SYNC_READ, param_len)
-        .u16_le(address::HARDWARE_ERROR_STATUS)
-        .u16_le(1)
-        .bytes(motor_ids)
-        .build()
+/// One motor's entry in a BULK_READ request.
+///
+/// Protocol 2.0 allows a single entry per motor ID, so several registers
+/// must be fetched as one contiguous block (see [`BulkReadEntry::covering`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BulkReadEntry {
+    pub id: u8,
+    pub address: u16,
+    pub length: u16,
+}
+
+impl BulkReadEntry {
+    /// Build an entry whose address range covers every register in `regs`.
+    pub fn covering(id: u8, regs: &[RegisterInfo]) -> Self {
+        let start = regs.iter().map(|r| r.address).min().unwrap_or(0);
+        let end = regs.iter().map(|r| r.address + r.size).max().unwrap_or(0);
+        Self {
+            id,
+            address: start,
+            length: end - start,
+        }
+    }
+
+    /// Extract a register value from the data block returned for this entry.
+    ///
+    /// Returns `None` if the register lies outside the entry's range.
+    pub fn value(&self, reg: &RegisterInfo, data: &[u8]) -> Option<i32> {
+        let start = reg.address.checked_sub(self.address)? as usize;
+        let end = start + reg.size as usize;
+        if end > self.length as usize || end > data.len() {
+            return None;
+        }
+        Some(reg.decode(&data[start..end]))
+    }
+}
+
+/// One motor's entry in a BULK_WRITE request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkWriteEntry {
+    pub id: u8,
+    pub address: u16,
+    pub data: Vec<u8>,
+}
+
+impl BulkWriteEntry {
+    /// Build an entry writing a raw value to a register.
+    pub fn register(id: u8, reg: &RegisterInfo, raw: i32) -> Self {
+        debug_assert_eq!(reg.access, Access::ReadWrite, "{} is read-only", reg.name);
+        Self {
+            id,
+            address: reg.address,
+            data: reg.encode(raw),
+        }
+    }
+}
+
+/// Build BULK_READ packet (different address/length per motor).
+///
+/// Each motor answers with its own status packet, in request order.
+pub fn build_bulk_read(entries: &[BulkReadEntry]) -> Vec<u8> {
+    let param_len = (5 * entries.len()) as u16; // n*(id + addr(2) + len(2))
+
+    let mut builder = PacketBuilder::new(BROADCAST_ID, 10 + 5 * entries.len())
+        .instruction(instruction::BULK_READ, param_len);
+
+    for entry in entries {
+        builder = builder
+            .u8(entry.id)
+            .u16_le(entry.address)
+            .u16_le(entry.length);
+    }
+
+    builder.build()
+}
+
+/// Build BULK_WRITE packet (different address/data per motor).
+pub fn build_bulk_write(entries: &[BulkWriteEntry]) -> Vec<u8> {
+    // n*(id + addr(2) + len(2) + data)
+    let data_len: usize = entries.iter().map(|e| 5 + e.data.len()).sum();
+
+    let mut builder = PacketBuilder::new(BROADCAST_ID, 10 + data_len)
+        .instruction(instruction::BULK_WRITE, data_len as u16);
+
+    for entry in entries {
+        builder = builder
+            .u8(entry.id)
+            .u16_le(entry.address)
+            .u16_le(entry.data.len() as u16)
+            .bytes(&entry.data);
+    }
+
+    builder.build()
 }
 
 // ============================================================================
@@ -273,98 +823,361 @@ pub fn build_sync_read_hardware_error(motor_ids: &[u8]) -> Vec<u8> {
 // ============================================================================
 
 /// Status packet parsing error
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ParseError {
     TooShort,
     InvalidHeader,
     InvalidInstruction,
     InvalidLength,
-    MotorError(u8),
+    CrcMismatch,
 }
 
-impl From<ParseError> for JsValue {
-    fn from(e: ParseError) -> Self {
-        JsValue::from_str(match e {
+impl ParseError {
+    /// Machine-readable reason, exposed to JavaScript as `e.reason`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseError::TooShort => "TOO_SHORT",
+            ParseError::InvalidHeader => "INVALID_HEADER",
+            ParseError::InvalidInstruction => "INVALID_INSTRUCTION",
+            ParseError::InvalidLength => "INVALID_LENGTH",
+            ParseError::CrcMismatch => "CRC_MISMATCH",
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
             ParseError::TooShort => "Packet too short",
             ParseError::InvalidHeader => "Invalid header",
             ParseError::InvalidInstruction => "Invalid instruction",
             ParseError::InvalidLength => "Invalid length",
-            ParseError::MotorError(code) => {
-                return JsValue::from_str(&format!("Motor error: 0x{:02X}", code))
-            }
+            ParseError::CrcMismatch => "CRC mismatch",
         })
     }
 }
 
-/// Validate packet header and return (id, length, error_byte, data_start)
-#[inline]
-fn validate_header(data: &[u8], min_len: usize) -> Result<(u8, u16, u8, usize), ParseError> {
-    if data.len() < min_len {
-        return Err(ParseError::TooShort);
-    }
+/// A decoded status packet of any length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusPacket {
+    pub id: u8,
+    pub error: u8,
+    pub params: Vec<u8>,
+}
 
-    // Check header: FF FF FD 00
-    if data[0] != 0xFF || data[1] != 0xFF || data[2] != 0xFD || data[3] != 0x00 {
-        return Err(ParseError::InvalidHeader);
+impl StatusPacket {
+    /// Decoded error byte.
+    pub fn status(&self) -> StatusError {
+        StatusError(self.error)
     }
+}
 
-    let id = data[4];
-    let length = u16::from_le_bytes([data[5], data[6]]);
+/// Protocol error reported in the low 7 bits of a status packet's error byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtocolError {
+    /// Failed to process the instruction
+    ResultFail = 1,
+    /// Undefined instruction, or ACTION without REG_WRITE
+    Instruction = 2,
+    /// CRC of the instruction packet doesn't match
+    Crc = 3,
+    /// Data to write is outside the allowed range
+    DataRange = 4,
+    /// Data is shorter than the register
+    DataLength = 5,
+    /// Data to write exceeds the configured limit
+    DataLimit = 6,
+    /// Write to a read-only or torque-locked register, or read of a write-only one
+    Access = 7,
+}
 
-    if data[7] != instruction::STATUS {
-        return Err(ParseError::InvalidInstruction);
+impl ProtocolError {
+    /// Decode a protocol error code (1-7).
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            1 => Some(ProtocolError::ResultFail),
+            2 => Some(ProtocolError::Instruction),
+            3 => Some(ProtocolError::Crc),
+            4 => Some(ProtocolError::DataRange),
+            5 => Some(ProtocolError::DataLength),
+            6 => Some(ProtocolError::DataLimit),
+            7 => Some(ProtocolError::Access),
+            _ => None,
+        }
     }
 
-    let error = data[8];
-
-    Ok((id, length, error, 9))
+    /// Short human-readable name.
+    pub fn label(self) -> &'static str {
+        match self {
+            ProtocolError::ResultFail => "Result Fail",
+            ProtocolError::Instruction => "Instruction Error",
+            ProtocolError::Crc => "CRC Error",
+            ProtocolError::DataRange => "Data Range Error",
+            ProtocolError::DataLength => "Data Length Error",
+            ProtocolError::DataLimit => "Data Limit Error",
+            ProtocolError::Access => "Access Error",
+        }
+    }
 }
 
-/// Parse status packet for position read (4 bytes).
+/// Decoded error byte of a status packet.
 ///
-/// Returns `(motor_id, raw_position)`.
-pub fn parse_status_packet(data: &[u8], offset: usize) -> Result<(u8, i32), JsValue> {
-    let slice = &data[offset..];
-
-    // Position response: header(4) + id(1) + len(2) + instr(1) + err(1) + data(4) + crc(2) = 15
-    let (id, length, _error, data_start) = validate_header(slice, 15)?;
+/// Bit 7 is the alert flag: the motor has a hardware error (see
+/// [`HardwareErrorStatus`]) but the packet's data is still valid. Bits 0-6
+/// hold a [`ProtocolError`] code, in which case the instruction failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StatusError(pub u8);
+
+impl StatusError {
+    /// Alert bit: the motor has a hardware error and needs a reboot
+    pub const ALERT: u8 = 0x80;
+
+    /// Whether the error byte is zero.
+    pub fn is_ok(self) -> bool {
+        self.0 == 0
+    }
 
-    if length != 8 {
-        return Err(ParseError::InvalidLength.into());
+    /// Whether the alert bit is set.
+    pub fn alert(self) -> bool {
+        self.0 & Self::ALERT != 0
     }
 
-    let pos = i32::from_le_bytes([
-        slice[data_start],
-        slice[data_start + 1],
-        slice[data_start + 2],
-        slice[data_start + 3],
-    ]);
+    /// Raw protocol error code (0 if none).
+    pub fn protocol_code(self) -> u8 {
+        self.0 & !Self::ALERT
+    }
 
-    Ok((id, pos))
+    /// Decoded protocol error, if any.
+    pub fn protocol_error(self) -> Option<ProtocolError> {
+        ProtocolError::from_code(self.protocol_code())
+    }
 }
 
-/// Parse status packet for 1-byte read (e.g., temperature).
-pub fn parse_status_packet_1byte(data: &[u8]) -> Result<u8, JsValue> {
-    // 1-byte response: header(4) + id(1) + len(2) + instr(1) + err(1) + data(1) + crc(2) = 12
-    let (_id, _length, error, data_start) = validate_header(data, 12)?;
-
-    if error != 0 {
-        return Err(ParseError::MotorError(error).into());
+/// `"OK"`, `"Alert"`, the protocol error's label, or both.
+impl fmt::Display for StatusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let protocol = match (self.protocol_code(), self.protocol_error()) {
+            (0, _) => None,
+            (_, Some(e)) => Some(e.label().to_string()),
+            (code, None) => Some(format!("Unknown Error ({})", code)),
+        };
+        match (self.alert(), protocol) {
+            (false, None) => f.write_str("OK"),
+            (true, None) => f.write_str("Alert"),
+            (false, Some(p)) => f.write_str(&p),
+            (true, Some(p)) => write!(f, "Alert, {}", p),
+        }
+    }
+}
+
+/// Instruction packet (host request), as seen by a motor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructionPacket {
+    pub id: u8,
+    pub instruction: u8,
+    pub params: Vec<u8>,
+}
+
+/// Decode the instruction packet at the start of `data`.
+///
+/// Verifies header and CRC and removes byte stuffing. This is the motor side
+/// of the protocol, used to emulate motors.
+///
+/// Returns the packet and the number of bytes it occupies in `data`.
+pub fn decode_instruction_packet(data: &[u8]) -> Result<(InstructionPacket, usize), ParseError> {
+    // header(4) + id(1) + len(2) + instr(1) + crc(2) = 10
+    if data.len() < 10 {
+        return Err(ParseError::TooShort);
+    }
+
+    if data[0] != 0xFF || data[1] != 0xFF || data[2] != 0xFD || data[3] != 0x00 {
+        return Err(ParseError::InvalidHeader);
+    }
+
+    if data[7] == instruction::STATUS {
+        return Err(ParseError::InvalidInstruction);
+    }
+
+    // Length field = instr + params + crc
+    let length = u16::from_le_bytes([data[5], data[6]]) as usize;
+    if length < 3 {
+        return Err(ParseError::InvalidLength);
+    }
+
+    let total = 7 + length;
+    if data.len() < total {
+        return Err(ParseError::TooShort);
+    }
+
+    let crc = u16::from_le_bytes([data[total - 2], data[total - 1]]);
+    if crc16(&data[..total - 2]) != crc {
+        return Err(ParseError::CrcMismatch);
+    }
+
+    let body = remove_stuffing(&data[7..total - 2]);
+
+    Ok((
+        InstructionPacket {
+            id: data[4],
+            instruction: body[0],
+            params: body[1..].to_vec(),
+        },
+        total,
+    ))
+}
+
+/// Parse every valid instruction packet in a buffer, skipping garbage and
+/// packets that fail the CRC check.
+pub fn parse_instruction_packets(data: &[u8]) -> Vec<InstructionPacket> {
+    let mut packets = Vec::new();
+    let mut pos = 0;
+    while let Some(offset) = find_packet_headers(&data[pos..]).next().map(|o| o + pos) {
+        match decode_instruction_packet(&data[offset..]) {
+            Ok((packet, len)) => {
+                packets.push(packet);
+                pos = offset + len;
+            }
+            Err(_) => pos = offset + 1,
+        }
+    }
+    packets
+}
+
+/// Decode the status packet at the start of `data`.
+///
+/// Verifies header, instruction and CRC, and removes byte stuffing.
+/// Packets failing the CRC check are counted in [`rejected_packet_count`].
+///
+/// Returns the packet and the number of bytes it occupies in `data`.
+fn decode_status_packet(data: &[u8]) -> Result<(StatusPacket, usize), ParseError> {
+    let result = decode_status_packet_uncounted(data);
+    if result == Err(ParseError::CrcMismatch) {
+        REJECTED_PACKETS.fetch_add(1, Ordering::Relaxed);
+    }
+    result
+}
+
+/// [`decode_status_packet`] without counting CRC failures, for offline decoding.
+fn decode_status_packet_uncounted(data: &[u8]) -> Result<(StatusPacket, usize), ParseError> {
+    // header(4) + id(1) + len(2) + instr(1) + err(1) + crc(2) = 11
+    if data.len() < 11 {
+        return Err(ParseError::TooShort);
+    }
+
+    // Check header: FF FF FD 00
+    if data[0] != 0xFF || data[1] != 0xFF || data[2] != 0xFD || data[3] != 0x00 {
+        return Err(ParseError::InvalidHeader);
+    }
+
+    if data[7] != instruction::STATUS {
+        return Err(ParseError::InvalidInstruction);
+    }
+
+    // Length field = instr + err + params + crc
+    let length = u16::from_le_bytes([data[5], data[6]]) as usize;
+    if length < 4 {
+        return Err(ParseError::InvalidLength);
+    }
+
+    let total = 7 + length;
+    if data.len() < total {
+        return Err(ParseError::TooShort);
+    }
+
+    let crc = u16::from_le_bytes([data[total - 2], data[total - 1]]);
+    if crc16(&data[..total - 2]) != crc {
+        return Err(ParseError::CrcMismatch);
+    }
+
+    let body = remove_stuffing(&data[8..total - 2]);
+
+    Ok((
+        StatusPacket {
+            id: data[4],
+            error: body[0],
+            params: body[1..].to_vec(),
+        },
+        total,
+    ))
+}
+
+/// Parse status packet for position read (4 bytes).
+///
+/// Returns `(motor_id, raw_position, status)`. The position is returned even
+/// if the motor is in alert, so callers must check `status`.
+pub fn parse_status_packet(
+    data: &[u8],
+    offset: usize,
+) -> Result<(u8, i32, StatusError), ReachyError> {
+    let (packet, _) = decode_status_packet(&data[offset..])?;
+
+    if packet.params.len() != 4 {
+        return Err(ParseError::InvalidLength.into());
+    }
+
+    let pos = i32::from_le_bytes([
+        packet.params[0],
+        packet.params[1],
+        packet.params[2],
+        packet.params[3],
+    ]);
+
+    Ok((packet.id, pos, packet.status()))
+}
+
+/// Parse status packet for 1-byte read (e.g., temperature).
+pub fn parse_status_packet_1byte(data: &[u8]) -> Result<u8, ReachyError> {
+    let (packet, _) = decode_status_packet(data)?;
+
+    if packet.error != 0 {
+        return Err(ReachyError::MotorStatusError {
+            id: packet.id,
+            status: packet.error,
+        });
     }
 
-    Ok(data[data_start])
+    if packet.params.len() != 1 {
+        return Err(ParseError::InvalidLength.into());
+    }
+
+    Ok(packet.params[0])
 }
 
 /// Parse status packet for 2-byte signed read (e.g., load).
-pub fn parse_status_packet_2byte_signed(data: &[u8]) -> Result<i16, JsValue> {
-    // 2-byte response: header(4) + id(1) + len(2) + instr(1) + err(1) + data(2) + crc(2) = 13
-    let (_id, _length, error, data_start) = validate_header(data, 13)?;
+pub fn parse_status_packet_2byte_signed(data: &[u8]) -> Result<i16, ReachyError> {
+    let (packet, _) = decode_status_packet(data)?;
+
+    if packet.error != 0 {
+        return Err(ReachyError::MotorStatusError {
+            id: packet.id,
+            status: packet.error,
+        });
+    }
+
+    if packet.params.len() != 2 {
+        return Err(ParseError::InvalidLength.into());
+    }
 
-    if error != 0 {
-        return Err(ParseError::MotorError(error).into());
+    Ok(i16::from_le_bytes([packet.params[0], packet.params[1]]))
+}
+
+/// Parse the acknowledgement of a WRITE (or any instruction without data,
+/// such as REG_WRITE, ACTION or FACTORY_RESET).
+///
+/// Returns the ID of the motor that answered, or its error if the status
+/// packet reports a protocol error. The alert bit alone doesn't fail: the
+/// instruction was executed, the motor just has a hardware error pending.
+pub fn parse_write_status(data: &[u8]) -> Result<u8, ReachyError> {
+    let (packet, _) = decode_status_packet(data)?;
+
+    if packet.status().protocol_code() != 0 {
+        return Err(ReachyError::MotorStatusError {
+            id: packet.id,
+            status: packet.error,
+        });
     }
 
-    Ok(i16::from_le_bytes([data[data_start], data[data_start + 1]]))
+    Ok(packet.id)
 }
 
 // ============================================================================
@@ -377,6 +1190,9 @@ const TICKS_PER_RAD: f32 = 4096.0 / (2.0 * std::f32::consts::PI);
 /// Radians per tick for XL330
 const RAD_PER_TICK: f32 = (2.0 * std::f32::consts::PI) / 4096.0;
 
+/// Revolutions per minute per velocity tick for XL330
+const RPM_PER_TICK: f32 = 0.229;
+
 /// Convert radians to raw Dynamixel position.
 ///
 /// XL330: 4096 positions/revolution, center = 2048 = 0 rad
@@ -391,6 +1207,217 @@ pub fn raw_to_radians(raw: i32) -> f32 {
     (raw as f32 - 2048.0) * RAD_PER_TICK
 }
 
+// ============================================================================
+// Hardware Error Status
+// ============================================================================
+
+/// Decoded Hardware Error Status register (address 70).
+///
+/// | Bit | Flag             |
+/// |-----|------------------|
+/// | 0   | Input Voltage    |
+/// | 2   | Motor Hall Sensor|
+/// | 3   | Overheating      |
+/// | 4   | Motor Encoder    |
+/// | 5   | Electrical Shock |
+/// | 7   | Overload         |
+///
+/// Any of these also sets the alert bit (0x80) in the motor's status packets
+/// until the motor is rebooted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HardwareErrorStatus(pub u8);
+
+impl HardwareErrorStatus {
+    pub const INPUT_VOLTAGE: u8 = 1 << 0;
+    pub const HALL_SENSOR: u8 = 1 << 2;
+    pub const OVERHEATING: u8 = 1 << 3;
+    pub const ENCODER: u8 = 1 << 4;
+    pub const ELECTRICAL_SHOCK: u8 = 1 << 5;
+    pub const OVERLOAD: u8 = 1 << 7;
+
+    /// Every flag as `(bit, key, label)`, in bit order.
+    ///
+    /// `key` is the snake_case field name used in JavaScript reports.
+    pub const FLAGS: [(u8, &'static str, &'static str); 6] = [
+        (Self::INPUT_VOLTAGE, "input_voltage", "Input Voltage Error"),
+        (Self::HALL_SENSOR, "hall_sensor", "Motor Hall Sensor Error"),
+        (Self::OVERHEATING, "overheating", "Overheating Error"),
+        (Self::ENCODER, "encoder", "Motor Encoder Error"),
+        (
+            Self::ELECTRICAL_SHOCK,
+            "electrical_shock",
+            "Electrical Shock Error",
+        ),
+        (Self::OVERLOAD, "overload", "Overload Error"),
+    ];
+
+    /// Raw register value.
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Whether no error bit is set.
+    pub fn is_ok(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn input_voltage(self) -> bool {
+        self.0 & Self::INPUT_VOLTAGE != 0
+    }
+
+    pub fn hall_sensor(self) -> bool {
+        self.0 & Self::HALL_SENSOR != 0
+    }
+
+    pub fn overheating(self) -> bool {
+        self.0 & Self::OVERHEATING != 0
+    }
+
+    pub fn encoder(self) -> bool {
+        self.0 & Self::ENCODER != 0
+    }
+
+    pub fn electrical_shock(self) -> bool {
+        self.0 & Self::ELECTRICAL_SHOCK != 0
+    }
+
+    pub fn overload(self) -> bool {
+        self.0 & Self::OVERLOAD != 0
+    }
+
+    /// Labels of the set flags, in bit order.
+    pub fn labels(self) -> Vec<&'static str> {
+        Self::FLAGS
+            .iter()
+            .filter(|(bit, _, _)| self.0 & bit != 0)
+            .map(|(_, _, label)| *label)
+            .collect()
+    }
+}
+
+/// `"OK"`, or the labels of the set flags separated by commas.
+impl fmt::Display for HardwareErrorStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_ok() {
+            return f.write_str("OK");
+        }
+        let mut parts: Vec<String> = self.labels().iter().map(|l| l.to_string()).collect();
+        let known = Self::FLAGS.iter().fold(0, |mask, (bit, _, _)| mask | bit);
+        if self.0 & !known != 0 {
+            parts.push(format!("Unknown Error (0x{:02X})", self.0 & !known));
+        }
+        f.write_str(&parts.join(", "))
+    }
+}
+
+// ============================================================================
+// EEPROM Provisioning
+// ============================================================================
+
+/// Supported baud rates, indexed by their Baud Rate register value.
+const BAUD_RATES: [u32; 7] = [
+    9_600, 57_600, 115_200, 1_000_000, 2_000_000, 3_000_000, 4_000_000,
+];
+
+/// Baud Rate register value for a bit rate, if supported.
+pub fn baud_rate_code(bps: u32) -> Option<u8> {
+    BAUD_RATES.iter().position(|&b| b == bps).map(|i| i as u8)
+}
+
+/// Bit rate for a Baud Rate register value, if valid.
+pub fn baud_rate_bps(code: u8) -> Option<u32> {
+    BAUD_RATES.get(code as usize).copied()
+}
+
+/// EEPROM settings to write to a single motor.
+///
+/// Fields left to `None` are not written. All values are raw register values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EepromConfig {
+    pub id: Option<u8>,
+    /// Baud Rate register value (see [`baud_rate_code`])
+    pub baud_rate: Option<u8>,
+    /// Return delay in 2 µs ticks
+    pub return_delay_time: Option<u8>,
+    pub drive_mode: Option<u8>,
+    pub min_position_limit: Option<i32>,
+    pub max_position_limit: Option<i32>,
+}
+
+impl EepromConfig {
+    /// Register writes for this configuration, in a safe order.
+    ///
+    /// The ID is written after the other settings, so they can still address
+    /// the motor by its current ID, and the baud rate comes last since it may
+    /// make the motor unreachable at the current link speed.
+    pub fn writes(&self) -> Vec<(RegisterInfo, i32)> {
+        let mut writes = Vec::new();
+        let mut push = |reg: RegisterInfo, value: Option<i32>| {
+            if let Some(v) = value {
+                writes.push((reg, v));
+            }
+        };
+
+        push(
+            <register::ReturnDelayTime as Register>::INFO,
+            self.return_delay_time.map(i32::from),
+        );
+        push(
+            <register::DriveMode as Register>::INFO,
+            self.drive_mode.map(i32::from),
+        );
+        push(
+            <register::MinPositionLimit as Register>::INFO,
+            self.min_position_limit,
+        );
+        push(
+            <register::MaxPositionLimit as Register>::INFO,
+            self.max_position_limit,
+        );
+        push(<register::Id as Register>::INFO, self.id.map(i32::from));
+        push(
+            <register::BaudRate as Register>::INFO,
+            self.baud_rate.map(i32::from),
+        );
+
+        writes
+    }
+
+    /// Check the values before writing anything.
+    pub fn validate(&self) -> Result<(), ReachyError> {
+        let invalid = |msg: String| Err(ReachyError::InvalidArgument(msg));
+
+        if let Some(id) = self.id {
+            if id > MAX_ID {
+                return invalid(format!("Invalid motor ID {} (must be 0-252)", id));
+            }
+        }
+        if let Some(code) = self.baud_rate {
+            if baud_rate_bps(code).is_none() {
+                return invalid(format!("Invalid baud rate code {}", code));
+            }
+        }
+        for limit in [self.min_position_limit, self.max_position_limit]
+            .iter()
+            .flatten()
+        {
+            if !(0..=4095).contains(limit) {
+                return invalid(format!("Position limit {} out of range 0-4095", limit));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_position_limit, self.max_position_limit) {
+            if min > max {
+                return invalid(format!(
+                    "Min position limit {} is above max position limit {}",
+                    min, max
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 // ============================================================================
 // Resilient Multi-Packet Parsing
 // ============================================================================
@@ -405,81 +1432,264 @@ fn find_packet_headers(data: &[u8]) -> impl Iterator<Item = usize> + '_ {
         .map(|(i, _)| i)
 }
 
-/// Parse all position status packets from a response buffer.
+/// Iterate over every valid status packet in a response buffer.
 ///
-/// This function scans for packet headers instead of using fixed offsets,
-/// making it resilient to missing motor responses.
-///
-/// # Returns
-/// Vector of (motor_id, raw_position) for each successfully parsed packet.
-pub fn parse_position_packets(data: &[u8]) -> Vec<(u8, i32)> {
-    let mut results = Vec::new();
-
-    for offset in find_packet_headers(data) {
-        // Need at least 15 bytes for a position status packet
-        if offset + 15 > data.len() {
-            continue;
+/// Scans for headers, skipping garbage and packets that fail the CRC check.
+fn status_packets(data: &[u8]) -> impl Iterator<Item = StatusPacket> + '_ {
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        while let Some(offset) = find_packet_headers(&data[pos..]).next().map(|o| o + pos) {
+            match decode_status_packet(&data[offset..]) {
+                Ok((packet, len)) => {
+                    pos = offset + len;
+                    return Some(packet);
+                }
+                Err(_) => pos = offset + 1,
+            }
         }
+        None
+    })
+}
 
-        let slice = &data[offset..];
+/// Parse every complete status packet in a response buffer, whatever its length.
+///
+/// # Returns
+/// Vector of status packets in the order they appear in the buffer.
+pub fn parse_status_packets(data: &[u8]) -> Vec<StatusPacket> {
+    status_packets(data).collect()
+}
 
-        // Check it's a status packet (instruction = 0x55)
-        if slice[7] != instruction::STATUS {
-            continue;
-        }
+/// Split the combined status packet of a FAST_SYNC_READ into per-motor packets.
+///
+/// The response is a single broadcast status packet laid out as
+/// `err1 id1 data1 crc1 | err2 id2 data2 crc2 | ... | errN idN dataN` followed
+/// by the packet CRC. Each motor's CRC covers the whole packet up to its own
+/// data, so segments are verified one by one: if a motor doesn't answer or a
+/// segment is corrupted, only the verified segments before it are returned.
+///
+/// # Returns
+/// Vector of per-motor status packets, in response order.
+pub fn parse_fast_sync_read(data: &[u8], size: usize) -> Vec<StatusPacket> {
+    // err(1) + id(1) + data + crc(2)
+    let segment_len = size + 4;
+
+    let Some(offset) = find_packet_headers(data).find(|&offset| {
+        data.len() >= offset + 11
+            && data[offset + 4] == BROADCAST_ID
+            && data[offset + 7] == instruction::STATUS
+    }) else {
+        return Vec::new();
+    };
+    let slice = &data[offset..];
 
-        // Check length field indicates position data (length = 8)
-        let length = u16::from_le_bytes([slice[5], slice[6]]);
-        if length != 8 {
-            continue;
+    // Length field = instr + segments + crc; the last segment's CRC is the packet CRC
+    let length = u16::from_le_bytes([slice[5], slice[6]]) as usize;
+    let end = (7 + length).min(slice.len());
+
+    let mut packets = Vec::new();
+    let mut start = 8;
+    while start + segment_len <= end {
+        let crc_pos = start + segment_len - 2;
+        let crc = u16::from_le_bytes([slice[crc_pos], slice[crc_pos + 1]]);
+        if crc16(&slice[..crc_pos]) != crc {
+            REJECTED_PACKETS.fetch_add(1, Ordering::Relaxed);
+            break;
         }
 
-        let motor_id = slice[4];
-        let pos = i32::from_le_bytes([slice[9], slice[10], slice[11], slice[12]]);
-
-        results.push((motor_id, pos));
+        packets.push(StatusPacket {
+            id: slice[start + 1],
+            error: slice[start],
+            params: slice[start + 2..crc_pos].to_vec(),
+        });
+        start += segment_len;
     }
 
-    results
+    packets
 }
 
-/// Parse all 1-byte status packets (e.g., temperature) from a response buffer.
+/// Parse a FAST_SYNC_READ response for Present Position.
 ///
 /// # Returns
-/// Vector of (motor_id, value) for each successfully parsed packet.
-pub fn parse_1byte_packets(data: &[u8]) -> Vec<(u8, u8)> {
-    let mut results = Vec::new();
+/// Vector of (motor_id, raw_position) for each motor found in the response.
+pub fn parse_fast_sync_position(data: &[u8]) -> Vec<(u8, i32)> {
+    parse_fast_sync_position_with_status(data)
+        .into_iter()
+        .map(|(id, pos, _)| (id, pos))
+        .collect()
+}
 
-    for offset in find_packet_headers(data) {
-        // Need at least 12 bytes for a 1-byte status packet
-        if offset + 12 > data.len() {
-            continue;
-        }
+/// Parse a FAST_SYNC_READ response for Present Position, keeping each motor's
+/// status error byte.
+///
+/// # Returns
+/// Vector of (motor_id, raw_position, status) for each motor.
+pub fn parse_fast_sync_position_with_status(data: &[u8]) -> Vec<(u8, i32, StatusError)> {
+    let reg = <register::PresentPosition as Register>::INFO;
+    parse_fast_sync_read(data, reg.size as usize)
+        .into_iter()
+        .map(|packet| (packet.id, reg.decode(&packet.params), packet.status()))
+        .collect()
+}
 
-        let slice = &data[offset..];
+/// Parse the response to a BULK_READ request.
+///
+/// Only packets whose ID and data length match a requested entry are kept,
+/// so missing or malformed motor responses don't affect the others.
+///
+/// # Returns
+/// Vector of (entry, error, data) for each motor that answered.
+pub fn parse_bulk_read_response(
+    data: &[u8],
+    entries: &[BulkReadEntry],
+) -> Vec<(BulkReadEntry, u8, Vec<u8>)> {
+    parse_status_packets(data)
+        .into_iter()
+        .filter_map(|packet| {
+            let entry = entries.iter().find(|e| e.id == packet.id)?;
+            if packet.params.len() != entry.length as usize {
+                return None;
+            }
+            Some((*entry, packet.error, packet.params))
+        })
+        .collect()
+}
 
-        if slice[7] != instruction::STATUS {
-            continue;
+/// Order parsed `(motor_id, value)` pairs by the requested motor IDs.
+///
+/// Motors that didn't respond are `None`, so they can't be mistaken for a
+/// real reading of 0. Responses from motors that weren't requested are
+/// ignored.
+///
+/// # Returns
+/// One entry per ID in `motor_ids`, in the same order.
+pub fn values_by_id<T>(
+    motor_ids: &[u8],
+    parsed: impl IntoIterator<Item = (u8, T)>,
+) -> Vec<Option<T>> {
+    let mut values: Vec<Option<T>> = motor_ids.iter().map(|_| None).collect();
+    for (id, value) in parsed {
+        if let Some(idx) = motor_ids.iter().position(|&m| m == id) {
+            values[idx] = Some(value);
         }
+    }
+    values
+}
 
-        // Length = 5 for 1-byte data (instr + err + data + crc)
-        let length = u16::from_le_bytes([slice[5], slice[6]]);
-        if length != 5 {
-            continue;
-        }
+/// IDs of the motors whose value is missing in a [`values_by_id`] result.
+pub fn missing_ids<T>(motor_ids: &[u8], values: &[Option<T>]) -> Vec<u8> {
+    motor_ids
+        .iter()
+        .zip(values)
+        .filter(|(_, v)| v.is_none())
+        .map(|(&id, _)| id)
+        .collect()
+}
 
-        let error = slice[8];
-        if error != 0 {
-            continue;
-        }
+/// A motor's answer to PING.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PingResponse {
+    pub id: u8,
+    pub model_number: u16,
+    pub firmware_version: u8,
+    pub error: u8,
+}
 
-        let motor_id = slice[4];
-        let value = slice[9];
+impl PingResponse {
+    /// Name of the motor model, if known.
+    pub fn model_name(&self) -> Option<&'static str> {
+        model_name(self.model_number)
+    }
+}
 
-        results.push((motor_id, value));
+/// Name of a Dynamixel model from its model number, for the models found on
+/// or compatible with Reachy Mini.
+pub fn model_name(model_number: u16) -> Option<&'static str> {
+    match model_number {
+        1060 => Some("XL430-W250"),
+        1190 => Some("XL330-M077"),
+        1200 => Some("XL330-M288"),
+        1230 => Some("XC330-M181"),
+        1240 => Some("XC330-M288"),
+        _ => None,
     }
+}
+
+/// Parse every PING status packet (model number + firmware version) in a
+/// response buffer.
+///
+/// # Returns
+/// One entry per motor that answered, in the order received.
+pub fn parse_ping_responses(data: &[u8]) -> Vec<PingResponse> {
+    scan_status_packets(data, 3)
+        .map(|packet| PingResponse {
+            id: packet.id,
+            model_number: u16::from_le_bytes([packet.params[0], packet.params[1]]),
+            firmware_version: packet.params[2],
+            error: packet.error,
+        })
+        .collect()
+}
+
+/// Scan a response buffer for status packets carrying `size` bytes of data.
+fn scan_status_packets(data: &[u8], size: usize) -> impl Iterator<Item = StatusPacket> + '_ {
+    status_packets(data).filter(move |packet| packet.params.len() == size)
+}
+
+/// Parse all status packets for a register from a SYNC_READ response buffer.
+///
+/// Packets where the motor reported an error are skipped.
+///
+/// # Returns
+/// Vector of (motor_id, raw_value) for each successfully parsed packet.
+pub fn parse_register_packets<R: Register>(data: &[u8]) -> Vec<(u8, i32)> {
+    parse_register_packets_info(&R::INFO, data)
+}
 
-    results
+/// Parse all status packets for a register described at runtime.
+pub fn parse_register_packets_info(reg: &RegisterInfo, data: &[u8]) -> Vec<(u8, i32)> {
+    scan_status_packets(data, reg.size as usize)
+        .filter(|packet| packet.error == 0)
+        .map(|packet| (packet.id, reg.decode(&packet.params)))
+        .collect()
+}
+
+/// Parse all position status packets from a response buffer.
+///
+/// This function scans for packet headers instead of using fixed offsets,
+/// making it resilient to missing motor responses.
+///
+/// # Returns
+/// Vector of (motor_id, raw_position) for each successfully parsed packet.
+pub fn parse_position_packets(data: &[u8]) -> Vec<(u8, i32)> {
+    parse_position_packets_with_status(data)
+        .into_iter()
+        .map(|(id, pos, _)| (id, pos))
+        .collect()
+}
+
+/// Parse all position status packets, keeping each motor's status error byte.
+///
+/// Motors in alert still report a valid position; check the returned
+/// [`StatusError`] to tell them apart from healthy ones.
+///
+/// # Returns
+/// Vector of (motor_id, raw_position, status) for each successfully parsed packet.
+pub fn parse_position_packets_with_status(data: &[u8]) -> Vec<(u8, i32, StatusError)> {
+    let reg = <register::PresentPosition as Register>::INFO;
+    scan_status_packets(data, 4)
+        .map(|packet| (packet.id, reg.decode(&packet.params), packet.status()))
+        .collect()
+}
+
+/// Parse all 1-byte status packets (e.g., temperature) from a response buffer.
+///
+/// # Returns
+/// Vector of (motor_id, value) for each successfully parsed packet.
+pub fn parse_1byte_packets(data: &[u8]) -> Vec<(u8, u8)> {
+    scan_status_packets(data, 1)
+        .filter(|packet| packet.error == 0)
+        .map(|packet| (packet.id, packet.params[0]))
+        .collect()
 }
 
 /// Parse all 1-byte status packets including those with motor errors.
@@ -491,72 +1701,484 @@ pub fn parse_1byte_packets(data: &[u8]) -> Vec<(u8, u8)> {
 /// # Returns
 /// Vector of (motor_id, value) for each packet found.
 pub fn parse_1byte_packets_with_errors(data: &[u8]) -> Vec<(u8, u8)> {
-    let mut results = Vec::new();
+    scan_status_packets(data, 1)
+        .map(|packet| (packet.id, packet.params[0]))
+        .collect()
+}
+
+/// Parse all 2-byte signed status packets (e.g., load) from a response buffer.
+///
+/// # Returns
+/// Vector of (motor_id, value) for each successfully parsed packet.
+pub fn parse_2byte_signed_packets(data: &[u8]) -> Vec<(u8, i16)> {
+    scan_status_packets(data, 2)
+        .filter(|packet| packet.error == 0)
+        .map(|packet| {
+            (
+                packet.id,
+                i16::from_le_bytes([packet.params[0], packet.params[1]]),
+            )
+        })
+        .collect()
+}
+
+// ============================================================================
+// Stream Framing
+// ============================================================================
+
+/// Largest status packet length field accepted by the framer.
+///
+/// Anything larger is treated as a corrupted header rather than waited for.
+const MAX_STATUS_LENGTH: usize = 1024;
+
+/// Number of status packets an instruction packet is expected to trigger.
+///
+/// # Returns
+/// * `Some(n)` - Exactly `n` responses are expected (0 for writes and broadcasts)
+/// * `None` - The count can't be known in advance (e.g. unknown instruction)
+pub fn expected_responses(packet: &[u8]) -> Option<usize> {
+    if packet.len() < 10 {
+        return None;
+    }
 
-    for offset in find_packet_headers(data) {
-        // Need at least 12 bytes for a 1-byte status packet
-        if offset + 12 > data.len() {
-            continue;
+    let id = packet[4];
+    let length = u16::from_le_bytes([packet[5], packet[6]]) as usize;
+
+    match packet[7] {
+        instruction::PING
+        | instruction::READ
+        | instruction::WRITE
+        | instruction::REG_WRITE
+        | instruction::ACTION
+        | instruction::FACTORY_RESET
+        | instruction::REBOOT
+            if id != BROADCAST_ID =>
+        {
+            Some(1)
         }
+        // Broadcast instructions other than PING get no response
+        instruction::WRITE
+        | instruction::REG_WRITE
+        | instruction::ACTION
+        | instruction::FACTORY_RESET
+        | instruction::REBOOT => Some(0),
+        // addr(2) + data_len(2) + ids
+        instruction::SYNC_READ => Some(length.saturating_sub(3 + 4)),
+        instruction::FAST_SYNC_READ => Some(1),
+        // n*(id + addr(2) + len(2))
+        instruction::BULK_READ => Some(length.saturating_sub(3) / 5),
+        instruction::SYNC_WRITE | instruction::BULK_WRITE => Some(0),
+        _ => None,
+    }
+}
 
-        let slice = &data[offset..];
+/// IDs whose status packets answer an instruction packet.
+///
+/// The combined answer to a FAST_SYNC_READ comes from [`BROADCAST_ID`].
+///
+/// # Returns
+/// * `Some(ids)` - Only these IDs answer (none for writes and broadcasts)
+/// * `None` - Any ID may answer (broadcast PING) or the instruction is unknown
+pub fn expected_responders(packet: &[u8]) -> Option<Vec<u8>> {
+    if packet.len() < 10 {
+        return None;
+    }
 
-        if slice[7] != instruction::STATUS {
-            continue;
+    let id = packet[4];
+    let length = u16::from_le_bytes([packet[5], packet[6]]) as usize;
+    // Between the instruction and the CRC
+    let params = packet.get(8..5 + length)?;
+
+    match packet[7] {
+        instruction::PING
+        | instruction::READ
+        | instruction::WRITE
+        | instruction::REG_WRITE
+        | instruction::ACTION
+        | instruction::FACTORY_RESET
+        | instruction::REBOOT
+            if id != BROADCAST_ID =>
+        {
+            Some(vec![id])
         }
+        instruction::WRITE
+        | instruction::REG_WRITE
+        | instruction::ACTION
+        | instruction::FACTORY_RESET
+        | instruction::REBOOT => Some(Vec::new()),
+        // addr(2) + data_len(2) + ids
+        instruction::SYNC_READ => params.get(4..).map(<[u8]>::to_vec),
+        instruction::FAST_SYNC_READ => Some(vec![BROADCAST_ID]),
+        // n*(id + addr(2) + len(2))
+        instruction::BULK_READ => Some(params.chunks_exact(5).map(|entry| entry[0]).collect()),
+        instruction::SYNC_WRITE | instruction::BULK_WRITE => Some(Vec::new()),
+        _ => None,
+    }
+}
+
+/// Stateful framer turning a raw serial byte stream into status packets.
+///
+/// Serial reads can return a packet split over several chunks, or several
+/// packets in one chunk. The framer accumulates bytes across reads and emits
+/// each status packet once it is complete and its CRC checks out. Garbage
+/// between packets is dropped.
+///
+/// # Example
+/// ```ignore
+/// let mut framer = PacketFramer::new();
+/// for chunk in chunks {
+///     for packet in framer.push(&chunk) {
+///         // packet is a complete, CRC-checked status packet
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct PacketFramer {
+    buf: Vec<u8>,
+}
+
+impl PacketFramer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append received bytes and return every newly completed status packet.
+    pub fn push(&mut self, data: &[u8]) -> Vec<Vec<u8>> {
+        self.buf.extend_from_slice(data);
+
+        let mut packets = Vec::new();
+        loop {
+            let header = find_packet_headers(&self.buf).next();
+            match header {
+                Some(offset) => {
+                    self.buf.drain(..offset);
+                }
+                None => {
+                    // Keep a possible partial header (FF, FF FF or FF FF FD) at the end
+                    let keep = (1..=3)
+                        .rev()
+                        .find(|&n| self.buf.ends_with(&[0xFF, 0xFF, 0xFD][..n]))
+                        .unwrap_or(0);
+                    self.buf.drain(..self.buf.len() - keep);
+                    break;
+                }
+            }
 
-        // Length = 5 for 1-byte data (instr + err + data + crc)
-        let length = u16::from_le_bytes([slice[5], slice[6]]);
-        if length != 5 {
-            continue;
+            let too_long = self.buf.len() >= 7
+                && u16::from_le_bytes([self.buf[5], self.buf[6]]) as usize > MAX_STATUS_LENGTH;
+
+            match decode_status_packet(&self.buf) {
+                Ok((_, len)) => packets.push(self.buf.drain(..len).collect()),
+                Err(ParseError::TooShort) if !too_long => break,
+                // Not a valid packet after all: skip this header and rescan
+                Err(_) => {
+                    self.buf.drain(..1);
+                }
+            }
         }
 
-        let motor_id = slice[4];
-        let value = slice[9];
+        packets
+    }
 
-        results.push((motor_id, value));
+    /// Number of buffered bytes not yet part of a complete packet.
+    pub fn pending(&self) -> usize {
+        self.buf.len()
     }
 
-    results
+    /// Discard any buffered leftover bytes.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+    }
 }
 
-/// Parse all 2-byte signed status packets (e.g., load) from a response buffer.
-///
-/// # Returns
-/// Vector of (motor_id, value) for each successfully parsed packet.
-pub fn parse_2byte_signed_packets(data: &[u8]) -> Vec<(u8, i16)> {
-    let mut results = Vec::new();
+// ============================================================================
+// Packet Decoding
+// ============================================================================
 
-    for offset in find_packet_headers(data) {
-        // Need at least 13 bytes for a 2-byte status packet
-        if offset + 13 > data.len() {
-            continue;
-        }
+/// A packet found in a captured byte stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodedPacket {
+    Instruction(InstructionPacket),
+    Status(StatusPacket),
+}
 
+/// Decode every instruction and status packet in a byte stream, in order.
+///
+/// Garbage and packets failing the CRC check are skipped (and not counted in
+/// [`rejected_packet_count`]).
+pub fn decode_packets(data: &[u8]) -> Vec<DecodedPacket> {
+    let mut packets = Vec::new();
+    let mut pos = 0;
+    while let Some(offset) = find_packet_headers(&data[pos..]).next().map(|o| o + pos) {
         let slice = &data[offset..];
+        let decoded = if slice.get(7) == Some(&instruction::STATUS) {
+            decode_status_packet_uncounted(slice).map(|(p, len)| (DecodedPacket::Status(p), len))
+        } else {
+            decode_instruction_packet(slice).map(|(p, len)| (DecodedPacket::Instruction(p), len))
+        };
+        match decoded {
+            Ok((packet, len)) => {
+                packets.push(packet);
+                pos = offset + len;
+            }
+            Err(_) => pos = offset + 1,
+        }
+    }
+    packets
+}
+
+/// Turns packets into human-readable lines.
+///
+/// Status packets don't say which registers they contain, so the describer
+/// remembers the last instruction and uses it to decode the values of the
+/// status packets that follow.
+#[derive(Debug, Clone, Default)]
+pub struct PacketDescriber {
+    request: Option<InstructionPacket>,
+}
+
+impl PacketDescriber {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-        if slice[7] != instruction::STATUS {
-            continue;
+    /// Describe a packet, e.g. `SYNC_READ Present Position from [11, 12]` or
+    /// `STATUS ID 11 Present Position = 2048`.
+    pub fn describe(&mut self, packet: &DecodedPacket) -> String {
+        match packet {
+            DecodedPacket::Instruction(request) => {
+                self.request = Some(request.clone());
+                describe_instruction(request)
+            }
+            DecodedPacket::Status(status) => describe_status(status, self.request.as_ref()),
         }
+    }
+}
+
+/// Describe every packet in a byte stream, one line per packet.
+///
+/// # Example
+/// ```ignore
+/// let lines = describe_packets(&build_sync_current_position(&[11, 12]));
+/// assert_eq!(lines, vec!["SYNC_READ Present Position from [11, 12]"]);
+/// ```
+pub fn describe_packets(data: &[u8]) -> Vec<String> {
+    let mut describer = PacketDescriber::new();
+    decode_packets(data)
+        .iter()
+        .map(|packet| describer.describe(packet))
+        .collect()
+}
 
-        // Length = 6 for 2-byte data (instr + err + data + crc)
-        let length = u16::from_le_bytes([slice[5], slice[6]]);
-        if length != 6 {
-            continue;
+/// Describe an instruction packet: instruction, target IDs, registers and
+/// written values.
+pub fn describe_instruction(packet: &InstructionPacket) -> String {
+    let p = &packet.params;
+    let u16_at = |i: usize| u16::from_le_bytes([p[i], p[i + 1]]);
+    let target = target_label(packet.id);
+
+    let Some(name) = instruction_name(packet.instruction) else {
+        return format!(
+            "INSTRUCTION 0x{:02X} {} {}",
+            packet.instruction,
+            target,
+            hex_label(p)
+        );
+    };
+
+    let detail = match packet.instruction {
+        instruction::PING | instruction::ACTION | instruction::REBOOT => Some(target.clone()),
+        instruction::READ if p.len() >= 4 => {
+            Some(format!("{} {}", target, block_label(u16_at(0), u16_at(2))))
         }
+        instruction::WRITE | instruction::REG_WRITE if p.len() > 2 => {
+            Some(format!("{} {}", target, block_values(u16_at(0), &p[2..])))
+        }
+        instruction::FACTORY_RESET if !p.is_empty() => {
+            let mode = match p[0] {
+                0xFF => "all".to_string(),
+                0x01 => "except ID".to_string(),
+                0x02 => "except ID and baud rate".to_string(),
+                other => format!("mode 0x{:02X}", other),
+            };
+            Some(format!("{} ({})", target, mode))
+        }
+        instruction::SYNC_READ | instruction::FAST_SYNC_READ if p.len() >= 4 => Some(format!(
+            "{} from {:?}",
+            block_label(u16_at(0), u16_at(2)),
+            &p[4..]
+        )),
+        instruction::SYNC_WRITE if p.len() >= 4 => {
+            let (addr, len) = (u16_at(0), u16_at(2) as usize);
+            let entries: Vec<String> = p[4..]
+                .chunks_exact(1 + len)
+                .map(|e| format!("{}: {}", e[0], block_values(addr, &e[1..])))
+                .collect();
+            Some(entries.join("; "))
+        }
+        instruction::BULK_READ => {
+            let entries: Vec<String> = p
+                .chunks_exact(5)
+                .map(|e| {
+                    let (addr, len) = (
+                        u16::from_le_bytes([e[1], e[2]]),
+                        u16::from_le_bytes([e[3], e[4]]),
+                    );
+                    format!("{}: {}", e[0], block_label(addr, len))
+                })
+                .collect();
+            Some(entries.join("; "))
+        }
+        instruction::BULK_WRITE => {
+            let mut entries = Vec::new();
+            let mut rest = &p[..];
+            while rest.len() >= 5 {
+                let addr = u16::from_le_bytes([rest[1], rest[2]]);
+                let len = (u16::from_le_bytes([rest[3], rest[4]]) as usize).min(rest.len() - 5);
+                entries.push(format!(
+                    "{}: {}",
+                    rest[0],
+                    block_values(addr, &rest[5..5 + len])
+                ));
+                rest = &rest[5 + len..];
+            }
+            Some(entries.join("; "))
+        }
+        _ => None,
+    };
 
-        let error = slice[8];
-        if error != 0 {
-            continue;
+    match detail {
+        Some(detail) => format!("{} {}", name, detail),
+        None => format!("{} {} {}", name, target, hex_label(p)),
+    }
+}
+
+/// Describe a status packet, decoding its values from the `request` it
+/// answers if known.
+pub fn describe_status(status: &StatusPacket, request: Option<&InstructionPacket>) -> String {
+    let p = &status.params;
+    let request = request.map(|r| (r.instruction, &r.params[..]));
+    let u16_in = |b: &[u8], i: usize| u16::from_le_bytes([b[i], b[i + 1]]);
+
+    let detail = match request {
+        // FAST_SYNC_READ: one broadcast packet with a segment per motor
+        Some((instruction::FAST_SYNC_READ, rp)) if status.id == BROADCAST_ID && rp.len() >= 4 => {
+            let (addr, size) = (u16_in(rp, 0), u16_in(rp, 2) as usize);
+            let mut bytes = vec![status.error];
+            bytes.extend_from_slice(p);
+            let segments: Vec<String> = bytes
+                .chunks(size + 4)
+                .filter(|seg| seg.len() >= size + 2)
+                .map(|seg| {
+                    let mut line = format!("{}: {}", seg[1], block_values(addr, &seg[2..2 + size]));
+                    if seg[0] != 0 {
+                        line.push_str(&format!(" ({})", StatusError(seg[0])));
+                    }
+                    line
+                })
+                .collect();
+            return format!("STATUS {} {}", target_label(status.id), segments.join("; "));
+        }
+        _ if p.is_empty() => None,
+        Some((instruction::PING, _)) if p.len() == 3 => {
+            let model = u16::from_le_bytes([p[0], p[1]]);
+            let name = model_name(model)
+                .map(|n| format!(" ({})", n))
+                .unwrap_or_default();
+            Some(format!("model {}{}, firmware {}", model, name, p[2]))
         }
+        Some((instruction::READ, rp)) | Some((instruction::SYNC_READ, rp)) if rp.len() >= 4 => {
+            Some(block_values(u16_in(rp, 0), p))
+        }
+        Some((instruction::BULK_READ, rp)) => rp
+            .chunks_exact(5)
+            .find(|entry| entry[0] == status.id)
+            .map(|entry| block_values(u16_in(entry, 1), p)),
+        _ => None,
+    };
+
+    let mut line = format!("STATUS {}", target_label(status.id));
+    match detail {
+        Some(detail) => line.push_str(&format!(" {}", detail)),
+        None if !p.is_empty() => line.push_str(&format!(" {}", hex_label(p))),
+        None => {}
+    }
+    if status.error != 0 || p.is_empty() {
+        line.push_str(&format!(" ({})", StatusError(status.error)));
+    }
+    line
+}
 
-        let motor_id = slice[4];
-        let value = i16::from_le_bytes([slice[9], slice[10]]);
+/// Name of an instruction code, if known.
+fn instruction_name(code: u8) -> Option<&'static str> {
+    Some(match code {
+        instruction::PING => "PING",
+        instruction::READ => "READ",
+        instruction::WRITE => "WRITE",
+        instruction::REG_WRITE => "REG_WRITE",
+        instruction::ACTION => "ACTION",
+        instruction::FACTORY_RESET => "FACTORY_RESET",
+        instruction::REBOOT => "REBOOT",
+        instruction::SYNC_READ => "SYNC_READ",
+        instruction::SYNC_WRITE => "SYNC_WRITE",
+        instruction::FAST_SYNC_READ => "FAST_SYNC_READ",
+        instruction::BULK_READ => "BULK_READ",
+        instruction::BULK_WRITE => "BULK_WRITE",
+        instruction::STATUS => "STATUS",
+        _ => return None,
+    })
+}
 
-        results.push((motor_id, value));
+/// `ID 11`, or `broadcast`.
+fn target_label(id: u8) -> String {
+    if id == BROADCAST_ID {
+        "broadcast".to_string()
+    } else {
+        format!("ID {}", id)
     }
+}
+
+/// Name of the register at `addr` if it is `len` bytes long, otherwise
+/// `address 132, 10 bytes`.
+fn block_label(addr: u16, len: u16) -> String {
+    match register_at(addr) {
+        Some(reg) if reg.size == len => reg.name.to_string(),
+        _ => format!("address {}, {} bytes", addr, len),
+    }
+}
+
+/// Decode every register fully contained in a block of data read from or
+/// written to `addr`, e.g. `Goal Position = 2048`. Falls back to hex.
+fn block_values(addr: u16, data: &[u8]) -> String {
+    let end = addr as usize + data.len();
+    let mut regs: Vec<&RegisterInfo> = CONTROL_TABLE
+        .iter()
+        .filter(|r| r.address >= addr && (r.address + r.size) as usize <= end)
+        // One name per address (e.g. Present Load, not Present Current)
+        .filter(|r| register_at(r.address).map(|first| first.name) == Some(r.name))
+        .collect();
+    regs.sort_by_key(|r| r.address);
+
+    if regs.is_empty() {
+        return hex_label(data);
+    }
+    regs.iter()
+        .map(|r| {
+            let start = (r.address - addr) as usize;
+            format!(
+                "{} = {}",
+                r.name,
+                r.decode(&data[start..start + r.size as usize])
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
 
-    results
+/// Bytes as `[FF FF FD 00]`.
+fn hex_label(data: &[u8]) -> String {
+    let bytes: Vec<String> = data.iter().map(|b| format!("{:02X}", b)).collect();
+    format!("[{}]", bytes.join(" "))
 }
 
 // ============================================================================
@@ -0,0 +1,228 @@
+//! Structured errors for the Reachy Mini API.
+//!
+//! Every fallible function in the crate reports failures as a [`ReachyError`].
+//! When crossing into JavaScript, it becomes a `ReachyError` instance (a
+//! subclass of `Error`) carrying a stable `code` string plus the variant's
+//! fields, so callers can branch on `e.code` instead of matching messages:
+//!
+//! ```javascript
+//! try {
+//!     await get_head_pose();
+//! } catch (e) {
+//!     if (e.code === "NOT_CONNECTED") await connect();
+//!     else if (e.code === "MOTOR_HARDWARE_ERROR") console.warn(`Motor ${e.id}: 0x${e.bits.toString(16)}`);
+//!     else throw e;
+//! }
+//! ```
+
+use std::fmt;
+
+use wasm_bindgen::prelude::*;
+
+use crate::dynamixel::ParseError;
+
+#[wasm_bindgen(inline_js = r#"
+export class ReachyError extends Error {
+    constructor(code, message, fields) {
+        super(message);
+        this.name = "ReachyError";
+        this.code = code;
+        Object.assign(this, fields);
+    }
+}
+"#)]
+extern "C" {
+    #[wasm_bindgen(js_name = ReachyError)]
+    type JsReachyError;
+
+    #[wasm_bindgen(constructor, js_class = "ReachyError")]
+    fn new(code: &str, message: &str, fields: &js_sys::Object) -> JsReachyError;
+}
+
+/// Error returned by the Reachy Mini API.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReachyError {
+    /// No connection to the robot; call `connect()` first
+    NotConnected,
+    /// No connection to the named media stream (`"video"` or `"audio"`)
+    StreamNotConnected(&'static str),
+    /// Opening the connection failed
+    ConnectionFailed(String),
+    /// The connection was closed by the other side
+    TransportClosed,
+    /// Reading from or writing to the connection failed
+    Transport(String),
+    /// Some motors didn't answer, so the requested data is incomplete
+    MissingMotors { ids: Vec<u8> },
+    /// No response arrived within the I/O timeout
+    Timeout { timeout_ms: u32, attempts: u32 },
+    /// A status packet could not be decoded
+    Parse(ParseError),
+    /// A motor answered with a non-zero error byte in its status packet
+    MotorStatusError { id: u8, status: u8 },
+    /// A motor reported bits in its Hardware Error Status register
+    MotorHardwareError { id: u8, bits: u8 },
+    /// Inverse kinematics has no solution for the requested pose: the rods
+    /// of these head branches (0 for head_1) can't reach their motor arm
+    UnreachablePose { branches: Vec<usize> },
+    /// A commanded joint angle or pose value is outside the configured
+    /// limits (see `set_limits`), with the `"reject"` policy
+    LimitExceeded {
+        /// Joint or pose axis, e.g. `head_3` or `pitch`
+        limit: String,
+        value: f32,
+        min: f32,
+        max: f32,
+    },
+    /// An argument has the wrong shape or value
+    InvalidArgument(String),
+    /// The operation is not possible in the current state
+    InvalidState(String),
+    /// A browser media API (camera, microphone, canvas) failed
+    Media(String),
+}
+
+impl ReachyError {
+    /// Stable, machine-readable error code exposed to JavaScript as `e.code`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ReachyError::NotConnected => "NOT_CONNECTED",
+            ReachyError::StreamNotConnected(_) => "STREAM_NOT_CONNECTED",
+            ReachyError::ConnectionFailed(_) => "CONNECTION_FAILED",
+            ReachyError::TransportClosed => "TRANSPORT_CLOSED",
+            ReachyError::Transport(_) => "TRANSPORT_ERROR",
+            ReachyError::MissingMotors { .. } => "MISSING_MOTORS",
+            ReachyError::Timeout { .. } => "TIMEOUT",
+            ReachyError::Parse(_) => "PARSE_ERROR",
+            ReachyError::MotorStatusError { .. } => "MOTOR_STATUS_ERROR",
+            ReachyError::MotorHardwareError { .. } => "MOTOR_HARDWARE_ERROR",
+            ReachyError::UnreachablePose { .. } => "UNREACHABLE_POSE",
+            ReachyError::LimitExceeded { .. } => "LIMIT_EXCEEDED",
+            ReachyError::InvalidArgument(_) => "INVALID_ARGUMENT",
+            ReachyError::InvalidState(_) => "INVALID_STATE",
+            ReachyError::Media(_) => "MEDIA_ERROR",
+        }
+    }
+
+    /// Variant-specific fields copied onto the JavaScript error object.
+    fn fields(&self) -> js_sys::Object {
+        let fields = js_sys::Object::new();
+        let set = |key: &str, value: JsValue| {
+            let _ = js_sys::Reflect::set(&fields, &key.into(), &value);
+        };
+
+        match self {
+            ReachyError::Timeout {
+                timeout_ms,
+                attempts,
+            } => {
+                set("timeout_ms", (*timeout_ms).into());
+                set("attempts", (*attempts).into());
+            }
+            ReachyError::StreamNotConnected(stream) => set("stream", (*stream).into()),
+            ReachyError::MissingMotors { ids } => set(
+                "missing_ids",
+                ids.iter()
+                    .map(|&id| JsValue::from(id))
+                    .collect::<js_sys::Array>()
+                    .into(),
+            ),
+            ReachyError::Parse(e) => set("reason", e.code().into()),
+            ReachyError::UnreachablePose { branches } => set(
+                "branches",
+                branches
+                    .iter()
+                    .map(|&k| JsValue::from(k as u32))
+                    .collect::<js_sys::Array>()
+                    .into(),
+            ),
+            // Not `name`, which would replace the JS `Error.name`
+            ReachyError::LimitExceeded {
+                limit,
+                value,
+                min,
+                max,
+            } => {
+                set("limit", limit.as_str().into());
+                set("value", (*value).into());
+                set("min", (*min).into());
+                set("max", (*max).into());
+            }
+            ReachyError::MotorStatusError { id, status } => {
+                set("id", (*id).into());
+                set("status", (*status).into());
+            }
+            ReachyError::MotorHardwareError { id, bits } => {
+                set("id", (*id).into());
+                set("bits", (*bits).into());
+            }
+            _ => {}
+        }
+
+        fields
+    }
+}
+
+impl fmt::Display for ReachyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReachyError::NotConnected => {
+                write!(f, "Not connected to Reachy Mini. Call connect() first.")
+            }
+            ReachyError::StreamNotConnected(stream) => write!(
+                f,
+                "Not connected to {0} stream. Call connect_{0}_stream() first.",
+                stream
+            ),
+            ReachyError::ConnectionFailed(msg) => write!(f, "{}", msg),
+            ReachyError::TransportClosed => write!(f, "Connection closed"),
+            ReachyError::Transport(msg) => write!(f, "{}", msg),
+            ReachyError::MissingMotors { ids } => write!(f, "No response from motors {:?}", ids),
+            ReachyError::Timeout {
+                timeout_ms,
+                attempts,
+            } => write!(
+                f,
+                "No response within {} ms after {} attempt(s)",
+                timeout_ms, attempts
+            ),
+            ReachyError::Parse(e) => write!(f, "{}", e),
+            ReachyError::MotorStatusError { id, status } => {
+                write!(f, "Motor {} error: 0x{:02X}", id, status)
+            }
+            ReachyError::MotorHardwareError { id, bits } => {
+                write!(f, "Motor {} hardware error: 0x{:02X}", id, bits)
+            }
+            ReachyError::UnreachablePose { branches } => {
+                write!(f, "Pose is unreachable by head branches {:?}", branches)
+            }
+            ReachyError::LimitExceeded {
+                limit,
+                value,
+                min,
+                max,
+            } => write!(
+                f,
+                "{} = {} is outside the limits [{}, {}]",
+                limit, value, min, max
+            ),
+            ReachyError::InvalidArgument(msg) => write!(f, "{}", msg),
+            ReachyError::InvalidState(msg) => write!(f, "{}", msg),
+            ReachyError::Media(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ReachyError {}
+
+impl From<ParseError> for ReachyError {
+    fn from(e: ParseError) -> Self {
+        ReachyError::Parse(e)
+    }
+}
+
+impl From<ReachyError> for JsValue {
+    fn from(e: ReachyError) -> Self {
+        JsReachyError::new(e.code(), &e.to_string(), &e.fields()).into()
+    }
+}
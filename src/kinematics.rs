@@ -1,7 +1,106 @@
-use nalgebra::{DVector, Matrix3, Matrix3x6, Matrix4, MatrixXx6, Vector3};
+use nalgebra::{
+    DMatrix, DVector, Matrix3, Matrix3x6, Matrix4, Matrix6, MatrixXx6, Rotation3, Vector3, Vector6,
+};
+
+use crate::error::ReachyError;
 
 const HEAD_Z_OFFSET: f32 = 0.172;
 
+/// Negative discriminants down to this value are rounding errors at the edge
+/// of the workspace, where the rod is tangent to the motor arm's circle
+const REACH_TOLERANCE: f32 = 1e-9;
+
+/// Discriminant, in units of the rod length to the fourth, kept by
+/// [`Kinematics::nearest_reachable_pose`] so its result stays reachable
+/// after rounding
+const PROJECTION_MARGIN: f32 = 1e-4;
+
+/// Iterations of [`Kinematics::nearest_reachable_pose`]
+const PROJECTION_ITERATIONS: usize = 50;
+
+/// Largest move of one projection iteration, in meters and radians
+const PROJECTION_MAX_STEP: (f32, f32) = (0.005, 0.05);
+
+/// Move under which the projection has settled, in meters and radians
+const PROJECTION_TOLERANCE: (f32, f32) = (1e-5, 1e-4);
+
+/// Bisection steps from the neutral pose when the projection doesn't settle
+const PROJECTION_BISECTIONS: usize = 30;
+
+/// Relative cost of position and orientation errors between two poses.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoseWeights {
+    /// Cost of one meter of position error
+    pub position: f32,
+    /// Cost of one radian of orientation error
+    pub orientation: f32,
+}
+
+impl Default for PoseWeights {
+    /// One millimeter costs as much as one degree.
+    fn default() -> Self {
+        Self {
+            position: 1000.0,
+            orientation: 1.0_f32.to_degrees(),
+        }
+    }
+}
+
+impl PoseWeights {
+    /// Weighted distance between two poses: the position error and the angle
+    /// of the rotation between their orientations, combined as
+    /// `sqrt((position * meters)^2 + (orientation * radians)^2)`.
+    pub fn distance(&self, a: &Matrix4<f32>, b: &Matrix4<f32>) -> f32 {
+        let translation = (b.fixed_view::<3, 1>(0, 3) - a.fixed_view::<3, 1>(0, 3)).norm();
+        let angle = rotation_of(a).angle_to(&rotation_of(b));
+        (self.position * translation).hypot(self.orientation * angle)
+    }
+
+    /// Weights of a `[translation, rotation vector]` step.
+    fn scale(&self) -> Vector6<f32> {
+        let (p, o) = (self.position, self.orientation);
+        Vector6::new(p, p, p, o, o, o)
+    }
+
+    /// Squared weights of a `[translation, rotation vector]` step.
+    fn metric(&self) -> Vector6<f32> {
+        self.scale().component_mul(&self.scale())
+    }
+}
+
+/// Conditioning of the head at one pose, from the singular values of its
+/// [Jacobian](Kinematics::jacobian) with head motions measured by
+/// [`PoseWeights`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Manipulability {
+    /// Largest over smallest singular value: 1 when the head moves as easily
+    /// in every direction, growing without bound near a singularity
+    pub condition_number: f32,
+    /// Product of the singular values (Yoshikawa's measure), the volume of
+    /// head motions reached with unit joint speeds: 0 where the head loses a
+    /// direction of motion, at the edge of the workspace
+    pub manipulability: f32,
+    /// Smallest joint motion in radians moving the head by one unit of
+    /// weighted distance: 0 at a parallel singularity, where the head could
+    /// move with its joints locked
+    pub singularity_distance: f32,
+}
+
+/// Rotation part of a homogeneous transform.
+fn rotation_of(t: &Matrix4<f32>) -> Rotation3<f32> {
+    Rotation3::from_matrix(&t.fixed_view::<3, 3>(0, 0).into_owned())
+}
+
+/// `target` moved by `offset`: a translation followed by a rotation vector
+/// in the target's frame.
+fn offset_pose(target: &Matrix4<f32>, offset: &Vector6<f32>) -> Matrix4<f32> {
+    let rotation = rotation_of(target) * Rotation3::new(offset.fixed_rows::<3>(3).into_owned());
+    let mut t = rotation.to_homogeneous();
+    let translation = target.fixed_view::<3, 1>(0, 3) + offset.fixed_rows::<3>(0);
+    t.fixed_view_mut::<3, 1>(0, 3).copy_from(&translation);
+    t
+}
+
 struct Branch {
     branch_platform: Vector3<f32>,
     t_world_motor: Matrix4<f32>,
@@ -71,13 +170,18 @@ impl Kinematics {
                 * ((angle + std::f32::consts::PI) * (1.0 / (2.0 * std::f32::consts::PI))).floor()
     }
 
+    /// Inverse kinematics with the body yaw limited, returning the body yaw
+    /// followed by the head joint angles.
+    ///
+    /// # Errors
+    /// Same as [`inverse_kinematics`](Self::inverse_kinematics).
     pub fn inverse_kinematics_safe(
         &mut self,
         t_world_platform: Matrix4<f32>,
         body_yaw: Option<f32>,
         max_relative_yaw: Option<f32>,
         max_body_yaw: Option<f32>,
-    ) -> Vec<f32> {
+    ) -> Result<Vec<f32>, ReachyError> {
         let mut joint_angles: Vec<f32> = vec![0.0; self.branches.len() + 1];
         let mut body_yaw_target = 0.0;
         // if body yaw is specified, rotate the platform accordingly
@@ -87,10 +191,9 @@ impl Kinematics {
             // relative yaw is the yaw difference between the current platform yaw and body yaw
             // it should stays within +/- max_relative_yaw
             if let Some(max_rel_yaw) = max_relative_yaw {
-                let current_yaw = t_world_platform[(0, 1)].atan2(t_world_platform[(0, 0)]);
+                let current_yaw = t_world_platform[(1, 0)].atan2(t_world_platform[(0, 0)]);
                 let relative_yaw = body_yaw_target - current_yaw;
                 body_yaw_target = current_yaw + relative_yaw.clamp(-max_rel_yaw, max_rel_yaw);
-                body_yaw_target = -body_yaw_target;
             }
             // then clamp the body yaw within +/- max_body_yaw
             // this is physically limited by the mechanical design
@@ -102,84 +205,446 @@ impl Kinematics {
         // construct the joint angles vector
         joint_angles[0] = body_yaw_target;
         joint_angles[1..]
-            .copy_from_slice(&self.inverse_kinematics(t_world_platform, Some(body_yaw_target)));
-        joint_angles
+            .copy_from_slice(&self.inverse_kinematics(t_world_platform, Some(body_yaw_target))?);
+        Ok(joint_angles)
     }
 
-    #[allow(non_snake_case)]
+    /// Joint angles placing the platform at `t_world_platform`, one per branch.
+    ///
+    /// # Errors
+    /// * `ReachyError::UnreachablePose` naming the branches whose rod can't
+    ///   reach the circle of their motor arm
+    /// * `ReachyError::InvalidArgument` if the pose has NaN or infinite values
     pub fn inverse_kinematics(
-        &mut self,
+        &self,
         t_world_platform: Matrix4<f32>,
         body_yaw: Option<f32>,
-    ) -> Vec<f32> {
-        let mut joint_angles: Vec<f32> = vec![0.0; self.branches.len()];
-        let rs = self.motor_arm_length;
-        let rp = self.rod_length;
+    ) -> Result<Vec<f32>, ReachyError> {
+        if t_world_platform.iter().any(|v| !v.is_finite()) {
+            return Err(ReachyError::InvalidArgument(
+                "Pose contains NaN or infinite values".into(),
+            ));
+        }
+
+        let solutions = self.branch_solutions(t_world_platform, body_yaw);
+        let branches: Vec<usize> = solutions
+            .iter()
+            .enumerate()
+            .filter(|(_, angle)| angle.is_none())
+            .map(|(k, _)| k)
+            .collect();
+        if !branches.is_empty() {
+            return Err(ReachyError::UnreachablePose { branches });
+        }
 
-        let mut t_world_platform_target = t_world_platform;
+        Ok(solutions.into_iter().flatten().collect())
+    }
 
-        // if body yaw is specified, rotate the platform accordingly
-        if body_yaw.is_some() {
-            let yaw = body_yaw.unwrap();
-            let rotation = nalgebra::Rotation3::from_axis_angle(
-                &nalgebra::Unit::new_normalize(Vector3::z()),
-                -yaw,
-            );
-            let t_yaw = rotation.to_homogeneous();
-            t_world_platform_target = t_yaw * t_world_platform;
+    /// Whether every branch can reach `t_world_platform`.
+    pub fn is_reachable(&self, t_world_platform: Matrix4<f32>, body_yaw: Option<f32>) -> bool {
+        self.branch_solutions(t_world_platform, body_yaw)
+            .iter()
+            .all(Option::is_some)
+    }
+
+    /// The reachable pose closest to `t_world_platform`, under the distance
+    /// given by `weights`.
+    ///
+    /// Returns `t_world_platform` itself when it is reachable. Otherwise the
+    /// target is projected on the workspace iteratively: each step heads for
+    /// the target, corrected to hold the branches it would push out of reach
+    /// on the edge of the workspace, until the pose settles. The result is
+    /// never farther than the last reachable pose on the straight way from
+    /// the neutral pose to the target, which is used if the projection fails.
+    ///
+    /// # Errors
+    /// * `ReachyError::InvalidArgument` if the pose has NaN or infinite values
+    /// * `ReachyError::UnreachablePose` if even the neutral pose is out of
+    ///   reach for this `body_yaw`
+    pub fn nearest_reachable_pose(
+        &self,
+        t_world_platform: Matrix4<f32>,
+        body_yaw: Option<f32>,
+        weights: PoseWeights,
+    ) -> Result<Matrix4<f32>, ReachyError> {
+        if t_world_platform.iter().any(|v| !v.is_finite()) {
+            return Err(ReachyError::InvalidArgument(
+                "Pose contains NaN or infinite values".into(),
+            ));
+        }
+        if self.is_reachable(t_world_platform, body_yaw) {
+            return Ok(t_world_platform);
         }
 
-        for (k, branch) in self.branches.iter().enumerate() {
-            let t_world_motor_inv = branch.t_world_motor.try_inverse().unwrap();
-            let branch_motor = t_world_motor_inv
-                * t_world_platform_target
-                * Matrix4::new(
-                    1.0,
-                    0.0,
-                    0.0,
-                    branch.branch_platform.x,
-                    0.0,
-                    1.0,
-                    0.0,
-                    branch.branch_platform.y,
-                    0.0,
-                    0.0,
-                    1.0,
-                    branch.branch_platform.z,
-                    0.0,
-                    0.0,
-                    0.0,
-                    1.0,
-                );
-            let px = branch_motor[(0, 3)];
-            let py = branch_motor[(1, 3)];
-            let pz = branch_motor[(2, 3)];
+        let neutral = Matrix4::new_translation(&Vector3::new(0.0, 0.0, HEAD_Z_OFFSET));
+        let branches: Vec<usize> = self
+            .branch_solutions(neutral, body_yaw)
+            .iter()
+            .enumerate()
+            .filter(|(_, angle)| angle.is_none())
+            .map(|(k, _)| k)
+            .collect();
+        if !branches.is_empty() {
+            return Err(ReachyError::UnreachablePose { branches });
+        }
+
+        // Offset of the neutral pose from the target, reachable
+        let mut start = Vector6::zeros();
+        start.fixed_rows_mut::<3>(0).copy_from(
+            &(neutral.fixed_view::<3, 1>(0, 3) - t_world_platform.fixed_view::<3, 1>(0, 3)),
+        );
+        start
+            .fixed_rows_mut::<3>(3)
+            .copy_from(&rotation_of(&t_world_platform).inverse().scaled_axis());
+
+        let mut candidates =
+            vec![self.bisect_reach(&t_world_platform, body_yaw, &start, &Vector6::zeros())];
+        let projected = self.project_on_workspace(&t_world_platform, body_yaw, weights);
+        if self.is_reachable(offset_pose(&t_world_platform, &projected), body_yaw) {
+            candidates.push(projected);
+        }
+
+        let metric = weights.metric();
+        let cost = |offset: &Vector6<f32>| offset.component_mul(offset).dot(&metric);
+        let best = candidates
+            .into_iter()
+            .min_by(|a, b| cost(a).total_cmp(&cost(b)))
+            .unwrap_or(start);
+        Ok(offset_pose(&t_world_platform, &best))
+    }
+
+    /// Offset from `target` minimizing the weighted distance, subject to the
+    /// discriminant of every branch staying above [`PROJECTION_MARGIN`].
+    ///
+    /// Each iteration solves the step toward the target with the
+    /// discriminants linearized, keeping active the branches that are, or
+    /// would be, out of reach, and releasing those pulling away from their
+    /// edge.
+    fn project_on_workspace(
+        &self,
+        target: &Matrix4<f32>,
+        body_yaw: Option<f32>,
+        weights: PoseWeights,
+    ) -> Vector6<f32> {
+        let inverse_metric = weights.metric().map(|w| 1.0 / w);
+        let mut offset = Vector6::zeros();
+
+        for _ in 0..PROJECTION_ITERATIONS {
+            let (margins, gradients) = self.reach_margins(target, body_yaw, &offset);
+            let toward_target = -offset;
+
+            // Active set: branches out of reach or that the free step would push out
+            let mut active: Vec<usize> = (0..margins.len())
+                .filter(|&k| margins[k] + gradients[k].dot(&toward_target) < 0.0)
+                .collect();
+            let mut step = toward_target;
+            for _ in 0..margins.len() * 2 {
+                if active.is_empty() {
+                    step = toward_target;
+                    break;
+                }
+                let g = DMatrix::from_fn(active.len(), 6, |i, j| gradients[active[i]][j]);
+                let g_weighted = DMatrix::from_fn(active.len(), 6, |i, j| {
+                    gradients[active[i]][j] * inverse_metric[j]
+                });
+                let residual = DVector::from_fn(active.len(), |i, _| {
+                    margins[active[i]] + gradients[active[i]].dot(&toward_target)
+                });
+                let Ok(system) = (&g_weighted * g.transpose()).pseudo_inverse(1e-12) else {
+                    break;
+                };
+                // Lagrange multipliers of the active branches' edges
+                let multipliers = -(system * residual);
+
+                step = toward_target + (g_weighted.transpose() * &multipliers).fixed_rows::<6>(0);
+
+                // Release a branch already in reach that the step pulls inward
+                let release = (0..active.len())
+                    .filter(|&i| multipliers[i] < 0.0 && margins[active[i]] >= 0.0)
+                    .min_by(|&a, &b| multipliers[a].total_cmp(&multipliers[b]));
+                // Add a branch the corrected step would push out of reach
+                let violated = (0..margins.len())
+                    .find(|k| !active.contains(k) && margins[*k] + gradients[*k].dot(&step) < 0.0);
+                match (release, violated) {
+                    (Some(i), _) => {
+                        active.remove(i);
+                    }
+                    (None, Some(k)) => active.push(k),
+                    (None, None) => break,
+                }
+            }
+
+            // Linearization only holds close by
+            let (max_translation, max_rotation) = PROJECTION_MAX_STEP;
+            let scale = (max_translation / step.fixed_rows::<3>(0).norm())
+                .min(max_rotation / step.fixed_rows::<3>(3).norm())
+                .min(1.0);
+            let step = step * scale;
+            offset += step;
+
+            let (translation_tolerance, rotation_tolerance) = PROJECTION_TOLERANCE;
+            if step.fixed_rows::<3>(0).norm() < translation_tolerance
+                && step.fixed_rows::<3>(3).norm() < rotation_tolerance
+            {
+                break;
+            }
+        }
+        offset
+    }
+
+    /// Discriminant of each branch at `target` moved by `offset`, minus
+    /// [`PROJECTION_MARGIN`], with its gradient along the offset.
+    fn reach_margins(
+        &self,
+        target: &Matrix4<f32>,
+        body_yaw: Option<f32>,
+        offset: &Vector6<f32>,
+    ) -> (Vec<f32>, Vec<Vector6<f32>>) {
+        let scale = self.rod_length.powi(4);
+        let margins = |offset: &Vector6<f32>| -> Vec<f32> {
+            self.branch_anchors(offset_pose(target, offset), body_yaw)
+                .iter()
+                .map(|p| self.discriminant(p) / scale - PROJECTION_MARGIN)
+                .collect()
+        };
+
+        // Central differences, in meters and radians
+        let h = 1e-3;
+        let mut gradients = vec![Vector6::zeros(); self.branches.len()];
+        for j in 0..6 {
+            let mut delta = Vector6::zeros();
+            delta[j] = h;
+            let ahead = margins(&(offset + delta));
+            let behind = margins(&(offset - delta));
+            for k in 0..gradients.len() {
+                gradients[k][j] = (ahead[k] - behind[k]) / (2.0 * h);
+            }
+        }
+        (margins(offset), gradients)
+    }
+
+    /// Last reachable offset on the segment from `reachable` to `unreachable`.
+    fn bisect_reach(
+        &self,
+        target: &Matrix4<f32>,
+        body_yaw: Option<f32>,
+        reachable: &Vector6<f32>,
+        unreachable: &Vector6<f32>,
+    ) -> Vector6<f32> {
+        let (mut inside, mut outside) = (*reachable, *unreachable);
+        for _ in 0..PROJECTION_BISECTIONS {
+            let middle = (inside + outside) * 0.5;
+            if self.is_reachable(offset_pose(target, &middle), body_yaw) {
+                inside = middle;
+            } else {
+                outside = middle;
+            }
+        }
+        inside
+    }
+
+    /// Jacobian of the head at `t_world_platform`: the platform twist for a
+    /// unit speed of each head joint, one column per branch.
+    ///
+    /// Twists are `[vx, vy, vz, wx, wy, wz]` in the world frame, the velocity
+    /// of the platform origin in meters per radian followed by the angular
+    /// velocity in radians per radian. Since the rods keep their length, the
+    /// arm tip and the platform anchor of each branch move equally along the
+    /// rod, which relates the twist to the joint speeds.
+    ///
+    /// # Errors
+    /// * `ReachyError::UnreachablePose` naming the branches that can't reach
+    ///   the pose
+    /// * `ReachyError::InvalidArgument` if the pose has NaN or infinite values,
+    ///   or is a parallel singularity, where the Jacobian is infinite
+    /// * `ReachyError::InvalidState` unless the head has 6 branches
+    pub fn jacobian(
+        &self,
+        t_world_platform: Matrix4<f32>,
+        body_yaw: Option<f32>,
+    ) -> Result<Matrix6<f32>, ReachyError> {
+        let (constraints, joints) = self.rod_constraints(t_world_platform, body_yaw)?;
+        let inverse = constraints.try_inverse().ok_or_else(|| {
+            ReachyError::InvalidArgument("Pose is a singularity of the head".into())
+        })?;
+        Ok(inverse * Matrix6::from_diagonal(&joints))
+    }
+
+    /// Conditioning of the head at `t_world_platform`, from its
+    /// [`jacobian`](Self::jacobian) with head motions measured by `weights`.
+    ///
+    /// A parallel singularity isn't an error: its condition number and
+    /// manipulability are infinite, and its distance to singularity 0.
+    ///
+    /// # Errors
+    /// Same as [`jacobian`](Self::jacobian), except for singularities.
+    pub fn manipulability(
+        &self,
+        t_world_platform: Matrix4<f32>,
+        body_yaw: Option<f32>,
+        weights: PoseWeights,
+    ) -> Result<Manipulability, ReachyError> {
+        let (constraints, joints) = self.rod_constraints(t_world_platform, body_yaw)?;
+        let Some(inverse) = constraints.try_inverse() else {
+            return Ok(Manipulability {
+                condition_number: f32::INFINITY,
+                manipulability: f32::INFINITY,
+                singularity_distance: 0.0,
+            });
+        };
+
+        let weighted =
+            Matrix6::from_diagonal(&weights.scale()) * inverse * Matrix6::from_diagonal(&joints);
+        let singular_values = weighted.singular_values();
+        let (largest, smallest) = (singular_values.max(), singular_values.min());
+        Ok(Manipulability {
+            condition_number: largest / smallest,
+            manipulability: singular_values.product(),
+            singularity_distance: 1.0 / largest,
+        })
+    }
+
+    /// Velocity constraints of the rods at a pose, `constraints * twist =
+    /// joints .* joint_speeds` for a twist in the world frame.
+    ///
+    /// Row `k` is the rod of branch `k` times the velocity of its platform
+    /// anchor, and `joints[k]` the rod times the velocity of its arm tip for
+    /// a unit joint speed.
+    fn rod_constraints(
+        &self,
+        t_world_platform: Matrix4<f32>,
+        body_yaw: Option<f32>,
+    ) -> Result<(Matrix6<f32>, Vector6<f32>), ReachyError> {
+        if self.branches.len() != 6 {
+            return Err(ReachyError::InvalidState(format!(
+                "The Jacobian requires 6 branches, got {}",
+                self.branches.len()
+            )));
+        }
+        let angles = self.inverse_kinematics(t_world_platform, body_yaw)?;
+        let t_platform_body = Self::body_frame(t_world_platform, body_yaw)
+            .try_inverse()
+            .unwrap();
+
+        let mut constraints = Matrix6::zeros();
+        let mut joints = Vector6::zeros();
+        for (k, (branch, angle)) in self.branches.iter().zip(angles).enumerate() {
+            // Arm tip and its velocity for a unit joint speed, in the platform frame
+            let t_platform_motor = t_platform_body * branch.t_world_motor;
+            let (sin, cos) = angle.sin_cos();
+            let arm_motor = self.motor_arm_length * Vector3::new(cos, sin, 0.0);
+            let arm_platform = (t_platform_motor * arm_motor.push(1.0))
+                .fixed_rows::<3>(0)
+                .into_owned();
+            let arm_velocity = t_platform_motor.fixed_view::<3, 3>(0, 0)
+                * (self.motor_arm_length * Vector3::new(-sin, cos, 0.0));
+
+            let rod = arm_platform - branch.branch_platform;
+            constraints
+                .row_mut(k)
+                .copy_from(&(rod.transpose() * branch.jacobian));
+            joints[k] = rod.dot(&arm_velocity);
+        }
+
+        // Twists above are in the platform frame
+        let rotation = rotation_of(&t_world_platform).into_inner();
+        let mut world_to_platform = Matrix6::zeros();
+        world_to_platform
+            .fixed_view_mut::<3, 3>(0, 0)
+            .copy_from(&rotation.transpose());
+        world_to_platform
+            .fixed_view_mut::<3, 3>(3, 3)
+            .copy_from(&rotation.transpose());
+        Ok((constraints * world_to_platform, joints))
+    }
+
+    /// `t_world_platform` in the frame of the body turned by `body_yaw`.
+    fn body_frame(t_world_platform: Matrix4<f32>, body_yaw: Option<f32>) -> Matrix4<f32> {
+        match body_yaw {
+            Some(yaw) => {
+                Rotation3::from_axis_angle(&Vector3::z_axis(), -yaw).to_homogeneous()
+                    * t_world_platform
+            }
+            None => t_world_platform,
+        }
+    }
+
+    /// Joint angle of each branch, `None` where the rod can't reach the
+    /// circle of the motor arm.
+    fn branch_solutions(
+        &self,
+        t_world_platform: Matrix4<f32>,
+        body_yaw: Option<f32>,
+    ) -> Vec<Option<f32>> {
+        let rs = self.motor_arm_length;
+        let rp = self.rod_length;
+
+        let mut joint_angles = Vec::with_capacity(self.branches.len());
+        let anchors = self.branch_anchors(t_world_platform, body_yaw);
+        for (branch, p) in self.branches.iter().zip(anchors) {
+            let (px, py, pz) = (p.x, p.y, p.z);
 
             let x = px.powi(2) + 2.0 * px * rs + py.powi(2) + pz.powi(2) - rp.powi(2) + rs.powi(2);
-            let y = 2.0 * py * rs
-                + branch.solution
-                    * (-(px.powi(4))
-                        - 2.0 * px.powi(2) * py.powi(2)
-                        - 2.0 * px.powi(2) * pz.powi(2)
-                        + 2.0 * px.powi(2) * rp.powi(2)
-                        + 2.0 * px.powi(2) * rs.powi(2)
-                        - py.powi(4)
-                        - 2.0 * py.powi(2) * pz.powi(2)
-                        + 2.0 * py.powi(2) * rp.powi(2)
-                        + 2.0 * py.powi(2) * rs.powi(2)
-                        - pz.powi(4)
-                        + 2.0 * pz.powi(2) * rp.powi(2)
-                        - 2.0 * pz.powi(2) * rs.powi(2)
-                        - rp.powi(4)
-                        + 2.0 * rp.powi(2) * rs.powi(2)
-                        - rs.powi(4))
-                    .sqrt();
-
-            joint_angles[k] = Self::wrap_angle(2.0 * y.atan2(x));
+            let discriminant = self.discriminant(&p);
+            // Negative: the rod is too short or too long for the arm
+            if discriminant.is_nan() || discriminant < -REACH_TOLERANCE {
+                joint_angles.push(None);
+                continue;
+            }
+            let y = 2.0 * py * rs + branch.solution * discriminant.max(0.0).sqrt();
+
+            joint_angles.push(Some(Self::wrap_angle(2.0 * y.atan2(x))));
         }
         joint_angles
     }
 
+    /// Platform anchor of each branch, in the frame of its motor.
+    fn branch_anchors(
+        &self,
+        t_world_platform: Matrix4<f32>,
+        body_yaw: Option<f32>,
+    ) -> Vec<Vector3<f32>> {
+        // if body yaw is specified, rotate the platform accordingly
+        let t_world_platform_target = Self::body_frame(t_world_platform, body_yaw);
+
+        self.branches
+            .iter()
+            .map(|branch| {
+                let t_world_motor_inv = branch.t_world_motor.try_inverse().unwrap();
+                let branch_motor = t_world_motor_inv
+                    * t_world_platform_target
+                    * Matrix4::new_translation(&branch.branch_platform);
+                Vector3::new(
+                    branch_motor[(0, 3)],
+                    branch_motor[(1, 3)],
+                    branch_motor[(2, 3)],
+                )
+            })
+            .collect()
+    }
+
+    /// Discriminant of the branch equation for an anchor at `p` in the motor
+    /// frame, negative when the rod can't reach the circle of the motor arm.
+    fn discriminant(&self, p: &Vector3<f32>) -> f32 {
+        let rs = self.motor_arm_length;
+        let rp = self.rod_length;
+        let (px, py, pz) = (p.x, p.y, p.z);
+
+        -(px.powi(4)) - 2.0 * px.powi(2) * py.powi(2) - 2.0 * px.powi(2) * pz.powi(2)
+            + 2.0 * px.powi(2) * rp.powi(2)
+            + 2.0 * px.powi(2) * rs.powi(2)
+            - py.powi(4)
+            - 2.0 * py.powi(2) * pz.powi(2)
+            + 2.0 * py.powi(2) * rp.powi(2)
+            + 2.0 * py.powi(2) * rs.powi(2)
+            - pz.powi(4)
+            + 2.0 * pz.powi(2) * rp.powi(2)
+            - 2.0 * pz.powi(2) * rs.powi(2)
+            - rp.powi(4)
+            + 2.0 * rp.powi(2) * rs.powi(2)
+            - rs.powi(4)
+    }
+
     pub fn reset_forward_kinematics(&mut self, t_world_platform: Matrix4<f32>) {
         self.t_world_platform = t_world_platform;
     }
@@ -294,8 +759,6 @@ impl Kinematics {
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
-
     use serde::Deserialize;
 
     use super::*;
@@ -310,8 +773,8 @@ mod tests {
 
     fn initialize_kinematics() -> Kinematics {
         let mut kinematics = Kinematics::new(0.038, 0.09);
-        let data = fs::read_to_string("motors.json").expect("Unable to read file");
-        let motors: Vec<Motor> = serde_json::from_str(&data).expect("Unable to parse JSON");
+        let motors: Vec<Motor> =
+            serde_json::from_str(include_str!("motors.json")).expect("Unable to parse JSON");
         for motor in motors {
             let branch_position = nalgebra::Vector3::new(
                 motor.branch_position[0],
@@ -352,68 +815,193 @@ mod tests {
         kinematics
     }
 
+    /// Head 8 mm up, 5 mm forward, 3 mm right, rolled by 0.1, pitched by
+    /// -0.05 and yawed by 0.2 rad.
+    fn tilted_pose() -> Matrix4<f32> {
+        let mut t = Rotation3::from_euler_angles(0.1, -0.05, 0.2).to_homogeneous();
+        t[(0, 3)] = 0.005;
+        t[(1, 3)] = -0.003;
+        t[(2, 3)] = HEAD_Z_OFFSET + 0.008;
+        t
+    }
+
+    // Reference joint angles, solved in double precision independently of
+    // the closed form of `branch_solutions`: the arm tip (r cos q, r sin q, 0)
+    // is at rod length from the anchor p (in the motor frame of
+    // src/motors.json) when px cos q + py sin q = (r² + |p|² - l²) / 2r, so
+    // q = atan2(py, px) ∓ acos(that / |(px, py)|), the minus root for
+    // `"solution": 0` and the plus root for `"solution": 1`.
+    const NEUTRAL_JOINTS: [f32; 6] = [
+        0.422086902,
+        -0.573021740,
+        0.506827700,
+        -0.506827700,
+        0.573015378,
+        -0.422086902,
+    ];
+    const TILTED_JOINTS: [f32; 6] = [
+        0.749538970,
+        -0.854397362,
+        0.795152544,
+        -0.615087350,
+        0.851181430,
+        -0.526381556,
+    ];
+
     #[test]
     fn test_inverse_kinematics() {
-        let mut kinematics = initialize_kinematics();
-        let t_world_platform =
+        let kinematics = initialize_kinematics();
+        let neutral =
             nalgebra::Matrix4::new_translation(&nalgebra::Vector3::new(0.0, 0.0, HEAD_Z_OFFSET));
-        let r = kinematics.inverse_kinematics(t_world_platform, None);
-        let expected_res = [
-            0.5469084013213722,
-            -0.6911929467384811,
-            0.6290593106168814,
-            -0.6290625053607944,
-            0.6911968541984359,
-            -0.5469156644896231,
-        ];
-        assert!(r
-            .iter()
-            .zip(expected_res.iter())
-            .all(|(a, b)| (a - b).abs() < 1e-6));
+        for (t, expected) in [(neutral, NEUTRAL_JOINTS), (tilted_pose(), TILTED_JOINTS)] {
+            let r = kinematics.inverse_kinematics(t, None).unwrap();
+            assert!(
+                r.iter().zip(expected).all(|(a, b)| (a - b).abs() < 1e-6),
+                "{:?} vs {:?}",
+                r,
+                expected
+            );
+        }
     }
 
     #[test]
     fn test_forward_kinematics() {
+        // The solver, started from the neutral pose, must land on the pose
+        // the reference joint angles were solved for
         let mut kinematics = initialize_kinematics();
-        let joints = vec![0.3, 0.0, 0.0, 0.0, 0.0, 0.0];
-        let mut t = kinematics.forward_kinematics(&joints, None);
-        t[(2, 3)] -= HEAD_Z_OFFSET;
-        let t_flat = t.as_slice().to_vec();
-        let expected_res = [
-            [
-                0.9500544312762279,
-                0.2784767970164817,
-                0.14088027234444309,
-                0.0,
-            ],
-            [
-                -0.303810126556134,
-                0.928527235235177,
-                0.21339301869663957,
-                0.0,
-            ],
-            [
-                -0.07138616542684614,
-                -0.24553583638638127,
-                0.9667554853403684,
-                0.0,
-            ],
-            [
-                -0.032726798589555045,
-                0.01043105987061096,
-                -0.04973316892312707,
-                1.0,
-            ],
-        ];
-        let expected_flat: Vec<f32> = expected_res
-            .iter()
-            .flat_map(|row| row.iter())
-            .copied()
-            .collect();
-        assert!(t_flat
-            .iter()
-            .zip(expected_flat.iter())
-            .all(|(a, b)| (a - b).abs() < 1e-6));
+        let mut t = kinematics.forward_kinematics(&TILTED_JOINTS, None);
+        for _ in 0..100 {
+            t = kinematics.forward_kinematics(&TILTED_JOINTS, None);
+        }
+        let expected = tilted_pose();
+        assert!(
+            t.iter()
+                .zip(expected.iter())
+                .all(|(a, b)| (a - b).abs() < 1e-5),
+            "{} vs {}",
+            t,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_inverse_kinematics_unreachable() {
+        let mut kinematics = initialize_kinematics();
+        let at = |x: f32, y: f32, z: f32| {
+            nalgebra::Matrix4::new_translation(&nalgebra::Vector3::new(x, y, HEAD_Z_OFFSET + z))
+        };
+
+        // Far above: no rod is long enough
+        assert_eq!(
+            kinematics.inverse_kinematics(at(0.0, 0.0, 0.1), None),
+            Err(ReachyError::UnreachablePose {
+                branches: vec![0, 1, 2, 3, 4, 5]
+            })
+        );
+        assert!(!kinematics.is_reachable(at(0.0, 0.0, 0.1), None));
+
+        // Far forward: only the rear branches fail
+        assert_eq!(
+            kinematics.inverse_kinematics(at(0.06, 0.0, 0.0), None),
+            Err(ReachyError::UnreachablePose {
+                branches: vec![2, 3]
+            })
+        );
+
+        assert!(kinematics.is_reachable(at(0.0, 0.0, 0.0), None));
+        assert!(matches!(
+            kinematics.inverse_kinematics(at(f32::NAN, 0.0, 0.0), None),
+            Err(ReachyError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_nearest_reachable_pose() {
+        let kinematics = initialize_kinematics();
+        let weights = PoseWeights::default();
+        let at = |x: f32, y: f32, z: f32| {
+            nalgebra::Matrix4::new_translation(&nalgebra::Vector3::new(x, y, HEAD_Z_OFFSET + z))
+        };
+
+        // Reachable poses are kept as is
+        let reachable = at(0.01, -0.01, 0.0);
+        assert_eq!(
+            kinematics.nearest_reachable_pose(reachable, None, weights),
+            Ok(reachable)
+        );
+
+        // Too far forward: stop a few millimeters short, not back at the neutral pose
+        let target = at(0.06, 0.0, 0.0);
+        let nearest = kinematics
+            .nearest_reachable_pose(target, None, weights)
+            .unwrap();
+        assert!(kinematics.is_reachable(nearest, None));
+        assert!(weights.distance(&target, &nearest) < 5.0);
+
+        // Pushing the result toward the target leaves the workspace
+        let beyond = nearest + (target - nearest) * 0.05;
+        assert!(!kinematics.is_reachable(beyond, None));
+
+        // Far above: still a pose, whatever the weights
+        let target = at(0.0, 0.0, 0.1);
+        for weights in [
+            weights,
+            PoseWeights {
+                position: 1.0,
+                orientation: 100.0,
+            },
+        ] {
+            let nearest = kinematics
+                .nearest_reachable_pose(target, None, weights)
+                .unwrap();
+            assert!(kinematics.is_reachable(nearest, None));
+            assert!(
+                weights.distance(&target, &nearest) < weights.distance(&target, &at(0.0, 0.0, 0.0))
+            );
+        }
+
+        assert!(matches!(
+            kinematics.nearest_reachable_pose(at(0.0, f32::INFINITY, 0.0), None, weights),
+            Err(ReachyError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_inverse_kinematics_safe_limits_body_yaw() {
+        let mut kinematics = initialize_kinematics();
+        let looking = |yaw: f32| {
+            let mut t = nalgebra::Rotation3::from_euler_angles(0.0, 0.0, yaw).to_homogeneous();
+            t[(2, 3)] = HEAD_Z_OFFSET;
+            t
+        };
+
+        // Within reach of the head's yaw: unchanged
+        let r = kinematics
+            .inverse_kinematics_safe(looking(0.5), Some(0.3), Some(0.65), Some(2.8))
+            .unwrap();
+        assert!((r[0] - 0.3).abs() < 1e-6);
+        assert_eq!(
+            r[1..],
+            kinematics
+                .inverse_kinematics(looking(0.5), Some(0.3))
+                .unwrap()[..]
+        );
+
+        // Too far from the head's yaw, either way
+        let r = kinematics
+            .inverse_kinematics_safe(looking(0.0), Some(1.0), Some(0.65), None)
+            .unwrap();
+        assert!((r[0] - 0.65).abs() < 1e-6);
+        let r = kinematics
+            .inverse_kinematics_safe(looking(0.5), Some(-1.0), Some(0.65), None)
+            .unwrap();
+        assert!((r[0] - (0.5 - 0.65)).abs() < 1e-6);
+
+        // Past the mechanical limit
+        let r = kinematics
+            .inverse_kinematics_safe(looking(3.0), Some(3.0), Some(0.65), Some(2.8))
+            .unwrap();
+        assert!((r[0] - 2.8).abs() < 1e-6);
     }
 
     // test ik + fk consistency
@@ -422,7 +1010,9 @@ mod tests {
         let mut kinematics = initialize_kinematics();
         let t_world_platform =
             nalgebra::Matrix4::new_translation(&nalgebra::Vector3::new(0.0, 0.0, HEAD_Z_OFFSET));
-        let r = kinematics.inverse_kinematics(t_world_platform, None);
+        let r = kinematics
+            .inverse_kinematics(t_world_platform, None)
+            .unwrap();
         kinematics.reset_forward_kinematics(t_world_platform);
         let mut t = kinematics.forward_kinematics(&r, None);
         for _ in 0..100 {
@@ -442,7 +1032,9 @@ mod tests {
         let mut kinematics = initialize_kinematics();
         let t_world_platform =
             nalgebra::Matrix4::new_translation(&nalgebra::Vector3::new(0.0, 0.0, HEAD_Z_OFFSET));
-        let r = kinematics.inverse_kinematics(t_world_platform, Some(body_yaw));
+        let r = kinematics
+            .inverse_kinematics(t_world_platform, Some(body_yaw))
+            .unwrap();
         kinematics.reset_forward_kinematics(t_world_platform);
         let mut t = kinematics.forward_kinematics(&r, Some(body_yaw));
         for _ in 0..100 {
@@ -455,4 +1047,86 @@ mod tests {
             .zip(expected_res.iter())
             .all(|(a, b)| (a - b).abs() < 1e-4));
     }
+
+    /// `t` moved by a twist in the world frame for a short time.
+    fn moved(t: &Matrix4<f32>, twist: &Vector6<f32>) -> Matrix4<f32> {
+        let rotation = Rotation3::new(twist.fixed_rows::<3>(3).into_owned()) * rotation_of(t);
+        let mut moved = rotation.to_homogeneous();
+        let translation = t.fixed_view::<3, 1>(0, 3) + twist.fixed_rows::<3>(0);
+        moved.fixed_view_mut::<3, 1>(0, 3).copy_from(&translation);
+        moved
+    }
+
+    #[test]
+    fn test_jacobian() {
+        let kinematics = initialize_kinematics();
+        let mut t = Rotation3::from_euler_angles(0.1, -0.05, 0.3).to_homogeneous();
+        t[(0, 3)] = 0.005;
+        t[(1, 3)] = -0.003;
+        t[(2, 3)] = HEAD_Z_OFFSET + 0.01;
+
+        for body_yaw in [None, Some(0.4)] {
+            let jacobian = kinematics.jacobian(t, body_yaw).unwrap();
+
+            // Joint speeds for each unit twist, by central differences of the
+            // inverse kinematics, are mapped back to that twist
+            let h = 1e-3;
+            for i in 0..6 {
+                let step = Vector6::ith(i, h);
+                let ahead = kinematics.inverse_kinematics(moved(&t, &step), body_yaw);
+                let behind = kinematics.inverse_kinematics(moved(&t, &-step), body_yaw);
+                let speeds = (Vector6::from_vec(ahead.unwrap())
+                    - Vector6::from_vec(behind.unwrap()))
+                    / (2.0 * h);
+                let error = (jacobian * speeds - Vector6::ith(i, 1.0)).norm();
+                assert!(
+                    error < 1e-2,
+                    "body yaw {:?}, twist {}: {}",
+                    body_yaw,
+                    i,
+                    error
+                );
+            }
+        }
+
+        let far = Matrix4::new_translation(&Vector3::new(0.0, 0.0, HEAD_Z_OFFSET + 0.1));
+        assert!(matches!(
+            kinematics.jacobian(far, None),
+            Err(ReachyError::UnreachablePose { .. })
+        ));
+    }
+
+    #[test]
+    fn test_manipulability() {
+        let kinematics = initialize_kinematics();
+        let weights = PoseWeights::default();
+        let neutral = Matrix4::new_translation(&Vector3::new(0.0, 0.0, HEAD_Z_OFFSET));
+
+        let center = kinematics.manipulability(neutral, None, weights).unwrap();
+        assert!(center.condition_number >= 1.0 && center.condition_number.is_finite());
+        assert!(center.manipulability > 0.0);
+        assert!(center.singularity_distance > 0.0);
+
+        // Turning the head with the body changes nothing
+        let turned = Rotation3::from_euler_angles(0.0, 0.0, 0.7).to_homogeneous() * neutral;
+        let with_body = kinematics
+            .manipulability(turned, Some(0.7), weights)
+            .unwrap();
+        assert!((with_body.condition_number / center.condition_number - 1.0).abs() < 1e-3);
+        assert!((with_body.manipulability / center.manipulability - 1.0).abs() < 1e-3);
+
+        // At the edge of the workspace, the head loses a direction of motion
+        let high = Matrix4::new_translation(&Vector3::new(0.0, 0.0, HEAD_Z_OFFSET + 0.1));
+        let edge = kinematics
+            .nearest_reachable_pose(high, None, weights)
+            .unwrap();
+        let edge = kinematics.manipulability(edge, None, weights).unwrap();
+        assert!(
+            edge.condition_number > 10.0 * center.condition_number,
+            "{:?} vs {:?}",
+            edge,
+            center
+        );
+        assert!(edge.manipulability < 0.1 * center.manipulability);
+    }
 }
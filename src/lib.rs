@@ -9,6 +9,7 @@
 //!
 //! | Motor ID | Description        | Joint Name |
 //! |----------|--------------------| -----------|
+//! | 10       | Body rotation      | body_yaw   |
 //! | 11       | Head motor 1       | neck_roll  |
 //! | 12       | Head motor 2       | neck_pitch |
 //! | 13       | Head motor 3       | neck_yaw   |
@@ -30,30 +31,53 @@
 //! 2. WebSerial (falls back if WebSocket unavailable)
 
 mod audio_stream;
+pub mod bus;
+pub mod capture;
 pub mod dynamixel;
+pub mod error;
 pub mod kinematics;
+pub mod limits;
+pub mod link;
+#[cfg(feature = "native")]
+pub mod native;
+pub mod sim;
 mod video_stream;
 
 // Re-export video and audio stream APIs
 pub use audio_stream::*;
 pub use video_stream::*;
 
-use std::cell::RefCell;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
+pub use bus::{Arbiter, IoPolicy, MotorBus, Priority};
+
+use crate::capture::{Capture, Direction};
+use crate::link::{check_link, LinkMonitor, ReconnectPolicy, StateChange};
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
 
 use crate::dynamixel::{
-    address, build_read_packet, build_reboot_packet, build_sync_current_position,
+    address, baud_rate_code, broadcast_ping_window_ms, build_action_packet, build_bulk_read,
+    build_factory_reset_packet, build_fast_sync_read, build_ping_packet, build_read_packet,
+    build_reboot_packet, build_reg_write_info, build_sync_current_position,
     build_sync_read_hardware_error, build_sync_read_load, build_sync_read_temperature,
-    build_sync_write_position_radians, build_sync_write_torque, parse_1byte_packets,
-    parse_2byte_signed_packets, parse_position_packets, parse_status_packet_1byte,
-    parse_status_packet_2byte_signed, raw_to_radians,
+    build_sync_write_position_radians, build_sync_write_torque, build_write_info, missing_ids,
+    parse_1byte_packets, parse_1byte_packets_with_errors, parse_2byte_signed_packets,
+    parse_bulk_read_response, parse_fast_sync_position_with_status, parse_ping_responses,
+    parse_position_packets, parse_position_packets_with_status, parse_status_packet_1byte,
+    parse_status_packet_2byte_signed, parse_write_status, radians_to_raw, raw_to_radians, register,
+    values_by_id, BulkReadEntry, EepromConfig, FactoryResetMode, HardwareErrorStatus, PingResponse,
+    Register, RegisterInfo, StatusError, BROADCAST_ID, MAX_ID,
 };
-use crate::kinematics::Kinematics;
+use crate::error::ReachyError;
+use crate::kinematics::{Kinematics, PoseWeights};
+use crate::limits::{LimitPolicy, Limits};
 
+use futures_util::lock::Mutex;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use gloo::net::websocket::futures::WebSocket;
-use gloo::net::websocket::Message;
+use gloo::net::websocket::{Message, WebSocketError};
 use gloo::utils::document;
 use js_sys::Promise;
 use serde::Deserialize;
@@ -71,15 +95,40 @@ use web_sys::{
 /// Head motor IDs (motors 11-16 form the parallel kinematics mechanism)
 const HEAD_MOTOR_IDS: [u8; 6] = [11, 12, 13, 14, 15, 16];
 
-/// All motor IDs including antennas
+/// Body rotation motor ID
+const BODY_MOTOR_ID: u8 = 10;
+
+/// Body and head motor IDs, which place the head in the world frame
+const BODY_AND_HEAD_MOTOR_IDS: [u8; 7] = [10, 11, 12, 13, 14, 15, 16];
+
+/// All motor IDs including antennas, in the order of the joint arrays of the
+/// API (the body is separate, see `BODY_MOTOR_ID`)
 const ALL_MOTOR_IDS: [u8; 8] = [11, 12, 13, 14, 15, 16, 17, 18];
 
+/// Every motor of the robot, body included
+const ROBOT_MOTOR_IDS: [u8; 9] = [10, 11, 12, 13, 14, 15, 16, 17, 18];
+
 /// Left antenna motor ID
 const LEFT_ANTENNA_ID: u8 = 17;
 
 /// Right antenna motor ID
 const RIGHT_ANTENNA_ID: u8 = 18;
 
+/// Joint names for `ALL_MOTOR_IDS`, in the same order
+const JOINT_NAMES: [&str; 8] = [
+    "head_1",
+    "head_2",
+    "head_3",
+    "head_4",
+    "head_5",
+    "head_6",
+    "l_antenna",
+    "r_antenna",
+];
+
+/// Joint name of `BODY_MOTOR_ID`
+const BODY_JOINT_NAME: &str = "body_yaw";
+
 /// Internal Z offset in meters (head minimum height)
 const HEAD_Z_OFFSET_M: f32 = 0.172;
 
@@ -89,6 +138,18 @@ const HEAD_Z_OFFSET_MM: f32 = 172.0;
 /// Default wait time for serial communication in milliseconds
 const DEFAULT_WAIT_MS: u32 = 10;
 
+/// Baud rate of the serial link to the motors
+const LINK_BAUD_RATE: u32 = 1_000_000;
+
+/// ID of a new Dynamixel motor out of the box
+const FACTORY_MOTOR_ID: u8 = 1;
+
+/// Baud rate of a new Dynamixel motor out of the box
+const FACTORY_BAUD_RATE: u32 = 57_600;
+
+/// Default idle time before a heartbeat is sent, in milliseconds
+const DEFAULT_HEARTBEAT_MS: u32 = 1000;
+
 // ============================================================================
 // Thread-local Storage & Global State
 // ============================================================================
@@ -98,12 +159,49 @@ thread_local! {
     static PLAYBACK_FRAMES: RefCell<Vec<Vec<f32>>> = RefCell::new(Vec::new());
 
     /// Global connection to the robot
-    static GENERIC_PORT: RefCell<Option<Arc<GenericPort>>> = RefCell::new(None);
+    static GENERIC_PORT: RefCell<Option<Rc<GenericPort>>> = RefCell::new(None);
+
+    /// I/O timeout and retry policy applied to new and current connections
+    static IO_POLICY: Cell<IoPolicy> = Cell::new(IoPolicy::default());
+
+    /// Traffic recorded by the bus tap (see `start_bus_capture`)
+    static BUS_CAPTURE: RefCell<Option<Capture>> = const { RefCell::new(None) };
+
+    /// State of the connection in `GENERIC_PORT`
+    static LINK: RefCell<LinkMonitor> = RefCell::new(LinkMonitor::new());
+
+    /// JS callback notified of connection state changes
+    static LINK_CALLBACK: RefCell<Option<js_sys::Function>> = const { RefCell::new(None) };
+
+    /// Backoff between reconnection attempts
+    static RECONNECT_POLICY: Cell<ReconnectPolicy> = Cell::new(ReconnectPolicy::default());
+
+    /// Limits applied to every position command (see `set_limits`)
+    static LIMITS: RefCell<Limits> = RefCell::new(Limits::default());
+
+    /// Whether position reads fail when a motor reports an error in its status packet
+    static FAIL_ON_MOTOR_ALERT: Cell<bool> = const { Cell::new(false) };
 }
 
 /// Flag to signal stopping of continuous operations (FK loop, replay, etc.)
 static STOP_FLAG: AtomicBool = AtomicBool::new(false);
 
+/// Whether position reads use Fast Sync Read instead of Sync Read
+static FAST_SYNC_READ: AtomicBool = AtomicBool::new(false);
+
+/// Whether bus traffic is being recorded into `BUS_CAPTURE`
+static BUS_CAPTURE_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Incremented whenever `GENERIC_PORT` changes, so background tasks and
+/// events of replaced connections are ignored
+static LINK_GENERATION: AtomicU32 = AtomicU32::new(0);
+
+/// Whether lost WebSocket connections are reopened automatically
+static AUTO_RECONNECT: AtomicBool = AtomicBool::new(true);
+
+/// Idle time before a heartbeat is sent, in milliseconds (0 disables it)
+static HEARTBEAT_INTERVAL_MS: AtomicU32 = AtomicU32::new(DEFAULT_HEARTBEAT_MS);
+
 // ============================================================================
 // External JavaScript Bindings
 // ============================================================================
@@ -195,9 +293,13 @@ pub fn main_js() -> Result<(), JsValue> {
 ///   - IP only: `192.168.1.100` (uses default port 8000)
 ///   - `None` to use default address
 ///
+/// Once connected, the link is monitored (see `on_connection_state_change`):
+/// a heartbeat probes the motors when the bus is idle, and a lost WebSocket
+/// connection is reopened automatically with backoff.
+///
 /// # Returns
 /// * `Ok(true)` - Successfully connected
-/// * `Err(JsValue)` - Connection failed
+/// * `Err(ReachyError)` - Connection failed (`code` is `"CONNECTION_FAILED"`)
 ///
 /// # Example
 /// ```javascript
@@ -215,8 +317,18 @@ pub fn main_js() -> Result<(), JsValue> {
 /// ```
 #[wasm_bindgen]
 pub async fn connect(address: Option<String>) -> Result<bool, JsValue> {
-    let port = GenericPort::new(address).await?;
-    GENERIC_PORT.with_borrow_mut(|p| *p = Some(Arc::new(port)));
+    // Stops the heartbeat and any reconnection of the previous connection
+    let generation = LINK_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    update_link(generation, LinkMonitor::connecting);
+
+    let port = match GenericPort::new(address).await {
+        Ok(port) => port,
+        Err(e) => {
+            update_link(generation, LinkMonitor::closed);
+            return Err(e);
+        }
+    };
+    install_port(port);
     console::log_1(&JsValue::from_str("Connected to Reachy Mini"));
     Ok(true)
 }
@@ -229,11 +341,13 @@ pub async fn connect(address: Option<String>) -> Result<bool, JsValue> {
 /// * `Ok(())` on success
 #[wasm_bindgen]
 pub async fn disconnect() -> Result<(), JsValue> {
+    let generation = LINK_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
     GENERIC_PORT.with_borrow_mut(|port| {
         if let Some(p) = port.take() {
             let _ = p.release_lock();
         }
     });
+    update_link(generation, LinkMonitor::closed);
     close_serial_port().await;
     console::log_1(&JsValue::from_str("Disconnected from Reachy Mini"));
     Ok(())
@@ -249,6 +363,182 @@ pub fn is_connected() -> bool {
     GENERIC_PORT.with_borrow(|port| port.is_some())
 }
 
+/// Get the state of the connection to the robot.
+///
+/// # Returns
+/// * `"connecting"` - Connecting, or reconnecting after the connection was lost
+/// * `"open"` - Connected, and the motors answer
+/// * `"degraded"` - Connected, but the motors stopped answering heartbeats
+///   (e.g. the robot is powered off)
+/// * `"closed"` - Not connected
+#[wasm_bindgen]
+pub fn get_connection_state() -> String {
+    LINK.with_borrow(|link| link.state().as_str().to_string())
+}
+
+/// Register a callback notified when the connection state changes.
+///
+/// The callback receives the new and previous states, as returned by
+/// `get_connection_state()`. Pass `null` to remove it.
+///
+/// # Example
+/// ```javascript
+/// on_connection_state_change((state, previous) => {
+///     status.textContent = state;
+///     if (state === "closed" && previous === "connecting") alert("Robot unreachable");
+/// });
+/// ```
+#[wasm_bindgen]
+pub fn on_connection_state_change(callback: Option<js_sys::Function>) {
+    LINK_CALLBACK.with_borrow_mut(|c| *c = callback);
+}
+
+/// Enable or disable automatic reconnection.
+///
+/// When enabled (default), a lost WebSocket connection is reopened to the
+/// same address, waiting 0.5 s before the first attempt and doubling up to
+/// 10 s (see `set_reconnect_backoff`). Calls made meanwhile reject with a
+/// `ReachyError` of code `"TRANSPORT_CLOSED"`. WebSerial connections are not
+/// reopened, as the browser requires a user gesture: call `connect()` again.
+///
+/// # Arguments
+/// * `enabled` - `true` to reconnect automatically, `false` to stay closed
+#[wasm_bindgen]
+pub fn set_auto_reconnect(enabled: bool) {
+    AUTO_RECONNECT.store(enabled, Ordering::Relaxed);
+}
+
+/// Set the delays between reconnection attempts.
+///
+/// # Arguments
+/// * `initial_ms` - Delay before the first attempt, doubled on each attempt (default 500)
+/// * `max_ms` - Upper bound of the delay (default 10000)
+/// * `max_attempts` - Attempts before giving up and staying closed (default: unlimited)
+///
+/// # Example
+/// ```javascript
+/// set_reconnect_backoff(200, 2000, 10);
+/// ```
+#[wasm_bindgen]
+pub fn set_reconnect_backoff(initial_ms: u32, max_ms: u32, max_attempts: Option<u32>) {
+    RECONNECT_POLICY.set(ReconnectPolicy {
+        initial_delay_ms: initial_ms,
+        max_delay_ms: max_ms,
+        max_attempts,
+    });
+}
+
+/// Set how long the bus must be idle before a heartbeat is sent.
+///
+/// The heartbeat reads one byte from motor 11. Two missed heartbeats in a
+/// row make the connection `"degraded"`; the next answer makes it `"open"`
+/// again.
+///
+/// # Arguments
+/// * `ms` - Idle time in milliseconds (default 1000), `0` to disable heartbeats
+#[wasm_bindgen]
+pub fn set_heartbeat_interval(ms: u32) {
+    HEARTBEAT_INTERVAL_MS.store(ms, Ordering::Relaxed);
+}
+
+/// Enable or disable Fast Sync Read for motor position reads.
+///
+/// With Fast Sync Read (instruction 0x8A) all motors answer in a single
+/// combined status packet instead of one packet each, which roughly halves
+/// read latency on the WebSerial link. Requires XL330 firmware support.
+///
+/// # Arguments
+/// * `enabled` - `true` to use Fast Sync Read, `false` for regular Sync Read (default)
+///
+/// # Example
+/// ```javascript
+/// set_fast_sync_read(true);
+/// const pose = await get_head_pose();
+/// ```
+#[wasm_bindgen]
+pub fn set_fast_sync_read(enabled: bool) {
+    FAST_SYNC_READ.store(enabled, Ordering::Relaxed);
+}
+
+/// Set how long to wait for motor responses before giving up.
+///
+/// Applies to every request/response exchange on the current connection and
+/// to future connections. When a request times out (after all retries), the
+/// call rejects with a `ReachyError` whose `code` is `"TIMEOUT"`.
+///
+/// # Arguments
+/// * `ms` - Timeout per attempt in milliseconds (default 100)
+///
+/// # Example
+/// ```javascript
+/// set_io_timeout(250);
+/// try {
+///     await get_all_joints();
+/// } catch (e) {
+///     if (e.code === "TIMEOUT") console.warn("Robot not answering");
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn set_io_timeout(ms: u32) {
+    update_io_policy(|policy| policy.timeout_ms = ms);
+}
+
+/// Set how many times a request is re-sent when responses are missing.
+///
+/// Retries happen when fewer motors answered than expected within the
+/// timeout. The delay before each retry starts at 20 ms and doubles every
+/// attempt.
+///
+/// # Arguments
+/// * `n` - Number of retries after the first attempt (default 0)
+///
+/// # Example
+/// ```javascript
+/// set_io_retries(2);  // up to 3 attempts per request
+/// ```
+#[wasm_bindgen]
+pub fn set_io_retries(n: u32) {
+    update_io_policy(|policy| policy.retries = n);
+}
+
+/// Make position reads fail when a motor is in alert or reports an error.
+///
+/// Motors with a hardware error (overheating, overload, ...) keep answering
+/// with the alert bit set in their status packets, and their positions are
+/// otherwise returned as if they were healthy. When enabled, `get_head_pose`,
+/// `get_all_joints` and the other position reads reject with a `ReachyError`
+/// of code `"MOTOR_STATUS_ERROR"` (fields `id` and `status`) instead.
+/// Use `get_joint_status()` to inspect each motor's status without failing.
+///
+/// # Arguments
+/// * `enabled` - `true` to fail on motor errors, `false` to ignore them (default)
+///
+/// # Example
+/// ```javascript
+/// set_fail_on_motor_alert(true);
+/// try {
+///     await get_all_joints();
+/// } catch (e) {
+///     if (e.code === "MOTOR_STATUS_ERROR") await reboot_motor(e.id);
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn set_fail_on_motor_alert(enabled: bool) {
+    FAIL_ON_MOTOR_ALERT.set(enabled);
+}
+
+/// Apply a change to the global I/O policy and to the current connection.
+fn update_io_policy(f: impl FnOnce(&mut IoPolicy)) {
+    let mut policy = IO_POLICY.get();
+    f(&mut policy);
+    IO_POLICY.set(policy);
+    GENERIC_PORT.with_borrow(|port| {
+        if let Some(p) = port {
+            p.set_io_policy(policy);
+        }
+    });
+}
+
 // ============================================================================
 // Head Pose API (Cartesian Space)
 // ============================================================================
@@ -259,50 +549,62 @@ pub fn is_connected() -> bool {
 /// computed via forward kinematics from the current motor positions.
 ///
 /// # Returns
-/// A vector of 6 floats: `[x, y, z, roll, pitch, yaw]`
+/// A vector of 7 floats: `[x, y, z, roll, pitch, yaw, body_yaw]`
 /// - `x`, `y`, `z`: Position in millimeters
-/// - `roll`, `pitch`, `yaw`: Orientation in degrees
+/// - `roll`, `pitch`, `yaw`: Orientation in degrees, in the world frame
+///   (the head turns with the body)
+/// - `body_yaw`: Rotation of the body in degrees
 ///
 /// # Errors
 /// * Returns error if not connected to the robot
 /// * Returns error if communication fails
+/// * Returns a `MISSING_MOTORS` error (with `missing_ids`) if the body or any
+///   head motor didn't respond
 ///
 /// # Example
 /// ```javascript
 /// const pose = await get_head_pose();
 /// console.log(`Position: (${pose[0]}, ${pose[1]}, ${pose[2]}) mm`);
 /// console.log(`Orientation: (${pose[3]}, ${pose[4]}, ${pose[5]}) deg`);
+/// console.log(`Body: ${pose[6]} deg`);
 /// ```
 #[wasm_bindgen]
 pub async fn get_head_pose() -> Result<Vec<f32>, JsValue> {
     let port = get_port()?;
+    Ok(read_head_pose(&port).await?)
+}
 
-    // Read current joint positions
-    let joint_angles = read_motor_positions(&port, &ALL_MOTOR_IDS).await?;
+/// Read the head pose from the motors on `port`.
+///
+/// Rust counterpart of `get_head_pose()`, for any [`MotorBus`].
+///
+/// # Returns
+/// `[x, y, z, roll, pitch, yaw, body_yaw]` in millimeters and degrees.
+///
+/// # Errors
+/// `ReachyError::MissingMotors` if the body or any head motor didn't respond.
+pub async fn read_head_pose(port: &impl MotorBus) -> Result<Vec<f32>, ReachyError> {
+    // Read current body and head positions
+    let angles = read_motor_positions(port, &BODY_AND_HEAD_MOTOR_IDS).await?;
 
-    // Extract head motors only (first 6)
-    let head_angles: Vec<f32> = joint_angles[0..6].to_vec();
+    // Forward kinematics on partial data would return a plausible but wrong pose
+    let missing: Vec<u8> = BODY_AND_HEAD_MOTOR_IDS
+        .iter()
+        .zip(&angles)
+        .filter(|(_, a)| a.is_nan())
+        .map(|(&id, _)| id)
+        .collect();
+    if !missing.is_empty() {
+        return Err(ReachyError::MissingMotors { ids: missing });
+    }
 
     // Compute forward kinematics
     let mut kinematics = create_kinematics();
-    let t = kinematics.forward_kinematics(&head_angles, None);
-
-    // Extract position (convert to mm and apply Z offset)
-    let x = t[(0, 3)] * 1000.0;
-    let y = t[(1, 3)] * 1000.0;
-    let z = t[(2, 3)] * 1000.0 - HEAD_Z_OFFSET_MM;
-
-    // Extract orientation (Euler XYZ)
-    let (roll, pitch, yaw) = extract_euler_angles(&t);
+    let t = kinematics.forward_kinematics(&angles[1..], Some(angles[0]));
 
-    Ok(vec![
-        x,
-        y,
-        z,
-        roll.to_degrees(),
-        pitch.to_degrees(),
-        yaw.to_degrees(),
-    ])
+    let mut pose = pose_to_xyzrpy(&t);
+    pose.push(angles[0].to_degrees());
+    Ok(pose)
 }
 
 /// Set the head pose in Cartesian coordinates.
@@ -317,15 +619,28 @@ pub async fn get_head_pose() -> Result<Vec<f32>, JsValue> {
 /// * `roll` - Roll angle in degrees
 /// * `pitch` - Pitch angle in degrees
 /// * `yaw` - Yaw angle in degrees
+/// * `body_yaw` - Optional body rotation in degrees. When given, the pose is
+///   in the world frame and the body is turned too, at most
+///   `max_relative_body_yaw_deg` away from the head's yaw. Otherwise the
+///   pose is relative to the body, which doesn't move.
+///
+/// The pose, the body yaw, then the joint angles go through the limits (see
+/// `set_limits()`).
 ///
 /// # Errors
 /// * Returns error if not connected
-/// * Returns error if pose is unreachable (IK fails)
+/// * Returns an `UNREACHABLE_POSE` error (with `branches`, the head branches
+///   that can't reach the pose) if IK has no solution; nothing is sent
+/// * Returns a `LIMIT_EXCEEDED` error if the pose, the body yaw or a joint
+///   angle is out of the limits with the `"reject"` policy; nothing is sent
 ///
 /// # Example
 /// ```javascript
 /// // Move head to center position, looking straight
 /// await set_head_pose(0, 0, 50, 0, 0, 0);
+///
+/// // Look 90° to the left, turning the body halfway
+/// await set_head_pose(0, 0, 0, 0, 0, 90, 45);
 /// ```
 #[wasm_bindgen]
 pub async fn set_head_pose(
@@ -335,18 +650,155 @@ pub async fn set_head_pose(
     roll: f32,
     pitch: f32,
     yaw: f32,
+    body_yaw: Option<f32>,
 ) -> Result<(), JsValue> {
     let port = get_port()?;
+    write_head_pose(&port, [x, y, z, roll, pitch, yaw], body_yaw).await?;
+    Ok(())
+}
 
-    // Compute inverse kinematics
-    let joint_angles = compute_inverse_kinematics(x, y, z, roll, pitch, yaw)?;
+/// Move the head to a pose on `port`.
+///
+/// Rust counterpart of `set_head_pose()`, for any [`MotorBus`].
+///
+/// # Arguments
+/// * `pose` - `[x, y, z, roll, pitch, yaw]` in millimeters and degrees
+/// * `body_yaw` - Optional body rotation in degrees, turning the body too
+///
+/// # Errors
+/// `ReachyError::UnreachablePose` if IK has no solution, or
+/// `ReachyError::LimitExceeded` with the `"reject"` policy; nothing is sent.
+pub async fn write_head_pose(
+    port: &impl MotorBus,
+    pose: [f32; 6],
+    body_yaw: Option<f32>,
+) -> Result<(), ReachyError> {
+    let mut pose = pose;
+    enforce_pose_limits(&mut pose)?;
+    let [x, y, z, roll, pitch, yaw] = pose;
+
+    // Compute inverse kinematics, which limits the body yaw
+    let mut joint_angles = compute_inverse_kinematics(x, y, z, roll, pitch, yaw, body_yaw)?;
+
+    // Send to the head motors, and the body if it turns
+    let ids = if body_yaw.is_some() {
+        &BODY_AND_HEAD_MOTOR_IDS[..]
+    } else {
+        &HEAD_MOTOR_IDS[..]
+    };
+    enforce_joint_limits(ids, &mut joint_angles)?;
+    let packet = build_sync_write_position_radians(ids, &joint_angles);
 
-    // Send to head motors only
-    let packet = build_sync_write_position_radians(&HEAD_MOTOR_IDS.to_vec(), &joint_angles);
+    port.send(&packet).await
+}
 
-    port.write(&packet).await?;
+/// Move the head as close as possible to a pose, even out of reach.
+///
+/// Unlike `set_head_pose()`, an unreachable pose isn't rejected: the head is
+/// sent to the closest reachable pose instead, e.g. when a teleoperation UI
+/// drags the head past the edge of the workspace. Closeness weighs position
+/// errors against orientation errors, one millimeter being worth
+/// `mm_per_degree` degrees.
+///
+/// # Arguments
+/// * `x`, `y`, `z` - Target position in millimeters (z: 0 = minimum height)
+/// * `roll`, `pitch`, `yaw` - Target orientation in degrees
+/// * `mm_per_degree` - Millimeters of position error as bad as one degree of
+///   orientation error (default: 1). Larger values favor the orientation.
+/// * `body_yaw` - Optional body rotation in degrees, as in `set_head_pose()`
+///
+/// The target and the body yaw are first brought within the limits, and the
+/// joint angles within the joint limits (see `set_limits()`).
+///
+/// # Returns
+/// The pose actually commanded, `[x, y, z, roll, pitch, yaw]` in millimeters
+/// and degrees, followed by the body yaw if given; the target itself if it
+/// was reachable and within limits.
+///
+/// # Errors
+/// * Returns error if not connected
+/// * Returns an `INVALID_ARGUMENT` error if a value is NaN or infinite, or
+///   `mm_per_degree` isn't positive
+/// * Returns a `LIMIT_EXCEEDED` error if the target or a joint angle is out
+///   of the limits with the `"reject"` policy; nothing is sent
+///
+/// # Example
+/// ```javascript
+/// // Follow the mouse, stopping at the edge of the workspace
+/// const pose = await set_head_pose_clamped(80, 0, 10, 0, 0, 0);
+/// if (pose[0] < 80) console.log(`Stopped at x = ${pose[0].toFixed(1)} mm`);
+/// ```
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub async fn set_head_pose_clamped(
+    x: f32,
+    y: f32,
+    z: f32,
+    roll: f32,
+    pitch: f32,
+    yaw: f32,
+    mm_per_degree: Option<f32>,
+    body_yaw: Option<f32>,
+) -> Result<Vec<f32>, JsValue> {
+    let port = get_port()?;
+    let pose = [x, y, z, roll, pitch, yaw];
+    Ok(write_head_pose_clamped(&port, pose, mm_per_degree, body_yaw).await?)
+}
 
-    Ok(())
+/// Move the head on `port` as close as possible to a pose, even out of reach.
+///
+/// Rust counterpart of `set_head_pose_clamped()`, for any [`MotorBus`].
+///
+/// # Returns
+/// The pose actually commanded, `[x, y, z, roll, pitch, yaw]` in millimeters
+/// and degrees, followed by the body yaw if given.
+pub async fn write_head_pose_clamped(
+    port: &impl MotorBus,
+    pose: [f32; 6],
+    mm_per_degree: Option<f32>,
+    body_yaw: Option<f32>,
+) -> Result<Vec<f32>, ReachyError> {
+    let weights = pose_weights(mm_per_degree)?;
+
+    let mut pose = pose;
+    enforce_pose_limits(&mut pose)?;
+    let [x, y, z, roll, pitch, yaw] = pose;
+    let body_yaw = body_yaw
+        .map(|body_yaw| enforce_body_yaw_limits(body_yaw, yaw))
+        .transpose()?;
+    let body_yaw_rad = body_yaw.map(f32::to_radians);
+
+    let kinematics = create_kinematics();
+    let target = pose_from_xyzrpy(x, y, z, roll, pitch, yaw);
+    let t = kinematics.nearest_reachable_pose(target, body_yaw_rad, weights)?;
+    let mut joint_angles = kinematics.inverse_kinematics(t, body_yaw_rad)?;
+
+    // Send to the head motors, and the body if it turns
+    let ids = match body_yaw_rad {
+        Some(body_yaw_rad) => {
+            joint_angles.insert(0, body_yaw_rad);
+            &BODY_AND_HEAD_MOTOR_IDS[..]
+        }
+        None => &HEAD_MOTOR_IDS[..],
+    };
+    let joints_clamped = enforce_joint_limits(ids, &mut joint_angles)?;
+    let packet = build_sync_write_position_radians(ids, &joint_angles);
+    port.send(&packet).await?;
+
+    if joints_clamped {
+        return Ok(match body_yaw_rad {
+            Some(_) => compute_forward_kinematics(&joint_angles[1..], Some(joint_angles[0])),
+            None => compute_forward_kinematics(&joint_angles, None),
+        });
+    }
+    // Report the target as given when it was reachable, not its round trip
+    let mut commanded = if t == target {
+        pose.to_vec()
+    } else {
+        pose_to_xyzrpy(&t)
+    };
+    commanded.extend(body_yaw);
+    Ok(commanded)
 }
 
 // ============================================================================
@@ -358,7 +810,8 @@ pub async fn set_head_pose(
 /// Returns the raw joint angles for all 6 head motors (11-16).
 ///
 /// # Returns
-/// A vector of 6 floats representing joint angles in degrees.
+/// A vector of 6 floats representing joint angles in degrees
+/// (NaN for motors that didn't respond).
 ///
 /// # Example
 /// ```javascript
@@ -375,7 +828,8 @@ pub async fn get_head_joints() -> Result<Vec<f32>, JsValue> {
 
 /// Set positions of all head joints.
 ///
-/// Directly commands all 6 head motors to the specified angles.
+/// Directly commands all 6 head motors to the specified angles, within the
+/// joint limits (see `set_limits()`).
 ///
 /// # Arguments
 /// * `angles_deg` - Vector of 6 joint angles in degrees
@@ -383,6 +837,8 @@ pub async fn get_head_joints() -> Result<Vec<f32>, JsValue> {
 /// # Errors
 /// * Returns error if `angles_deg` length is not 6
 /// * Returns error if not connected
+/// * Returns a `LIMIT_EXCEEDED` error if an angle is out of the limits with
+///   the `"reject"` policy; nothing is sent
 ///
 /// # Example
 /// ```javascript
@@ -391,23 +847,34 @@ pub async fn get_head_joints() -> Result<Vec<f32>, JsValue> {
 /// ```
 #[wasm_bindgen]
 pub async fn set_head_joints(angles_deg: Vec<f32>) -> Result<(), JsValue> {
-    if angles_deg.len() != 6 {
-        return Err(JsValue::from_str("Expected 6 joint angles for head motors"));
-    }
-
     let port = get_port()?;
-    let angles_rad: Vec<f32> = angles_deg.iter().map(|d| d.to_radians()).collect();
-
-    let packet = build_sync_write_position_radians(&HEAD_MOTOR_IDS.to_vec(), &angles_rad);
-
-    port.write(&packet).await?;
-
+    write_head_joints(&port, &angles_deg).await?;
     Ok(())
 }
 
+/// Move the 6 head motors on `port` to angles in degrees.
+///
+/// Rust counterpart of `set_head_joints()`, for any [`MotorBus`].
+///
+/// # Errors
+/// `ReachyError::InvalidArgument` if there aren't 6 angles, or
+/// `ReachyError::LimitExceeded` with the `"reject"` policy; nothing is sent.
+pub async fn write_head_joints(
+    port: &impl MotorBus,
+    angles_deg: &[f32],
+) -> Result<(), ReachyError> {
+    if angles_deg.len() != 6 {
+        return Err(ReachyError::InvalidArgument(
+            "Expected 6 joint angles for head motors".into(),
+        ));
+    }
+    write_joint_angles(port, &HEAD_MOTOR_IDS, angles_deg).await
+}
+
 /// Get positions of all motors (head + antennas).
 ///
-/// Returns joint angles for all 8 motors (11-18).
+/// Returns joint angles for all 8 motors (11-18). The body is read with
+/// `get_body_yaw()`.
 ///
 /// # Returns
 /// A vector of 8 floats representing joint angles in degrees:
@@ -415,6 +882,9 @@ pub async fn set_head_joints(angles_deg: Vec<f32>) -> Result<(), JsValue> {
 /// - Index 6: Left antenna (17)
 /// - Index 7: Right antenna (18)
 ///
+/// Motors that didn't respond are NaN; use `get_all_joints_with_missing()`
+/// to also get their IDs.
+///
 /// # Example
 /// ```javascript
 /// const allJoints = await get_all_joints();
@@ -430,119 +900,346 @@ pub async fn get_all_joints() -> Result<Vec<f32>, JsValue> {
     Ok(angles_deg)
 }
 
-/// Set positions of all motors (head + antennas).
-///
-/// Directly commands all 8 motors to the specified angles.
+/// Get positions of all motors, reporting which motors didn't respond.
 ///
-/// # Arguments
-/// * `angles_deg` - Vector of 8 joint angles in degrees
-///
-/// # Errors
-/// * Returns error if `angles_deg` length is not 8
+/// # Returns
+/// An object `{values, missing_ids}`:
+/// - `values`: 8 joint angles in degrees (same order as `get_all_joints()`),
+///   `null` for motors that didn't respond
+/// - `missing_ids`: IDs of the motors that didn't respond
 ///
 /// # Example
 /// ```javascript
-/// // Set all joints including antennas
-/// await set_all_joints([0, 0, 0, 0, 0, 0, 45, -45]);
+/// const { values, missing_ids } = await get_all_joints_with_missing();
+/// if (missing_ids.length > 0) console.warn(`No answer from ${missing_ids}`);
 /// ```
 #[wasm_bindgen]
-pub async fn set_all_joints(angles_deg: Vec<f32>) -> Result<(), JsValue> {
-    if angles_deg.len() != 8 {
-        return Err(JsValue::from_str(
-            "Expected 8 joint angles (6 head + 2 antennas)",
-        ));
-    }
-
+pub async fn get_all_joints_with_missing() -> Result<JsValue, JsValue> {
     let port = get_port()?;
-    let angles_rad: Vec<f32> = angles_deg.iter().map(|d| d.to_radians()).collect();
-
-    let packet = build_sync_write_position_radians(&ALL_MOTOR_IDS.to_vec(), &angles_rad);
-
-    port.write(&packet).await?;
-
-    Ok(())
+    let angles_rad = read_motor_positions(&port, &ALL_MOTOR_IDS).await?;
+    let angles_deg: Vec<Option<f32>> = angles_rad
+        .iter()
+        .map(|r| (!r.is_nan()).then(|| r.to_degrees()))
+        .collect();
+    readings_to_js(&ALL_MOTOR_IDS, angles_deg)
 }
 
-// ============================================================================
-// Antenna API
-// ============================================================================
-
-/// Get the current position of the left antenna.
+/// Get positions of all motors along with their status.
+///
+/// Unlike `get_all_joints()`, this never fails because of a motor error:
+/// each motor's status error byte is reported next to its position so that
+/// motors in alert can be flagged in the UI.
 ///
 /// # Returns
-/// Antenna angle in degrees
+/// Array of `{id, joint, position, ok, alert, error, status}` for each
+/// responding motor, where:
+/// - `position`: Joint angle in degrees
+/// - `alert`: Motor has a hardware error (see `get_motor_error_report()`)
+/// - `error`: Raw status error byte (protocol error code in bits 0-6)
+/// - `status`: Human-readable status, e.g. `"OK"` or `"Alert"`
 ///
 /// # Example
 /// ```javascript
-/// const leftAngle = await get_left_antenna();
+/// const joints = await get_joint_status();
+/// for (const j of joints) {
+///   if (j.alert) console.warn(`${j.joint} in alert at ${j.position.toFixed(1)}°`);
+/// }
 /// ```
 #[wasm_bindgen]
-pub async fn get_left_antenna() -> Result<f32, JsValue> {
+pub async fn get_joint_status() -> Result<JsValue, JsValue> {
     let port = get_port()?;
-    let angles = read_motor_positions(&port, &[LEFT_ANTENNA_ID]).await?;
-    Ok(angles[0].to_degrees())
+    let states = read_motor_states(&port, &ALL_MOTOR_IDS).await?;
+
+    let result = js_sys::Array::new();
+    for ((&id, joint), state) in ALL_MOTOR_IDS.iter().zip(JOINT_NAMES).zip(states) {
+        let Some((rad, status)) = state else {
+            continue;
+        };
+
+        let entry = js_sys::Object::new();
+        js_sys::Reflect::set(&entry, &"id".into(), &JsValue::from(id))?;
+        js_sys::Reflect::set(&entry, &"joint".into(), &JsValue::from_str(joint))?;
+        js_sys::Reflect::set(&entry, &"position".into(), &JsValue::from(rad.to_degrees()))?;
+        js_sys::Reflect::set(&entry, &"ok".into(), &JsValue::from(status.is_ok()))?;
+        js_sys::Reflect::set(&entry, &"alert".into(), &JsValue::from(status.alert()))?;
+        js_sys::Reflect::set(&entry, &"error".into(), &JsValue::from(status.0))?;
+        js_sys::Reflect::set(
+            &entry,
+            &"status".into(),
+            &JsValue::from_str(&status.to_string()),
+        )?;
+        result.push(&entry);
+    }
+
+    Ok(result.into())
 }
 
-/// Set the position of the left antenna.
+/// Set positions of all motors (head + antennas).
+///
+/// Directly commands all 8 motors to the specified angles, within the joint
+/// and antenna limits (see `set_limits()`). The body is moved with
+/// `set_body_yaw()`.
 ///
 /// # Arguments
-/// * `angle_deg` - Target angle in degrees
+/// * `angles_deg` - Vector of 8 joint angles in degrees, in the order of
+///   `get_all_joints()`
+///
+/// # Errors
+/// * Returns error if `angles_deg` length is not 8
+/// * Returns a `LIMIT_EXCEEDED` error if an angle is out of the limits with
+///   the `"reject"` policy; nothing is sent
 ///
 /// # Example
 /// ```javascript
-/// await set_left_antenna(45);  // Raise left antenna
+/// // Set all joints including antennas
+/// await set_all_joints([0, 0, 0, 0, 0, 0, 45, -45]);
 /// ```
 #[wasm_bindgen]
-pub async fn set_left_antenna(angle_deg: f32) -> Result<(), JsValue> {
+pub async fn set_all_joints(angles_deg: Vec<f32>) -> Result<(), JsValue> {
     let port = get_port()?;
-    let angle_rad = angle_deg.to_radians();
-
-    let packet = build_sync_write_position_radians(&vec![LEFT_ANTENNA_ID], &vec![angle_rad]);
-
-    port.write(&packet).await?;
+    write_all_joints(&port, &angles_deg).await?;
     Ok(())
 }
 
-/// Get the current position of the right antenna.
+/// Move the head and antenna motors on `port` to angles in degrees, in the
+/// order of `get_all_joints()`.
 ///
-/// # Returns
-/// Antenna angle in degrees
+/// Rust counterpart of `set_all_joints()`, for any [`MotorBus`].
 ///
-/// # Example
-/// ```javascript
-/// const rightAngle = await get_right_antenna();
-/// ```
-#[wasm_bindgen]
-pub async fn get_right_antenna() -> Result<f32, JsValue> {
-    let port = get_port()?;
-    let angles = read_motor_positions(&port, &[RIGHT_ANTENNA_ID]).await?;
-    Ok(angles[0].to_degrees())
+/// # Errors
+/// `ReachyError::InvalidArgument` if there aren't 8 angles, or
+/// `ReachyError::LimitExceeded` with the `"reject"` policy; nothing is sent.
+pub async fn write_all_joints(port: &impl MotorBus, angles_deg: &[f32]) -> Result<(), ReachyError> {
+    if angles_deg.len() != ALL_MOTOR_IDS.len() {
+        return Err(ReachyError::InvalidArgument(
+            "Expected 8 joint angles (6 head + 2 antennas)".into(),
+        ));
+    }
+    write_joint_angles(port, &ALL_MOTOR_IDS, angles_deg).await
 }
 
-/// Set the position of the right antenna.
+/// Stage head joint positions without moving, using REG_WRITE.
+///
+/// Each head motor stores its goal position and acknowledges it; nothing
+/// moves until `trigger_staged_writes()` is called. This gives the same
+/// simultaneous start as `set_head_joints()` on links without SYNC_WRITE.
 ///
 /// # Arguments
-/// * `angle_deg` - Target angle in degrees
+/// * `angles_deg` - Array of 6 joint angles in degrees
+///
+/// # Errors
+/// * `MISSING_MOTORS` if some motors didn't acknowledge (the others stay staged)
+/// * `MOTOR_STATUS_ERROR` if a motor rejected the write
+/// * `LIMIT_EXCEEDED` if an angle is out of the joint limits with the
+///   `"reject"` policy; nothing is staged
 ///
 /// # Example
 /// ```javascript
-/// await set_right_antenna(-45);  // Raise right antenna
+/// await stage_head_joints([0, 10, 0, 0, 10, 0]);
+/// await trigger_staged_writes();
 /// ```
 #[wasm_bindgen]
-pub async fn set_right_antenna(angle_deg: f32) -> Result<(), JsValue> {
+pub async fn stage_head_joints(angles_deg: Vec<f32>) -> Result<(), JsValue> {
+    let port = get_port()?;
+    stage_head_joints_on(&port, &angles_deg).await?;
+    Ok(())
+}
+
+/// Stage head joint positions in degrees on `port` with REG_WRITE, without
+/// moving.
+///
+/// Rust counterpart of `stage_head_joints()`, for any [`MotorBus`].
+///
+/// # Errors
+/// * `ReachyError::InvalidArgument` if there aren't 6 angles
+/// * `ReachyError::MissingMotors` if some motors didn't acknowledge (the
+///   others stay staged)
+/// * `ReachyError::MotorStatusError` if a motor rejected the write
+/// * `ReachyError::LimitExceeded` with the `"reject"` policy; nothing is
+///   staged
+pub async fn stage_head_joints_on(
+    port: &impl MotorBus,
+    angles_deg: &[f32],
+) -> Result<(), ReachyError> {
+    if angles_deg.len() != HEAD_MOTOR_IDS.len() {
+        return Err(ReachyError::InvalidArgument(
+            "Expected 6 joint angles for head motors".into(),
+        ));
+    }
+
+    let goal = <register::GoalPosition as Register>::INFO;
+    let mut angles_rad: Vec<f32> = angles_deg.iter().map(|d| d.to_radians()).collect();
+    enforce_joint_limits(&HEAD_MOTOR_IDS, &mut angles_rad)?;
+
+    let mut missing = Vec::new();
+    for (&id, &rad) in HEAD_MOTOR_IDS.iter().zip(angles_rad.iter()) {
+        let packet = build_reg_write_info(id, &goal, radians_to_raw(rad));
+        let response = port.try_write_read(&packet, None).await?;
+        if response.is_empty() {
+            missing.push(id);
+        } else {
+            parse_write_status(&response)?;
+        }
+    }
+
+    if !missing.is_empty() {
+        return Err(ReachyError::MissingMotors { ids: missing });
+    }
+    Ok(())
+}
+
+/// Apply the writes staged on every motor, all at once (broadcast ACTION).
+///
+/// # Example
+/// ```javascript
+/// await stage_head_joints([0, 0, 0, 0, 0, 0]);
+/// await trigger_staged_writes();
+/// ```
+#[wasm_bindgen]
+pub async fn trigger_staged_writes() -> Result<(), JsValue> {
+    let port = get_port()?;
+    trigger_staged_writes_on(&port).await?;
+    Ok(())
+}
+
+/// Apply the writes staged on every motor on `port`, all at once.
+///
+/// Rust counterpart of `trigger_staged_writes()`, for any [`MotorBus`].
+pub async fn trigger_staged_writes_on(port: &impl MotorBus) -> Result<(), ReachyError> {
+    port.send(&build_action_packet(BROADCAST_ID)).await
+}
+
+// ============================================================================
+// Body API
+// ============================================================================
+
+/// Get the current rotation of the body.
+///
+/// # Returns
+/// Body yaw in degrees (NaN if the motor didn't respond)
+///
+/// # Example
+/// ```javascript
+/// const bodyYaw = await get_body_yaw();
+/// ```
+#[wasm_bindgen]
+pub async fn get_body_yaw() -> Result<f32, JsValue> {
+    let port = get_port()?;
+    let angles = read_motor_positions(&port, &[BODY_MOTOR_ID]).await?;
+    Ok(angles[0].to_degrees())
+}
+
+/// Rotate the body, the head turning with it.
+///
+/// Only the body motor moves, so the head keeps its pose relative to the
+/// body; use `set_head_pose()` with a body yaw to move both.
+///
+/// # Arguments
+/// * `angle_deg` - Target angle in degrees
+///
+/// # Errors
+/// Returns a `LIMIT_EXCEEDED` error if the angle is out of the body's joint
+/// limits with the `"reject"` policy (see `set_limits()`); it is clamped
+/// otherwise
+///
+/// # Example
+/// ```javascript
+/// await set_body_yaw(30);  // Turn to the left
+/// ```
+#[wasm_bindgen]
+pub async fn set_body_yaw(angle_deg: f32) -> Result<(), JsValue> {
+    let port = get_port()?;
+    write_body_yaw(&port, angle_deg).await?;
+    Ok(())
+}
+
+/// Rotate the body on `port` to an angle in degrees.
+///
+/// Rust counterpart of `set_body_yaw()`, for any [`MotorBus`].
+pub async fn write_body_yaw(port: &impl MotorBus, angle_deg: f32) -> Result<(), ReachyError> {
+    write_joint_angles(port, &[BODY_MOTOR_ID], &[angle_deg]).await
+}
+
+// ============================================================================
+// Antenna API
+// ============================================================================
+
+/// Get the current position of the left antenna.
+///
+/// # Returns
+/// Antenna angle in degrees (NaN if the motor didn't respond)
+///
+/// # Example
+/// ```javascript
+/// const leftAngle = await get_left_antenna();
+/// ```
+#[wasm_bindgen]
+pub async fn get_left_antenna() -> Result<f32, JsValue> {
+    let port = get_port()?;
+    let angles = read_motor_positions(&port, &[LEFT_ANTENNA_ID]).await?;
+    Ok(angles[0].to_degrees())
+}
+
+/// Set the position of the left antenna.
+///
+/// # Arguments
+/// * `angle_deg` - Target angle in degrees
+///
+/// # Errors
+/// Returns a `LIMIT_EXCEEDED` error if an angle is out of the antenna limits
+/// with the `"reject"` policy (see `set_limits()`); it is clamped otherwise
+///
+/// # Example
+/// ```javascript
+/// await set_left_antenna(45);  // Raise left antenna
+/// ```
+#[wasm_bindgen]
+pub async fn set_left_antenna(angle_deg: f32) -> Result<(), JsValue> {
     let port = get_port()?;
-    let angle_rad = angle_deg.to_radians();
+    write_joint_angles(&port, &[LEFT_ANTENNA_ID], &[angle_deg]).await?;
+    Ok(())
+}
 
-    let packet = build_sync_write_position_radians(&vec![RIGHT_ANTENNA_ID], &vec![angle_rad]);
+/// Get the current position of the right antenna.
+///
+/// # Returns
+/// Antenna angle in degrees (NaN if the motor didn't respond)
+///
+/// # Example
+/// ```javascript
+/// const rightAngle = await get_right_antenna();
+/// ```
+#[wasm_bindgen]
+pub async fn get_right_antenna() -> Result<f32, JsValue> {
+    let port = get_port()?;
+    let angles = read_motor_positions(&port, &[RIGHT_ANTENNA_ID]).await?;
+    Ok(angles[0].to_degrees())
+}
 
-    port.write(&packet).await?;
+/// Set the position of the right antenna.
+///
+/// # Arguments
+/// * `angle_deg` - Target angle in degrees
+///
+/// # Errors
+/// Returns a `LIMIT_EXCEEDED` error if an angle is out of the antenna limits
+/// with the `"reject"` policy (see `set_limits()`); it is clamped otherwise
+///
+/// # Example
+/// ```javascript
+/// await set_right_antenna(-45);  // Raise right antenna
+/// ```
+#[wasm_bindgen]
+pub async fn set_right_antenna(angle_deg: f32) -> Result<(), JsValue> {
+    let port = get_port()?;
+    write_joint_angles(&port, &[RIGHT_ANTENNA_ID], &[angle_deg]).await?;
     Ok(())
 }
 
 /// Get positions of both antennas.
 ///
 /// # Returns
-/// Vector of 2 floats: `[left_angle, right_angle]` in degrees
+/// Vector of 2 floats: `[left_angle, right_angle]` in degrees (NaN if a motor didn't respond)
 ///
 /// # Example
 /// ```javascript
@@ -561,6 +1258,10 @@ pub async fn get_antennas() -> Result<Vec<f32>, JsValue> {
 /// * `left_deg` - Left antenna angle in degrees
 /// * `right_deg` - Right antenna angle in degrees
 ///
+/// # Errors
+/// Returns a `LIMIT_EXCEEDED` error if an angle is out of the antenna limits
+/// with the `"reject"` policy (see `set_limits()`); it is clamped otherwise
+///
 /// # Example
 /// ```javascript
 /// await set_antennas(45, -45);  // Both antennas up (mirrored)
@@ -568,16 +1269,111 @@ pub async fn get_antennas() -> Result<Vec<f32>, JsValue> {
 #[wasm_bindgen]
 pub async fn set_antennas(left_deg: f32, right_deg: f32) -> Result<(), JsValue> {
     let port = get_port()?;
+    write_antennas(&port, left_deg, right_deg).await?;
+    Ok(())
+}
 
-    let packet = build_sync_write_position_radians(
-        &vec![LEFT_ANTENNA_ID, RIGHT_ANTENNA_ID],
-        &vec![left_deg.to_radians(), right_deg.to_radians()],
-    );
+/// Move both antennas on `port` to angles in degrees.
+///
+/// Rust counterpart of `set_antennas()`, for any [`MotorBus`].
+pub async fn write_antennas(
+    port: &impl MotorBus,
+    left_deg: f32,
+    right_deg: f32,
+) -> Result<(), ReachyError> {
+    write_joint_angles(
+        port,
+        &[LEFT_ANTENNA_ID, RIGHT_ANTENNA_ID],
+        &[left_deg, right_deg],
+    )
+    .await
+}
+
+// ============================================================================
+// Limits API
+// ============================================================================
+
+/// Get the limits applied to position commands, as JSON.
+///
+/// Every command that moves a motor (`set_head_pose()`, `set_head_joints()`,
+/// `set_all_joints()`, `set_antennas()`, ...) is checked against them first.
+///
+/// # Returns
+/// JSON in the format of `limits.json`:
+/// - `policy`: `"clamp"` or `"reject"`
+/// - `joints`: `[{id, name, min_deg, max_deg}]` per motor
+/// - `position_mm`: `{x, y, z}` head position bounds as `[min, max]`
+/// - `orientation_deg`: `{roll, pitch, yaw}` largest head rotation either way
+/// - `max_antenna_deg`: largest antenna angle either way
+/// - `max_relative_body_yaw_deg`: largest angle between the body and head
+///   yaws either way, when a pose is set with a body yaw
+///
+/// # Example
+/// ```javascript
+/// const limits = JSON.parse(get_limits());
+/// limits.orientation_deg.pitch = 20;
+/// set_limits(JSON.stringify(limits));
+/// ```
+#[wasm_bindgen]
+pub fn get_limits() -> String {
+    LIMITS.with_borrow(Limits::to_json)
+}
+
+/// Replace the limits applied to position commands.
+///
+/// # Arguments
+/// * `json` - Limits in the format returned by `get_limits()`; `policy` may
+///   be omitted (defaults to `"clamp"`)
+///
+/// # Errors
+/// Returns an `INVALID_ARGUMENT` error if the JSON is malformed or a range
+/// is empty; the current limits are kept
+#[wasm_bindgen]
+pub fn set_limits(json: &str) -> Result<(), JsValue> {
+    let limits = Limits::from_json(json)?;
+    LIMITS.with_borrow_mut(|l| *l = limits);
+    Ok(())
+}
+
+/// Restore the default limits shipped with the library.
+#[wasm_bindgen]
+pub fn reset_limits() {
+    LIMITS.with_borrow_mut(|l| *l = Limits::default());
+}
 
-    port.write(&packet).await?;
+/// Choose what happens to commands outside the limits.
+///
+/// # Arguments
+/// * `policy` - `"clamp"` (default) to move out-of-range values to the
+///   nearest limit and send the command, or `"reject"` to fail with a
+///   `LIMIT_EXCEEDED` error (with `limit`, `value`, `min` and `max`) and send
+///   nothing
+///
+/// # Errors
+/// Returns an `INVALID_ARGUMENT` error for any other policy
+///
+/// # Example
+/// ```javascript
+/// set_limit_policy("reject");
+/// try {
+///   await set_antennas(170, 0);
+/// } catch (e) {
+///   if (e.code === "LIMIT_EXCEEDED") console.warn(`${e.limit} limited to [${e.min}, ${e.max}]`);
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn set_limit_policy(policy: &str) -> Result<(), JsValue> {
+    let policy = LimitPolicy::from_name(policy)?;
+    LIMITS.with_borrow_mut(|l| l.policy = policy);
     Ok(())
 }
 
+/// Get the current limit policy: `"clamp"` or `"reject"`.
+#[wasm_bindgen]
+pub fn get_limit_policy() -> String {
+    LIMITS.with_borrow(|l| l.policy.as_str().to_string())
+}
+
 // ============================================================================
 // Torque Control API
 // ============================================================================
@@ -622,7 +1418,7 @@ pub async fn disable_torque() -> Result<(), JsValue> {
 pub async fn enable_head_torque() -> Result<(), JsValue> {
     let port = get_port()?;
     let packet = build_sync_write_torque(&HEAD_MOTOR_IDS.to_vec(), true);
-    port.write(&packet).await?;
+    port.send(&packet).await?;
     Ok(())
 }
 
@@ -633,7 +1429,25 @@ pub async fn enable_head_torque() -> Result<(), JsValue> {
 pub async fn disable_head_torque() -> Result<(), JsValue> {
     let port = get_port()?;
     let packet = build_sync_write_torque(&HEAD_MOTOR_IDS.to_vec(), false);
-    port.write(&packet).await?;
+    port.send(&packet).await?;
+    Ok(())
+}
+
+/// Enable torque on the body motor only (10).
+#[wasm_bindgen]
+pub async fn enable_body_torque() -> Result<(), JsValue> {
+    let port = get_port()?;
+    let packet = build_sync_write_torque(&[BODY_MOTOR_ID], true);
+    port.send(&packet).await?;
+    Ok(())
+}
+
+/// Disable torque on the body motor only (10).
+#[wasm_bindgen]
+pub async fn disable_body_torque() -> Result<(), JsValue> {
+    let port = get_port()?;
+    let packet = build_sync_write_torque(&[BODY_MOTOR_ID], false);
+    port.send(&packet).await?;
     Ok(())
 }
 
@@ -642,7 +1456,7 @@ pub async fn disable_head_torque() -> Result<(), JsValue> {
 pub async fn enable_antenna_torque() -> Result<(), JsValue> {
     let port = get_port()?;
     let packet = build_sync_write_torque(&vec![LEFT_ANTENNA_ID, RIGHT_ANTENNA_ID], true);
-    port.write(&packet).await?;
+    port.send(&packet).await?;
     Ok(())
 }
 
@@ -651,7 +1465,7 @@ pub async fn enable_antenna_torque() -> Result<(), JsValue> {
 pub async fn disable_antenna_torque() -> Result<(), JsValue> {
     let port = get_port()?;
     let packet = build_sync_write_torque(&vec![LEFT_ANTENNA_ID, RIGHT_ANTENNA_ID], false);
-    port.write(&packet).await?;
+    port.send(&packet).await?;
     Ok(())
 }
 
@@ -660,7 +1474,7 @@ pub async fn disable_antenna_torque() -> Result<(), JsValue> {
 pub async fn enable_left_antenna_torque() -> Result<(), JsValue> {
     let port = get_port()?;
     let packet = build_sync_write_torque(&vec![LEFT_ANTENNA_ID], true);
-    port.write(&packet).await?;
+    port.send(&packet).await?;
     Ok(())
 }
 
@@ -669,7 +1483,7 @@ pub async fn enable_left_antenna_torque() -> Result<(), JsValue> {
 pub async fn disable_left_antenna_torque() -> Result<(), JsValue> {
     let port = get_port()?;
     let packet = build_sync_write_torque(&vec![LEFT_ANTENNA_ID], false);
-    port.write(&packet).await?;
+    port.send(&packet).await?;
     Ok(())
 }
 
@@ -678,7 +1492,7 @@ pub async fn disable_left_antenna_torque() -> Result<(), JsValue> {
 pub async fn enable_right_antenna_torque() -> Result<(), JsValue> {
     let port = get_port()?;
     let packet = build_sync_write_torque(&vec![RIGHT_ANTENNA_ID], true);
-    port.write(&packet).await?;
+    port.send(&packet).await?;
     Ok(())
 }
 
@@ -687,7 +1501,7 @@ pub async fn enable_right_antenna_torque() -> Result<(), JsValue> {
 pub async fn disable_right_antenna_torque() -> Result<(), JsValue> {
     let port = get_port()?;
     let packet = build_sync_write_torque(&vec![RIGHT_ANTENNA_ID], false);
-    port.write(&packet).await?;
+    port.send(&packet).await?;
     Ok(())
 }
 
@@ -698,7 +1512,7 @@ pub async fn disable_right_antenna_torque() -> Result<(), JsValue> {
 /// Get the temperature of a specific motor.
 ///
 /// # Arguments
-/// * `motor_id` - Motor ID (11-18)
+/// * `motor_id` - Motor ID (10-18)
 ///
 /// # Returns
 /// Temperature in degrees Celsius
@@ -712,8 +1526,8 @@ pub async fn disable_right_antenna_torque() -> Result<(), JsValue> {
 pub async fn get_motor_temperature(motor_id: u8) -> Result<u8, JsValue> {
     let port = get_port()?;
     let packet = build_read_packet(motor_id, address::PRESENT_TEMPERATURE, 1);
-    let response = port.write_read(&packet, Some(DEFAULT_WAIT_MS)).await?;
-    parse_status_packet_1byte(&response)
+    let response = port.write_read(&packet, None).await?;
+    Ok(parse_status_packet_1byte(&response)?)
 }
 
 /// Get the current load of a specific motor.
@@ -721,7 +1535,7 @@ pub async fn get_motor_temperature(motor_id: u8) -> Result<u8, JsValue> {
 /// Load represents the percentage of maximum torque currently being applied.
 ///
 /// # Arguments
-/// * `motor_id` - Motor ID (11-18)
+/// * `motor_id` - Motor ID (10-18)
 ///
 /// # Returns
 /// Load value from -1000 to 1000 (percentage of max torque × 10)
@@ -737,8 +1551,8 @@ pub async fn get_motor_temperature(motor_id: u8) -> Result<u8, JsValue> {
 pub async fn get_motor_load(motor_id: u8) -> Result<i16, JsValue> {
     let port = get_port()?;
     let packet = build_read_packet(motor_id, address::PRESENT_LOAD, 2);
-    let response = port.write_read(&packet, Some(DEFAULT_WAIT_MS)).await?;
-    parse_status_packet_2byte_signed(&response)
+    let response = port.write_read(&packet, None).await?;
+    Ok(parse_status_packet_2byte_signed(&response)?)
 }
 
 /// Get temperatures of all motors using bulk read.
@@ -746,7 +1560,8 @@ pub async fn get_motor_load(motor_id: u8) -> Result<i16, JsValue> {
 /// Uses resilient parsing - missing motor responses don't affect others.
 ///
 /// # Returns
-/// Vector of 8 temperatures in °C for motors 11-18 (0 if motor didn't respond)
+/// Vector of 8 temperatures in °C for motors 11-18 (0 if motor didn't respond;
+/// use `get_all_motor_temperatures_with_missing()` to tell them apart)
 ///
 /// # Example
 /// ```javascript
@@ -756,19 +1571,22 @@ pub async fn get_motor_load(motor_id: u8) -> Result<i16, JsValue> {
 #[wasm_bindgen]
 pub async fn get_all_motor_temperatures() -> Result<Vec<u8>, JsValue> {
     let port = get_port()?;
-    let packet = build_sync_read_temperature(&ALL_MOTOR_IDS);
-    let response = port.write_read(&packet, Some(DEFAULT_WAIT_MS)).await?;
-
-    let parsed = parse_1byte_packets(&response);
+    let temps = read_temperatures(&port, &ALL_MOTOR_IDS).await?;
+    Ok(temps.into_iter().map(|t| t.unwrap_or(0)).collect())
+}
 
-    // Map by motor ID, default to 0 for missing
-    let mut temps = vec![0u8; 8];
-    for (id, temp) in parsed {
-        if id >= 11 && id <= 18 {
-            temps[(id - 11) as usize] = temp;
-        }
-    }
-    Ok(temps)
+/// Get temperatures of all motors, reporting which motors didn't respond.
+///
+/// # Returns
+/// An object `{values, missing_ids}`:
+/// - `values`: 8 temperatures in °C for motors 11-18, `null` for motors
+///   that didn't respond
+/// - `missing_ids`: IDs of the motors that didn't respond
+#[wasm_bindgen]
+pub async fn get_all_motor_temperatures_with_missing() -> Result<JsValue, JsValue> {
+    let port = get_port()?;
+    let temps = read_temperatures(&port, &ALL_MOTOR_IDS).await?;
+    readings_to_js(&ALL_MOTOR_IDS, temps)
 }
 
 /// Get loads of all motors using bulk read.
@@ -776,7 +1594,8 @@ pub async fn get_all_motor_temperatures() -> Result<Vec<u8>, JsValue> {
 /// Uses resilient parsing - missing motor responses don't affect others.
 ///
 /// # Returns
-/// Vector of 8 load values for motors 11-18 (0 if motor didn't respond)
+/// Vector of 8 load values for motors 11-18 (0 if motor didn't respond;
+/// use `get_all_motor_loads_with_missing()` to tell them apart)
 ///
 /// # Example
 /// ```javascript
@@ -786,60 +1605,74 @@ pub async fn get_all_motor_temperatures() -> Result<Vec<u8>, JsValue> {
 #[wasm_bindgen]
 pub async fn get_all_motor_loads() -> Result<Vec<i16>, JsValue> {
     let port = get_port()?;
-    let packet = build_sync_read_load(&ALL_MOTOR_IDS);
-    let response = port.write_read(&packet, Some(DEFAULT_WAIT_MS)).await?;
-
-    let parsed = parse_2byte_signed_packets(&response);
+    let loads = read_loads(&port, &ALL_MOTOR_IDS).await?;
+    Ok(loads.into_iter().map(|l| l.unwrap_or(0)).collect())
+}
 
-    let mut loads = vec![0i16; 8];
-    for (id, load) in parsed {
-        if id >= 11 && id <= 18 {
-            loads[(id - 11) as usize] = load;
-        }
-    }
-    Ok(loads)
+/// Get loads of all motors, reporting which motors didn't respond.
+///
+/// # Returns
+/// An object `{values, missing_ids}`:
+/// - `values`: 8 load values for motors 11-18, `null` for motors that
+///   didn't respond
+/// - `missing_ids`: IDs of the motors that didn't respond
+#[wasm_bindgen]
+pub async fn get_all_motor_loads_with_missing() -> Result<JsValue, JsValue> {
+    let port = get_port()?;
+    let loads = read_loads(&port, &ALL_MOTOR_IDS).await?;
+    readings_to_js(&ALL_MOTOR_IDS, loads)
 }
 
 /// Get temperatures of head motors (11-16) using bulk read.
 ///
 /// # Returns
-/// Vector of 6 temperatures in °C (0 if motor didn't respond)
+/// Vector of 6 temperatures in °C (0 if motor didn't respond; use
+/// `get_head_motor_temperatures_with_missing()` to tell them apart)
 #[wasm_bindgen]
 pub async fn get_head_motor_temperatures() -> Result<Vec<u8>, JsValue> {
     let port = get_port()?;
-    let packet = build_sync_read_temperature(&HEAD_MOTOR_IDS);
-    let response = port.write_read(&packet, Some(DEFAULT_WAIT_MS)).await?;
-
-    let parsed = parse_1byte_packets(&response);
+    let temps = read_temperatures(&port, &HEAD_MOTOR_IDS).await?;
+    Ok(temps.into_iter().map(|t| t.unwrap_or(0)).collect())
+}
 
-    let mut temps = vec![0u8; 6];
-    for (id, temp) in parsed {
-        if id >= 11 && id <= 16 {
-            temps[(id - 11) as usize] = temp;
-        }
-    }
-    Ok(temps)
+/// Get temperatures of head motors, reporting which motors didn't respond.
+///
+/// # Returns
+/// An object `{values, missing_ids}`:
+/// - `values`: 6 temperatures in °C for motors 11-16, `null` for motors
+///   that didn't respond
+/// - `missing_ids`: IDs of the motors that didn't respond
+#[wasm_bindgen]
+pub async fn get_head_motor_temperatures_with_missing() -> Result<JsValue, JsValue> {
+    let port = get_port()?;
+    let temps = read_temperatures(&port, &HEAD_MOTOR_IDS).await?;
+    readings_to_js(&HEAD_MOTOR_IDS, temps)
 }
 
 /// Get loads of head motors (11-16) using bulk read.
 ///
 /// # Returns
-/// Vector of 6 load values (0 if motor didn't respond)
+/// Vector of 6 load values (0 if motor didn't respond; use
+/// `get_head_motor_loads_with_missing()` to tell them apart)
 #[wasm_bindgen]
 pub async fn get_head_motor_loads() -> Result<Vec<i16>, JsValue> {
     let port = get_port()?;
-    let packet = build_sync_read_load(&HEAD_MOTOR_IDS);
-    let response = port.write_read(&packet, Some(DEFAULT_WAIT_MS)).await?;
-
-    let parsed = parse_2byte_signed_packets(&response);
+    let loads = read_loads(&port, &HEAD_MOTOR_IDS).await?;
+    Ok(loads.into_iter().map(|l| l.unwrap_or(0)).collect())
+}
 
-    let mut loads = vec![0i16; 6];
-    for (id, load) in parsed {
-        if id >= 11 && id <= 16 {
-            loads[(id - 11) as usize] = load;
-        }
-    }
-    Ok(loads)
+/// Get loads of head motors, reporting which motors didn't respond.
+///
+/// # Returns
+/// An object `{values, missing_ids}`:
+/// - `values`: 6 load values for motors 11-16, `null` for motors that
+///   didn't respond
+/// - `missing_ids`: IDs of the motors that didn't respond
+#[wasm_bindgen]
+pub async fn get_head_motor_loads_with_missing() -> Result<JsValue, JsValue> {
+    let port = get_port()?;
+    let loads = read_loads(&port, &HEAD_MOTOR_IDS).await?;
+    readings_to_js(&HEAD_MOTOR_IDS, loads)
 }
 
 /// Get the temperature of the left antenna motor (17).
@@ -863,25 +1696,31 @@ pub async fn get_right_antenna_temperature() -> Result<u8, JsValue> {
 /// Get temperatures of both antenna motors using bulk read.
 ///
 /// # Returns
-/// Vector of 2 temperatures: `[left_temp, right_temp]` in °C (0 if motor didn't respond)
+/// Vector of 2 temperatures: `[left_temp, right_temp]` in °C (0 if motor
+/// didn't respond; use `get_antenna_temperatures_with_missing()` to tell
+/// them apart)
 #[wasm_bindgen]
 pub async fn get_antenna_temperatures() -> Result<Vec<u8>, JsValue> {
     let port = get_port()?;
     let motor_ids = [LEFT_ANTENNA_ID, RIGHT_ANTENNA_ID];
-    let packet = build_sync_read_temperature(&motor_ids);
-    let response = port.write_read(&packet, Some(DEFAULT_WAIT_MS)).await?;
-
-    let parsed = parse_1byte_packets(&response);
+    let temps = read_temperatures(&port, &motor_ids).await?;
+    Ok(temps.into_iter().map(|t| t.unwrap_or(0)).collect())
+}
 
-    let mut temps = vec![0u8; 2];
-    for (id, temp) in parsed {
-        if id == LEFT_ANTENNA_ID {
-            temps[0] = temp;
-        } else if id == RIGHT_ANTENNA_ID {
-            temps[1] = temp;
-        }
-    }
-    Ok(temps)
+/// Get temperatures of both antenna motors, reporting which motors didn't
+/// respond.
+///
+/// # Returns
+/// An object `{values, missing_ids}`:
+/// - `values`: `[left_temp, right_temp]` in °C, `null` for motors that
+///   didn't respond
+/// - `missing_ids`: IDs of the motors that didn't respond
+#[wasm_bindgen]
+pub async fn get_antenna_temperatures_with_missing() -> Result<JsValue, JsValue> {
+    let port = get_port()?;
+    let motor_ids = [LEFT_ANTENNA_ID, RIGHT_ANTENNA_ID];
+    let temps = read_temperatures(&port, &motor_ids).await?;
+    readings_to_js(&motor_ids, temps)
 }
 
 /// Get the load of the left antenna motor (17).
@@ -905,54 +1744,631 @@ pub async fn get_right_antenna_load() -> Result<i16, JsValue> {
 /// Get loads of both antenna motors using bulk read.
 ///
 /// # Returns
-/// Vector of 2 loads: `[left_load, right_load]` (0 if motor didn't respond)
+/// Vector of 2 loads: `[left_load, right_load]` (0 if motor didn't respond;
+/// use `get_antenna_loads_with_missing()` to tell them apart)
 #[wasm_bindgen]
 pub async fn get_antenna_loads() -> Result<Vec<i16>, JsValue> {
     let port = get_port()?;
     let motor_ids = [LEFT_ANTENNA_ID, RIGHT_ANTENNA_ID];
-    let packet = build_sync_read_load(&motor_ids);
-    let response = port.write_read(&packet, Some(DEFAULT_WAIT_MS)).await?;
+    let loads = read_loads(&port, &motor_ids).await?;
+    Ok(loads.into_iter().map(|l| l.unwrap_or(0)).collect())
+}
 
-    let parsed = parse_2byte_signed_packets(&response);
+/// Get loads of both antenna motors, reporting which motors didn't respond.
+///
+/// # Returns
+/// An object `{values, missing_ids}`:
+/// - `values`: `[left_load, right_load]`, `null` for motors that didn't
+///   respond
+/// - `missing_ids`: IDs of the motors that didn't respond
+#[wasm_bindgen]
+pub async fn get_antenna_loads_with_missing() -> Result<JsValue, JsValue> {
+    let port = get_port()?;
+    let motor_ids = [LEFT_ANTENNA_ID, RIGHT_ANTENNA_ID];
+    let loads = read_loads(&port, &motor_ids).await?;
+    readings_to_js(&motor_ids, loads)
+}
 
-    let mut loads = vec![0i16; 2];
-    for (id, load) in parsed {
-        if id == LEFT_ANTENNA_ID {
-            loads[0] = load;
-        } else if id == RIGHT_ANTENNA_ID {
-            loads[1] = load;
-        }
-    }
-    Ok(loads)
+/// Get the temperature of the body rotation motor (10).
+///
+/// The `get_all_motor_temperatures` family covers motors 11-18 only.
+///
+/// # Returns
+/// Temperature in degrees Celsius
+#[wasm_bindgen]
+pub async fn get_body_temperature() -> Result<u8, JsValue> {
+    get_motor_temperature(BODY_MOTOR_ID).await
 }
 
-// ============================================================================
-// Motor Reboot API
-// ============================================================================
+/// Get the load of the body rotation motor (10).
+///
+/// The `get_all_motor_loads` family covers motors 11-18 only.
+///
+/// # Returns
+/// Load value from -1000 to 1000
+#[wasm_bindgen]
+pub async fn get_body_load() -> Result<i16, JsValue> {
+    get_motor_load(BODY_MOTOR_ID).await
+}
 
-/// Reboot a specific motor by ID.
+/// Get a snapshot of position, load, temperature and hardware error status
+/// of all motors (10-18) in a single round trip.
 ///
-/// This clears any hardware error status and reinitializes the motor.
-/// The motor will be unresponsive for approximately 500ms after reboot.
+/// Uses one BULK_READ instead of a SYNC_READ per register. Motors that
+/// didn't respond are omitted from the result.
 ///
-/// # Arguments
-/// * `motor_id` - Motor ID (11-18)
+/// # Returns
+/// Array of objects, one per responding motor:
+/// - `id`: Motor ID
+/// - `position`: Joint angle in degrees
+/// - `load`: Load value from -1000 to 1000
+/// - `temperature`: Temperature in °C
+/// - `hardware_error`: Hardware Error Status register (0 = no error)
 ///
 /// # Example
 /// ```javascript
-/// await reboot_motor(11);
+/// const states = await get_motor_state_snapshot();
+/// states.forEach(s => console.log(`Motor ${s.id}: ${s.position.toFixed(1)}° ${s.temperature}°C`));
 /// ```
 #[wasm_bindgen]
-pub async fn reboot_motor(motor_id: u8) -> Result<(), JsValue> {
+pub async fn get_motor_state_snapshot() -> Result<JsValue, JsValue> {
     let port = get_port()?;
-    console::log_1(&format!("Rebooting motor {}...", motor_id).into());
 
-    let packet = build_reboot_packet(motor_id);
-    port.write(&packet).await?;
+    let regs = [
+        register::HardwareErrorStatus::INFO,
+        register::PresentLoad::INFO,
+        register::PresentPosition::INFO,
+        register::PresentTemperature::INFO,
+    ];
+    let entries: Vec<BulkReadEntry> = ROBOT_MOTOR_IDS
+        .iter()
+        .map(|&id| BulkReadEntry::covering(id, &regs))
+        .collect();
 
-    // Wait for motor to reboot
+    let packet = build_bulk_read(&entries);
+    let response = port.write_read(&packet, None).await?;
+
+    let states = js_sys::Array::new();
+    for (entry, _error, data) in parse_bulk_read_response(&response, &entries) {
+        let [hardware_error, load, position, temperature] =
+            regs.map(|reg| entry.value(&reg, &data).unwrap_or(0));
+
+        let state = js_sys::Object::new();
+        js_sys::Reflect::set(&state, &"id".into(), &JsValue::from(entry.id))?;
+        js_sys::Reflect::set(
+            &state,
+            &"position".into(),
+            &JsValue::from(raw_to_radians(position).to_degrees()),
+        )?;
+        js_sys::Reflect::set(&state, &"load".into(), &JsValue::from(load))?;
+        js_sys::Reflect::set(&state, &"temperature".into(), &JsValue::from(temperature))?;
+        js_sys::Reflect::set(
+            &state,
+            &"hardware_error".into(),
+            &JsValue::from(hardware_error),
+        )?;
+        states.push(&state);
+    }
+
+    Ok(states.into())
+}
+
+/// Get the number of status packets rejected because of a CRC mismatch.
+///
+/// Corrupted responses (e.g. on a noisy serial line) are dropped instead of
+/// being reported as garbage values. A growing count points to a wiring or
+/// baud rate issue.
+///
+/// # Example
+/// ```javascript
+/// const rejected = get_rejected_packet_count();
+/// if (rejected > 0) console.warn(`${rejected} corrupted packets dropped`);
+/// ```
+#[wasm_bindgen]
+pub fn get_rejected_packet_count() -> u32 {
+    dynamixel::rejected_packet_count()
+}
+
+/// Reset the rejected packet counter.
+#[wasm_bindgen]
+pub fn reset_rejected_packet_count() {
+    dynamixel::reset_rejected_packet_count();
+}
+
+/// Start recording all motor bus traffic.
+///
+/// Every chunk of bytes sent to or received from the motors is stored with a
+/// timestamp, to debug intermittent issues. Any previous capture is discarded.
+///
+/// # Arguments
+/// * `max_chunks` - Maximum number of chunks kept, oldest dropped first
+///   (default: 100000)
+///
+/// # Example
+/// ```javascript
+/// start_bus_capture();
+/// // ... reproduce the issue ...
+/// stop_bus_capture();
+/// const blob = new Blob([export_bus_capture()], { type: 'application/jsonl' });
+/// const a = document.createElement('a');
+/// a.href = URL.createObjectURL(blob);
+/// a.download = 'reachy-bus.jsonl';
+/// a.click();
+/// ```
+#[wasm_bindgen]
+pub fn start_bus_capture(max_chunks: Option<usize>) {
+    let capture = max_chunks.map_or_else(Capture::new, Capture::with_limit);
+    BUS_CAPTURE.with_borrow_mut(|c| *c = Some(capture));
+    BUS_CAPTURE_ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Stop recording bus traffic, keeping the capture for export.
+#[wasm_bindgen]
+pub fn stop_bus_capture() {
+    BUS_CAPTURE_ENABLED.store(false, Ordering::Relaxed);
+}
+
+/// Get the number of chunks in the bus capture.
+#[wasm_bindgen]
+pub fn get_bus_capture_length() -> usize {
+    BUS_CAPTURE.with_borrow(|c| c.as_ref().map_or(0, Capture::len))
+}
+
+/// Export the bus capture as JSON Lines.
+///
+/// # Returns
+/// One `{"t_ms", "dir", "data"}` object per line: milliseconds since the
+/// first chunk, `"tx"` or `"rx"`, and the bytes in hex. Empty if nothing
+/// was captured.
+#[wasm_bindgen]
+pub fn export_bus_capture() -> String {
+    BUS_CAPTURE.with_borrow(|c| c.as_ref().map(Capture::to_jsonl).unwrap_or_default())
+}
+
+/// Decode the bus capture into human-readable lines, one per packet.
+///
+/// # Example
+/// ```javascript
+/// console.log(describe_bus_capture());
+/// //      0.000 ms  TX  SYNC_READ Present Position from [10, 11, 12, 13, 14, 15, 16, 17, 18]
+/// //      1.000 ms  RX  STATUS ID 11 Present Position = 2048
+/// ```
+#[wasm_bindgen]
+pub fn describe_bus_capture() -> String {
+    BUS_CAPTURE.with_borrow(|c| c.as_ref().map(Capture::describe).unwrap_or_default())
+}
+
+/// Discard the bus capture.
+#[wasm_bindgen]
+pub fn clear_bus_capture() {
+    BUS_CAPTURE_ENABLED.store(false, Ordering::Relaxed);
+    BUS_CAPTURE.with_borrow_mut(|c| *c = None);
+}
+
+// ============================================================================
+// Motor Discovery API
+// ============================================================================
+
+/// Discover the motors present on the bus.
+///
+/// Broadcasts a PING and collects every answer, so a single call finds all
+/// motors regardless of their ID. Motors answer a broadcast PING one after
+/// the other, 3 ms apart per ID, so the scan waits `3 * (last_id + 1)` ms
+/// plus the I/O timeout: about 860 ms for the full range by default, and
+/// much less with a narrow range like `scan_motors(0, 20)`. Useful to diagnose a
+/// dead servo (missing from the expected IDs) or a mis-addressed one (found
+/// at an unexpected ID, e.g. a replacement motor still at factory ID 1).
+///
+/// # Arguments
+/// * `first_id` - Lowest ID to report (default 0)
+/// * `last_id` - Highest ID to report (default 252)
+///
+/// # Returns
+/// An object with:
+/// - `motors`: Array of `{id, model_number, model, firmware_version, error, expected}`
+///   for each motor found, where `model` is the model name (or `null` if
+///   unknown) and `expected` tells if the ID is one of Reachy Mini's (10-18)
+/// - `present_ids`: IDs of the motors found
+/// - `missing_ids`: Reachy Mini motor IDs in the range that didn't answer
+/// - `unexpected_ids`: IDs found that don't belong to Reachy Mini
+///
+/// # Example
+/// ```javascript
+/// const scan = await scan_motors();
+/// if (scan.missing_ids.length) console.warn(`Missing motors: ${scan.missing_ids}`);
+/// for (const m of scan.motors) {
+///   console.log(`ID ${m.id}: ${m.model ?? m.model_number} fw ${m.firmware_version}`);
+/// }
+/// ```
+#[wasm_bindgen]
+pub async fn scan_motors(first_id: Option<u8>, last_id: Option<u8>) -> Result<JsValue, JsValue> {
+    let first_id = first_id.unwrap_or(0);
+    let last_id = last_id.unwrap_or(MAX_ID);
+    if first_id > last_id || last_id > MAX_ID {
+        return Err(ReachyError::InvalidArgument(format!(
+            "Invalid ID range {}-{} (IDs go from 0 to {})",
+            first_id, last_id, MAX_ID
+        ))
+        .into());
+    }
+    let in_range = |id: u8| (first_id..=last_id).contains(&id);
+
+    let port = get_port()?;
+    let packet = build_ping_packet(BROADCAST_ID);
+    let timeout = broadcast_ping_window_ms(last_id) + port.io_policy().timeout_ms;
+    let response = port.write_read(&packet, Some(timeout)).await?;
+
+    let mut found = parse_ping_responses(&response);
+    found.retain(|ping| in_range(ping.id));
+    found.sort_by_key(|ping| ping.id);
+    found.dedup_by_key(|ping| ping.id);
+
+    let motors = js_sys::Array::new();
+    let present_ids = js_sys::Array::new();
+    let unexpected_ids = js_sys::Array::new();
+    for ping in &found {
+        let expected = ROBOT_MOTOR_IDS.contains(&ping.id);
+
+        let motor = js_sys::Object::new();
+        js_sys::Reflect::set(&motor, &"id".into(), &JsValue::from(ping.id))?;
+        js_sys::Reflect::set(
+            &motor,
+            &"model_number".into(),
+            &JsValue::from(ping.model_number),
+        )?;
+        js_sys::Reflect::set(
+            &motor,
+            &"model".into(),
+            &ping.model_name().map_or(JsValue::NULL, JsValue::from_str),
+        )?;
+        js_sys::Reflect::set(
+            &motor,
+            &"firmware_version".into(),
+            &JsValue::from(ping.firmware_version),
+        )?;
+        js_sys::Reflect::set(&motor, &"error".into(), &JsValue::from(ping.error))?;
+        js_sys::Reflect::set(&motor, &"expected".into(), &JsValue::from(expected))?;
+        motors.push(&motor);
+
+        present_ids.push(&JsValue::from(ping.id));
+        if !expected {
+            unexpected_ids.push(&JsValue::from(ping.id));
+        }
+    }
+
+    let missing_ids = js_sys::Array::new();
+    for &id in ROBOT_MOTOR_IDS.iter().filter(|&&id| in_range(id)) {
+        if !found.iter().any(|ping| ping.id == id) {
+            missing_ids.push(&JsValue::from(id));
+        }
+    }
+
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(&result, &"motors".into(), &motors)?;
+    js_sys::Reflect::set(&result, &"present_ids".into(), &present_ids)?;
+    js_sys::Reflect::set(&result, &"missing_ids".into(), &missing_ids)?;
+    js_sys::Reflect::set(&result, &"unexpected_ids".into(), &unexpected_ids)?;
+    Ok(result.into())
+}
+
+// ============================================================================
+// Motor Provisioning API
+// ============================================================================
+
+/// Write EEPROM settings to a single motor.
+///
+/// EEPROM registers can only be written with torque disabled, so this checks
+/// that the motor answers PING and has torque off before writing anything.
+/// When changing the ID, it also checks that no other motor already uses the
+/// new ID. Omitted settings are left unchanged.
+///
+/// # Arguments
+/// * `motor_id` - Current ID of the motor
+/// * `new_id` - New motor ID (0-252)
+/// * `baud_rate` - New baud rate in bps (9600, 57600, 115200, 1000000, 2000000,
+///   3000000 or 4000000). Anything other than 1000000 makes the motor
+///   unreachable over the Reachy Mini link.
+/// * `return_delay_us` - Status return delay in microseconds (0-508)
+/// * `drive_mode` - Drive Mode register value (bit 0: reverse direction)
+/// * `min_position_deg` - Min position limit in degrees
+/// * `max_position_deg` - Max position limit in degrees
+///
+/// # Errors
+/// * `MISSING_MOTORS` if the motor doesn't answer
+/// * `INVALID_STATE` if torque is enabled or the new ID is already taken
+/// * `INVALID_ARGUMENT` if a value is out of range
+/// * `MOTOR_STATUS_ERROR` if the motor rejects a write
+///
+/// # Example
+/// ```javascript
+/// await disable_torque();
+/// await configure_motor_eeprom(1, 14, null, 0);  // ID 1 -> 14, no return delay
+/// ```
+#[wasm_bindgen]
+pub async fn configure_motor_eeprom(
+    motor_id: u8,
+    new_id: Option<u8>,
+    baud_rate: Option<u32>,
+    return_delay_us: Option<u16>,
+    drive_mode: Option<u8>,
+    min_position_deg: Option<f32>,
+    max_position_deg: Option<f32>,
+) -> Result<(), JsValue> {
+    let baud_rate = match baud_rate {
+        Some(bps) => Some(baud_rate_code(bps).ok_or_else(|| {
+            ReachyError::InvalidArgument(format!("Unsupported baud rate {}", bps))
+        })?),
+        None => None,
+    };
+    let return_delay_time = match return_delay_us {
+        Some(us) if us > 508 => {
+            return Err(ReachyError::InvalidArgument(format!(
+                "Return delay {} us out of range 0-508",
+                us
+            ))
+            .into())
+        }
+        Some(us) => Some((us / 2) as u8),
+        None => None,
+    };
+    let position_limit = |deg: f32| radians_to_raw(deg.to_radians());
+
+    let config = EepromConfig {
+        id: new_id,
+        baud_rate,
+        return_delay_time,
+        drive_mode,
+        min_position_limit: min_position_deg.map(position_limit),
+        max_position_limit: max_position_deg.map(position_limit),
+    };
+
+    let port = get_port()?;
+    write_eeprom(&port, motor_id, &config).await?;
+    Ok(())
+}
+
+/// Provision a replacement motor as one of Reachy Mini's motors.
+///
+/// Guided flow for a new XL330 out of the box:
+/// 1. Looks for the new motor at `current_id` (default 1), at 1 Mbps and
+///    then at its factory 57600 baud
+/// 2. Checks `target_id` isn't already used and torque is off
+/// 3. Writes return delay 0, baud rate 1 Mbps and the new ID to EEPROM
+/// 4. Verifies the motor answers PING at `target_id` at 1 Mbps
+///
+/// Connect only the new motor's cable if another motor on the bus could
+/// share its current ID.
+///
+/// Reaching a motor at 57600 baud requires a WebSerial connection, which is
+/// reopened at that speed for the EEPROM writes and back at 1 Mbps after
+/// them. Over WebSocket, the motor must already be set to 1 Mbps (e.g. with
+/// Dynamixel Wizard).
+///
+/// # Arguments
+/// * `target_id` - ID the motor replaces (10-18)
+/// * `current_id` - ID the new motor answers to (default 1)
+///
+/// # Returns
+/// `{id, model_number, model, firmware_version}` as reported by the motor
+/// at its new ID.
+///
+/// # Errors
+/// Returns an `INVALID_STATE` error if no motor answers at `current_id`,
+/// naming the baud rates tried.
+///
+/// # Example
+/// ```javascript
+/// const motor = await provision_replacement_motor(14);
+/// console.log(`Motor ${motor.id} is a ${motor.model}`);
+/// ```
+#[wasm_bindgen]
+pub async fn provision_replacement_motor(
+    target_id: u8,
+    current_id: Option<u8>,
+) -> Result<JsValue, JsValue> {
+    let current_id = current_id.unwrap_or(FACTORY_MOTOR_ID);
+
+    let mut port = get_port()?;
+    let mut reopened = false;
+    if ping_motor_internal(&port, current_id).await?.is_none() {
+        if port.websocket_url().is_some() {
+            return Err(ReachyError::InvalidState(format!(
+                "No motor answers at ID {} at 1 Mbps. New motors ship at 57600 baud, which a \
+                 WebSocket connection can't switch to: provision over WebSerial, or set the \
+                 motor to 1 Mbps first",
+                current_id
+            ))
+            .into());
+        }
+        port = reopen_serial_port(FACTORY_BAUD_RATE).await?;
+        reopened = true;
+        if !matches!(ping_motor_internal(&port, current_id).await, Ok(Some(_))) {
+            reopen_serial_port(LINK_BAUD_RATE).await?;
+            return Err(ReachyError::InvalidState(format!(
+                "No motor answers at ID {} at 1 Mbps or 57600 baud",
+                current_id
+            ))
+            .into());
+        }
+    }
+
+    let provisioned = provision_motor(&port, current_id, target_id).await;
+    if reopened {
+        port = reopen_serial_port(LINK_BAUD_RATE).await?;
+    }
+    provisioned?;
+
+    let ping = ping_motor_internal(&port, target_id)
+        .await?
+        .ok_or(ReachyError::MissingMotors {
+            ids: vec![target_id],
+        })?;
+    console::log_1(&format!("Motor {} provisioned as ID {}", current_id, target_id).into());
+
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(&result, &"id".into(), &JsValue::from(ping.id))?;
+    js_sys::Reflect::set(
+        &result,
+        &"model_number".into(),
+        &JsValue::from(ping.model_number),
+    )?;
+    js_sys::Reflect::set(
+        &result,
+        &"model".into(),
+        &ping.model_name().map_or(JsValue::NULL, JsValue::from_str),
+    )?;
+    js_sys::Reflect::set(
+        &result,
+        &"firmware_version".into(),
+        &JsValue::from(ping.firmware_version),
+    )?;
+    Ok(result.into())
+}
+
+/// Write the EEPROM settings that make the motor at `current_id` Reachy
+/// Mini's motor `target_id`: return delay 0, baud rate 1 Mbps and the ID.
+///
+/// Rust counterpart of the EEPROM step of `provision_replacement_motor()`,
+/// for any [`MotorBus`] opened at the motor's current baud rate, e.g.
+/// `native::SerialBus::open_with_baud_rate(path, 57600)` for a new motor.
+/// The motor then answers at 1 Mbps only.
+///
+/// # Errors
+/// * `ReachyError::InvalidArgument` if `target_id` isn't one of Reachy
+///   Mini's motor IDs
+/// * `ReachyError::MissingMotors` if no motor answers at `current_id`
+/// * `ReachyError::InvalidState` if `target_id` is taken or torque is on
+pub async fn provision_motor(
+    port: &impl MotorBus,
+    current_id: u8,
+    target_id: u8,
+) -> Result<(), ReachyError> {
+    if !ROBOT_MOTOR_IDS.contains(&target_id) {
+        return Err(ReachyError::InvalidArgument(format!(
+            "Target ID {} is not a Reachy Mini motor ID (10-18)",
+            target_id
+        )));
+    }
+
+    let config = EepromConfig {
+        id: Some(target_id),
+        baud_rate: baud_rate_code(LINK_BAUD_RATE),
+        return_delay_time: Some(0),
+        ..EepromConfig::default()
+    };
+    write_eeprom(port, current_id, &config).await
+}
+
+/// Reset a motor's control table to its factory defaults.
+///
+/// Servicing tool: this erases calibration such as position limits and drive
+/// mode. To guard against resetting the wrong motor, `confirm_id` must repeat
+/// `motor_id`, and there is no default `mode`.
+///
+/// # Arguments
+/// * `motor_id` - Motor ID (not broadcast)
+/// * `mode` - What to reset:
+///   - `"except_id_and_baud"` - Keeps ID and baud rate; the motor stays reachable
+///   - `"except_id"` - Keeps the ID; baud rate goes back to 57600
+///   - `"all"` - ID goes back to 1 and baud rate to 57600
+/// * `confirm_id` - Must equal `motor_id`
+///
+/// With `"except_id"` or `"all"`, the motor can't be reached over the 1 Mbps
+/// link afterwards until its baud rate is set back, e.g. with
+/// `provision_replacement_motor()` over WebSerial.
+///
+/// # Example
+/// ```javascript
+/// await disable_torque();
+/// await factory_reset_motor(14, "except_id_and_baud", 14);
+/// ```
+#[wasm_bindgen]
+pub async fn factory_reset_motor(
+    motor_id: u8,
+    mode: String,
+    confirm_id: u8,
+) -> Result<(), JsValue> {
+    let mode = FactoryResetMode::from_name(&mode).ok_or_else(|| {
+        ReachyError::InvalidArgument(format!(
+            "Unknown factory reset mode \"{}\" (expected \"except_id_and_baud\", \"except_id\" or \"all\")",
+            mode
+        ))
+    })?;
+    if motor_id > MAX_ID {
+        return Err(ReachyError::InvalidArgument(format!(
+            "Factory reset needs a single motor ID, got {}",
+            motor_id
+        ))
+        .into());
+    }
+    if confirm_id != motor_id {
+        return Err(ReachyError::InvalidArgument(format!(
+            "confirm_id {} doesn't match motor_id {}",
+            confirm_id, motor_id
+        ))
+        .into());
+    }
+
+    let port = get_port()?;
+    if ping_motor_internal(&port, motor_id).await?.is_none() {
+        return Err(ReachyError::MissingMotors {
+            ids: vec![motor_id],
+        }
+        .into());
+    }
+
+    console::log_1(&format!("Factory resetting motor {} ({:?})...", motor_id, mode).into());
+    let packet = build_factory_reset_packet(motor_id, mode);
+    let response = port.try_write_read(&packet, None).await?;
+    if !response.is_empty() {
+        parse_write_status(&response)?;
+    }
+
+    // The motor restarts to apply the defaults
     sleep(500).await?;
 
+    if mode.keeps_baud_rate() {
+        console::log_1(&format!("Motor {} reset to factory defaults", motor_id).into());
+    } else {
+        let id = if mode.keeps_id() {
+            motor_id
+        } else {
+            FACTORY_MOTOR_ID
+        };
+        console::warn_1(
+            &format!(
+                "Motor {} reset to factory defaults: now ID {} at 57600 baud",
+                motor_id, id
+            )
+            .into(),
+        );
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Motor Reboot API
+// ============================================================================
+
+/// Reboot a specific motor by ID.
+///
+/// This clears any hardware error status and reinitializes the motor.
+/// The motor will be unresponsive for approximately 500ms after reboot.
+///
+/// # Arguments
+/// * `motor_id` - Motor ID (10-18)
+///
+/// # Example
+/// ```javascript
+/// await reboot_motor(11);
+/// ```
+#[wasm_bindgen]
+pub async fn reboot_motor(motor_id: u8) -> Result<(), JsValue> {
+    let port = get_port()?;
+    console::log_1(&format!("Rebooting motor {}...", motor_id).into());
+
+    reboot_motor_internal(&port, motor_id).await?;
+
     console::log_1(&format!("Motor {} rebooted", motor_id).into());
     Ok(())
 }
@@ -1012,13 +2428,13 @@ pub async fn reboot_head_motors() -> Result<(), JsValue> {
     Ok(())
 }
 
-/// Reboot all motors (head + antennas).
+/// Reboot all motors (body + head + antennas).
 ///
 /// Reboots each motor sequentially with appropriate delays.
 /// This is useful for clearing hardware errors on all motors.
 ///
 /// # Warning
-/// This operation takes approximately 4 seconds (500ms per motor × 8 motors).
+/// This operation takes approximately 4.5 seconds (500ms per motor × 9 motors).
 ///
 /// # Example
 /// ```javascript
@@ -1029,7 +2445,7 @@ pub async fn reboot_head_motors() -> Result<(), JsValue> {
 #[wasm_bindgen]
 pub async fn reboot_all_motors() -> Result<(), JsValue> {
     console::log_1(&JsValue::from_str("Rebooting all motors..."));
-    for &motor_id in &ALL_MOTOR_IDS {
+    for &motor_id in &ROBOT_MOTOR_IDS {
         reboot_motor(motor_id).await?;
     }
     console::log_1(&JsValue::from_str("All motors rebooted successfully"));
@@ -1049,6 +2465,11 @@ pub async fn reboot_all_motors() -> Result<(), JsValue> {
 /// - Bit 5: Electrical Shock Error
 /// - Bit 7: Overload Error
 ///
+/// Use `get_motor_error_report()` to get these bits decoded per motor,
+/// including the body motor (10). Motors that didn't respond are reported as
+/// 0; use
+/// `get_motor_errors_with_missing()` to tell them apart.
+///
 /// # Example
 /// ```javascript
 /// const errors = await get_motor_errors();
@@ -1060,18 +2481,121 @@ pub async fn reboot_all_motors() -> Result<(), JsValue> {
 pub async fn get_motor_errors() -> Result<Vec<u8>, JsValue> {
     let port = get_port()?;
     let packet = build_sync_read_hardware_error(&ALL_MOTOR_IDS);
-    let response = port.write_read(&packet, Some(DEFAULT_WAIT_MS)).await?;
+    let response = port.write_read(&packet, None).await?;
 
-    let parsed = parse_1byte_packets(&response);
+    // Motors with a hardware error answer with the alert bit set, so keep those
+    let parsed = parse_1byte_packets_with_errors(&response);
 
     // Map by motor ID, default to 0 for missing
-    let mut errors = vec![0u8; 8];
-    for (id, error) in parsed {
-        if id >= 11 && id <= 18 {
-            errors[(id - 11) as usize] = error;
+    let errors = values_by_id(&ALL_MOTOR_IDS, parsed)
+        .into_iter()
+        .map(|error| error.unwrap_or(0))
+        .collect();
+    Ok(errors)
+}
+
+/// Get hardware error status for all motors, reporting which motors didn't
+/// respond.
+///
+/// # Returns
+/// An object `{values, missing_ids}`:
+/// - `values`: 8 Hardware Error Status values for motors 11-18, `null` for
+///   motors that didn't respond
+/// - `missing_ids`: IDs of the motors that didn't respond
+#[wasm_bindgen]
+pub async fn get_motor_errors_with_missing() -> Result<JsValue, JsValue> {
+    let port = get_port()?;
+    let packet = build_sync_read_hardware_error(&ALL_MOTOR_IDS);
+    let response = port.write_read(&packet, None).await?;
+
+    // Motors with a hardware error answer with the alert bit set, so keep those
+    let errors = values_by_id(&ALL_MOTOR_IDS, parse_1byte_packets_with_errors(&response));
+    readings_to_js(&ALL_MOTOR_IDS, errors)
+}
+
+/// Get a decoded hardware error report for all motors, body included.
+///
+/// Reads the Hardware Error Status register of every motor (10-18) and
+/// decodes each bit into a named flag.
+///
+/// # Returns
+/// An object with:
+/// - `motors`: Array of `{id, joint, error, ok, input_voltage, hall_sensor,
+///   overheating, encoder, electrical_shock, overload, summary}`, one per
+///   responding motor (`joint` is `"body_yaw"` for motor 10, `error` the raw
+///   register value)
+/// - `motors_no_response`: Array of motor IDs that didn't respond
+/// - `summary`: Human-readable one-line summary
+///
+/// # Example
+/// ```javascript
+/// const report = await get_motor_error_report();
+/// console.log(report.summary);
+/// for (const m of report.motors) {
+///   if (m.overheating) console.warn(`${m.joint} is overheating`);
+/// }
+/// ```
+#[wasm_bindgen]
+pub async fn get_motor_error_report() -> Result<JsValue, JsValue> {
+    let port = get_port()?;
+    let packet = build_sync_read_hardware_error(&ROBOT_MOTOR_IDS);
+    let response = port.write_read(&packet, None).await?;
+
+    // Motors with a hardware error answer with the alert bit set, so keep those
+    let parsed = parse_1byte_packets_with_errors(&response);
+
+    let motors = js_sys::Array::new();
+    let mut problems: Vec<String> = Vec::new();
+    let mut motors_no_response: Vec<u8> = Vec::new();
+
+    let joints = std::iter::once(BODY_JOINT_NAME).chain(JOINT_NAMES);
+    for (&id, joint) in ROBOT_MOTOR_IDS.iter().zip(joints) {
+        let Some(&(_, bits)) = parsed.iter().find(|(motor_id, _)| *motor_id == id) else {
+            motors_no_response.push(id);
+            continue;
+        };
+        let status = HardwareErrorStatus(bits);
+
+        let motor = js_sys::Object::new();
+        js_sys::Reflect::set(&motor, &"id".into(), &JsValue::from(id))?;
+        js_sys::Reflect::set(&motor, &"joint".into(), &JsValue::from_str(joint))?;
+        js_sys::Reflect::set(&motor, &"error".into(), &JsValue::from(bits))?;
+        js_sys::Reflect::set(&motor, &"ok".into(), &JsValue::from(status.is_ok()))?;
+        for (bit, key, _) in HardwareErrorStatus::FLAGS {
+            js_sys::Reflect::set(&motor, &key.into(), &JsValue::from(bits & bit != 0))?;
+        }
+        js_sys::Reflect::set(
+            &motor,
+            &"summary".into(),
+            &JsValue::from_str(&status.to_string()),
+        )?;
+        motors.push(&motor);
+
+        if !status.is_ok() {
+            problems.push(format!("Motor {} ({}): {}", id, joint, status));
         }
     }
-    Ok(errors)
+
+    if !motors_no_response.is_empty() {
+        problems.push(format!("No response from motors {:?}", motors_no_response));
+    }
+    let summary = if problems.is_empty() {
+        format!("All {} motors OK", ROBOT_MOTOR_IDS.len())
+    } else {
+        problems.join("; ")
+    };
+
+    let no_response = js_sys::Array::new();
+    for id in &motors_no_response {
+        no_response.push(&JsValue::from(*id));
+    }
+
+    let report = js_sys::Object::new();
+    js_sys::Reflect::set(&report, &"motors".into(), &motors)?;
+    js_sys::Reflect::set(&report, &"motors_no_response".into(), &no_response)?;
+    js_sys::Reflect::set(&report, &"summary".into(), &JsValue::from_str(&summary))?;
+
+    Ok(report.into())
 }
 
 /// Check all motors and reboot any that have hardware errors.
@@ -1100,57 +2624,22 @@ pub async fn check_and_reboot_motors() -> Result<JsValue, JsValue> {
 
     console::log_1(&JsValue::from_str("Checking motors for hardware errors..."));
 
-    // Use SYNC_READ to get hardware error status from all motors
-    let packet = build_sync_read_hardware_error(&ALL_MOTOR_IDS);
-    let response = port.write_read(&packet, Some(DEFAULT_WAIT_MS)).await?;
-
-    let parsed = parse_1byte_packets(&response);
-
-    // Track which motors responded and which have errors
-    let mut motors_with_errors: Vec<u8> = Vec::new();
-    let mut motors_responded: Vec<u8> = Vec::new();
-
-    for (motor_id, error_status) in parsed {
-        motors_responded.push(motor_id);
-        if error_status != 0 {
-            motors_with_errors.push(motor_id);
-            console::log_1(
-                &format!(
-                    "Motor {} has hardware error: 0x{:02X}",
-                    motor_id, error_status
-                )
-                .into(),
-            );
-        }
-    }
+    let report = check_and_reboot(&port).await?;
 
-    // Find motors that didn't respond
-    let motors_no_response: Vec<u8> = ALL_MOTOR_IDS
-        .iter()
-        .filter(|id| !motors_responded.contains(id))
-        .copied()
-        .collect();
-
-    if !motors_no_response.is_empty() {
-        console::log_1(&format!("Motors did not respond: {:?}", motors_no_response).into());
+    for motor_id in &report.motors_with_errors {
+        console::log_1(&format!("Motor {} had a hardware error", motor_id).into());
     }
-
-    // Reboot motors with errors
-    let mut motors_rebooted: Vec<u8> = Vec::new();
-    for motor_id in &motors_with_errors {
-        console::log_1(&format!("Rebooting motor {}...", motor_id).into());
-        reboot_motor(*motor_id).await?;
-        motors_rebooted.push(*motor_id);
+    if !report.motors_no_response.is_empty() {
+        console::log_1(&format!("Motors did not respond: {:?}", report.motors_no_response).into());
     }
-
-    if motors_rebooted.is_empty() {
+    if report.motors_rebooted.is_empty() {
         console::log_1(&JsValue::from_str("All motors OK, no reboot needed"));
     } else {
         console::log_1(
             &format!(
                 "Rebooted {} motor(s): {:?}",
-                motors_rebooted.len(),
-                motors_rebooted
+                report.motors_rebooted.len(),
+                report.motors_rebooted
             )
             .into(),
         );
@@ -1158,44 +2647,70 @@ pub async fn check_and_reboot_motors() -> Result<JsValue, JsValue> {
 
     // Return result as a JS object
     let result = js_sys::Object::new();
-    let motors_checked = ALL_MOTOR_IDS.len() - motors_no_response.len();
     js_sys::Reflect::set(
         &result,
         &JsValue::from_str("motors_checked"),
-        &JsValue::from(motors_checked as u32),
+        &JsValue::from(report.motors_checked as u32),
     )?;
 
-    let errors_arr = js_sys::Array::new();
-    for id in &motors_with_errors {
-        errors_arr.push(&JsValue::from(*id));
+    for (key, ids) in [
+        ("motors_with_errors", &report.motors_with_errors),
+        ("motors_rebooted", &report.motors_rebooted),
+        ("motors_no_response", &report.motors_no_response),
+    ] {
+        let arr = js_sys::Array::new();
+        for id in ids {
+            arr.push(&JsValue::from(*id));
+        }
+        js_sys::Reflect::set(&result, &JsValue::from_str(key), &arr)?;
     }
-    js_sys::Reflect::set(
-        &result,
-        &JsValue::from_str("motors_with_errors"),
-        &errors_arr,
-    )?;
 
-    let rebooted_arr = js_sys::Array::new();
-    for id in &motors_rebooted {
-        rebooted_arr.push(&JsValue::from(*id));
+    Ok(result.into())
+}
+
+/// Outcome of [`check_and_reboot`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RebootReport {
+    /// Number of motors that responded
+    pub motors_checked: usize,
+    /// Motors that reported a hardware error
+    pub motors_with_errors: Vec<u8>,
+    /// Motors that were rebooted
+    pub motors_rebooted: Vec<u8>,
+    /// Motors that didn't respond
+    pub motors_no_response: Vec<u8>,
+}
+
+/// Reboot every motor on `port` that reports a hardware error.
+///
+/// Rust counterpart of `check_and_reboot_motors()`, for any [`MotorBus`].
+pub async fn check_and_reboot(port: &impl MotorBus) -> Result<RebootReport, ReachyError> {
+    // Use SYNC_READ to get hardware error status from all motors
+    let packet = build_sync_read_hardware_error(&ROBOT_MOTOR_IDS);
+    let response = port.write_read(&packet, None).await?;
+
+    // Motors with a hardware error answer with the alert bit set, so keep those
+    let parsed = parse_1byte_packets_with_errors(&response);
+
+    let mut report = RebootReport::default();
+    for &id in &ROBOT_MOTOR_IDS {
+        match parsed.iter().find(|(motor_id, _)| *motor_id == id) {
+            Some(&(_, 0)) => report.motors_checked += 1,
+            Some(_) => {
+                report.motors_checked += 1;
+                report.motors_with_errors.push(id);
+            }
+            None => report.motors_no_response.push(id),
+        }
     }
-    js_sys::Reflect::set(
-        &result,
-        &JsValue::from_str("motors_rebooted"),
-        &rebooted_arr,
-    )?;
 
-    let no_response_arr = js_sys::Array::new();
-    for id in &motors_no_response {
-        no_response_arr.push(&JsValue::from(*id));
+    // Reboot motors with errors
+    for &id in &report.motors_with_errors {
+        reboot_motor_internal(port, id).await?;
+        report.motors_rebooted.push(id);
     }
-    js_sys::Reflect::set(
-        &result,
-        &JsValue::from_str("motors_no_response"),
-        &no_response_arr,
-    )?;
 
-    Ok(result.into())
+    Ok(report)
 }
 
 // ============================================================================
@@ -1208,54 +2723,41 @@ pub async fn check_and_reboot_motors() -> Result<JsValue, JsValue> {
 /// Use this for trajectory planning or simulation.
 ///
 /// # Arguments
-/// * `angles_deg` - Vector of 6 joint angles in degrees (or 8 if including antennas)
+/// * `angles_deg` - Joint angles in degrees: the 6 head angles, or 8 with
+///   the antennas after them (as returned by `get_all_joints()`), optionally
+///   followed by the body angle (7 or 9 values)
 ///
 /// # Returns
-/// Vector of 6 floats: `[x, y, z, roll, pitch, yaw]`
-/// - Position in mm, orientation in degrees
+/// Vector of 6 floats: `[x, y, z, roll, pitch, yaw]`, followed by the body
+/// yaw when the body angle was given
+/// - Position in mm, orientation in degrees, in the world frame when the
+///   body angle was given and relative to the body otherwise
 ///
 /// # Example
 /// ```javascript
 /// const pose = forward_kinematics([0, 0, 0, 0, 0, 0]);
 /// console.log(`At zero position, head is at: ${pose}`);
+///
+/// const joints = [...await get_all_joints(), await get_body_yaw()];
+/// const world = forward_kinematics(joints);
 /// ```
 #[wasm_bindgen]
 pub fn forward_kinematics(angles_deg: Vec<f32>) -> Result<Vec<f32>, JsValue> {
-    if angles_deg.len() < 6 {
-        return Err(JsValue::from_str("Expected at least 6 joint angles"));
-    }
-
-    let angles_rad: Vec<f32> = angles_deg[0..6].iter().map(|d| d.to_radians()).collect();
-
-    let mut kinematics = create_kinematics();
-
-    // Initialize with default position
-    let t_init =
-        nalgebra::Matrix4::new_translation(&nalgebra::Vector3::new(0.0, 0.0, HEAD_Z_OFFSET_M));
-    kinematics.reset_forward_kinematics(t_init);
-
-    // Iterate to converge
-    for _ in 0..100 {
-        kinematics.forward_kinematics(&angles_rad, None);
-    }
-
-    let t = kinematics.forward_kinematics(&angles_rad, None);
-
-    // Extract pose
-    let x = t[(0, 3)] * 1000.0;
-    let y = t[(1, 3)] * 1000.0;
-    let z = t[(2, 3)] * 1000.0 - HEAD_Z_OFFSET_MM;
+    let angles_rad: Vec<f32> = angles_deg.iter().map(|d| d.to_radians()).collect();
 
-    let (roll, pitch, yaw) = extract_euler_angles(&t);
+    let body_yaw = match angles_rad.len() {
+        6 | 8 => None,
+        7 | 9 => angles_rad.last().copied(),
+        n => {
+            return Err(ReachyError::InvalidArgument(format!(
+                "Expected 6, 7, 8 or 9 joint angles, got {}",
+                n
+            ))
+            .into())
+        }
+    };
 
-    Ok(vec![
-        x,
-        y,
-        z,
-        roll.to_degrees(),
-        pitch.to_degrees(),
-        yaw.to_degrees(),
-    ])
+    Ok(compute_forward_kinematics(&angles_rad[0..6], body_yaw))
 }
 
 /// Compute inverse kinematics from Cartesian pose.
@@ -1264,47 +2766,166 @@ pub fn forward_kinematics(angles_deg: Vec<f32>) -> Result<Vec<f32>, JsValue> {
 /// Use this for trajectory planning or to preview joint angles before sending.
 ///
 /// # Arguments
-/// * `xyzrpy` - Vector of 6 floats: `[x, y, z, roll, pitch, yaw]`
+/// * `xyzrpy` - Vector of 6 floats: `[x, y, z, roll, pitch, yaw]`, or 7 with
+///   the body yaw after them (as returned by `get_head_pose()`)
 ///   - Position in mm, orientation in degrees
 ///
 /// # Returns
-/// Vector of 6 joint angles in degrees
+/// Vector of 6 head joint angles in degrees, followed by the body angle
+/// when the body yaw was given. The body angle is kept within
+/// `max_relative_body_yaw_deg` of the head's yaw and within the range of its
+/// motor (see `set_limits()`).
 ///
 /// # Errors
-/// Returns error if the pose is unreachable
+/// Returns an `UNREACHABLE_POSE` error (with `branches`) if the pose is
+/// unreachable
 ///
 /// # Example
 /// ```javascript
 /// const joints = inverse_kinematics([0, 0, 50, 0, 15, 0]);
 /// console.log(`To look up 15°, set joints to: ${joints}`);
+///
+/// const joints = inverse_kinematics([0, 0, 0, 0, 0, 90, 45]);
+/// const bodyAngle = joints[6];
 /// ```
 #[wasm_bindgen]
 pub fn inverse_kinematics(xyzrpy: Vec<f32>) -> Result<Vec<f32>, JsValue> {
-    if xyzrpy.len() != 6 {
-        return Err(JsValue::from_str(
-            "Expected 6 values: [x, y, z, roll, pitch, yaw]",
-        ));
+    if xyzrpy.len() != 6 && xyzrpy.len() != 7 {
+        return Err(ReachyError::InvalidArgument(
+            "Expected 6 or 7 values: [x, y, z, roll, pitch, yaw, body_yaw?]".into(),
+        )
+        .into());
     }
 
-    let mut kinematics = create_kinematics();
-
-    // Build transformation matrix
-    let roll_rad = xyzrpy[3].to_radians();
-    let pitch_rad = xyzrpy[4].to_radians();
-    let yaw_rad = xyzrpy[5].to_radians();
+    let joints = compute_inverse_kinematics(
+        xyzrpy[0],
+        xyzrpy[1],
+        xyzrpy[2],
+        xyzrpy[3],
+        xyzrpy[4],
+        xyzrpy[5],
+        xyzrpy.get(6).copied(),
+    )?;
+    // The body comes first in motor ID order, but after the head in the API
+    let mut joints_deg: Vec<f32> = joints.iter().map(|r| r.to_degrees()).collect();
+    if joints_deg.len() == BODY_AND_HEAD_MOTOR_IDS.len() {
+        joints_deg.rotate_left(1);
+    }
 
-    let rotation = nalgebra::Rotation3::from_euler_angles(roll_rad, pitch_rad, yaw_rad);
-    let mut t = rotation.to_homogeneous();
+    Ok(joints_deg)
+}
 
-    // Apply translation (convert mm to m, add Z offset)
-    t[(0, 3)] = xyzrpy[0] / 1000.0;
-    t[(1, 3)] = xyzrpy[1] / 1000.0;
-    t[(2, 3)] = (xyzrpy[2] + HEAD_Z_OFFSET_MM) / 1000.0;
+/// Compute the Jacobian of the head at a pose.
+///
+/// This is a pure computation function that does not communicate with hardware.
+/// The Jacobian maps speeds of the 6 head joints to the motion of the head.
+///
+/// # Arguments
+/// * `xyzrpy` - Vector of 6 floats: `[x, y, z, roll, pitch, yaw]`, or 7 with
+///   the body yaw after them (as returned by `get_head_pose()`)
+///   - Position in mm, orientation in degrees
+///
+/// # Returns
+/// 36 floats, the 6x6 matrix in row-major order. Column `j` is the motion of
+/// the head for one degree of head joint `j`: the velocity of the head
+/// origin `[vx, vy, vz]` in mm, then the angular velocity `[wx, wy, wz]` in
+/// degrees, both in the world frame.
+///
+/// # Errors
+/// * Returns an `UNREACHABLE_POSE` error (with `branches`) if the pose is
+///   unreachable
+/// * Returns an `INVALID_ARGUMENT` error if the pose is a singularity, where
+///   the head can move with its joints locked
+///
+/// # Example
+/// ```javascript
+/// const j = jacobian([0, 0, 0, 0, 0, 0]);
+/// // Head velocity along x (mm) for 1° of each joint
+/// console.log(j.slice(0, 6));
+/// ```
+#[wasm_bindgen]
+pub fn jacobian(xyzrpy: Vec<f32>) -> Result<Vec<f32>, JsValue> {
+    let (t, body_yaw) = pose_argument(&xyzrpy)?;
+    let jacobian = create_kinematics().jacobian(t, body_yaw)?;
+
+    // Per degree of joint, in mm and degrees
+    let unit = 1.0_f32.to_radians();
+    let scale = [1000.0 * unit, 1000.0 * unit, 1000.0 * unit, 1.0, 1.0, 1.0];
+    let values = jacobian
+        .row_iter()
+        .zip(scale)
+        .flat_map(|(row, scale)| row.iter().map(move |v| v * scale).collect::<Vec<f32>>())
+        .collect();
+    Ok(values)
+}
 
-    let joints = kinematics.inverse_kinematics(t, None);
-    let joints_deg: Vec<f32> = joints.iter().map(|r| r.to_degrees()).collect();
+/// Measure how close the head is to a singularity at a pose.
+///
+/// This is a pure computation function that does not communicate with hardware.
+/// The measures come from the singular values of the `jacobian()`, with head
+/// motions in mm and degrees weighted like in `set_head_pose_clamped()`.
+///
+/// # Arguments
+/// * `xyzrpy` - Vector of 6 floats: `[x, y, z, roll, pitch, yaw]`, or 7 with
+///   the body yaw after them
+/// * `mm_per_degree` - Millimeters of head motion worth one degree of head
+///   rotation (default: 1)
+///
+/// # Returns
+/// An object `{condition_number, manipulability, singularity_distance}`:
+/// - `condition_number`: 1 when the head moves as easily in every direction,
+///   growing without bound near either kind of singularity (about 4 at the
+///   center of the workspace)
+/// - `manipulability`: volume of head motions for one degree of each joint
+/// - `singularity_distance`: smallest joint motion in degrees moving the head
+///   by 1 mm (or `1 / mm_per_degree` degrees)
+///
+/// The two kinds of singularity give different values:
+/// - At the edge of the workspace (a serial singularity), an arm is aligned
+///   with its rod and the head loses a direction of motion: `manipulability`
+///   goes to 0 while `singularity_distance` stays non-zero.
+/// - At a parallel singularity, the head could move with its joints locked:
+///   `condition_number` and `manipulability` are `Infinity` and
+///   `singularity_distance` is 0.
+///
+/// `singularity_distance` only measures parallel singularities, so use
+/// `condition_number` to find poses close to any singularity.
+///
+/// # Errors
+/// * Returns an `UNREACHABLE_POSE` error (with `branches`) if the pose is
+///   unreachable
+/// * Returns an `INVALID_ARGUMENT` error if `mm_per_degree` isn't positive
+///
+/// # Example
+/// ```javascript
+/// const { condition_number } = manipulability(await get_head_pose());
+/// if (condition_number > 20) console.warn("Close to a singularity");
+/// ```
+#[wasm_bindgen]
+pub fn manipulability(xyzrpy: Vec<f32>, mm_per_degree: Option<f32>) -> Result<JsValue, JsValue> {
+    let (t, body_yaw) = pose_argument(&xyzrpy)?;
+    let weights = pose_weights(mm_per_degree)?;
+    let measures = create_kinematics().manipulability(t, body_yaw, weights)?;
 
-    Ok(joints_deg)
+    // Per degree of joint instead of per radian
+    let unit = 1.0_f32.to_radians();
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(
+        &result,
+        &"condition_number".into(),
+        &JsValue::from(measures.condition_number),
+    )?;
+    js_sys::Reflect::set(
+        &result,
+        &"manipulability".into(),
+        &JsValue::from(measures.manipulability * unit.powi(6)),
+    )?;
+    js_sys::Reflect::set(
+        &result,
+        &"singularity_distance".into(),
+        &JsValue::from(measures.singularity_distance.to_degrees()),
+    )?;
+    Ok(result.into())
 }
 
 // ============================================================================
@@ -1340,7 +2961,8 @@ pub async fn start_fk_stream(duration: Option<f64>) -> Result<(), JsValue> {
 /// Replay recorded motion.
 ///
 /// Plays back frames that were recorded during a previous `start_fk_stream(duration)` call.
-/// Automatically enables torque before playback and disables after.
+/// Automatically enables torque before playback and disables after. Frames
+/// go through the joint limits (see `set_limits()`).
 ///
 /// # Example
 /// ```javascript
@@ -1352,27 +2974,58 @@ pub async fn start_fk_stream(duration: Option<f64>) -> Result<(), JsValue> {
 /// ```
 #[wasm_bindgen]
 pub async fn replay_recording() -> Result<(), JsValue> {
-    enable_torque().await?;
-
+    let port = get_port()?;
     let frames = PLAYBACK_FRAMES.with_borrow(|f| f.clone());
+    replay_frames(&port, &frames).await?;
+    Ok(())
+}
+
+/// Replay frames of joint angles in radians on `port`, one every 20 ms.
+///
+/// Rust counterpart of `replay_recording()`, for any [`MotorBus`]. Each
+/// frame holds one angle per motor of `get_all_joints()`; playback stops
+/// early when `stop()` is called. Every frame is checked against the limits
+/// of `set_limits` before torque is enabled, and torque is disabled again
+/// however playback ends.
+///
+/// # Errors
+/// * `ReachyError::InvalidState` if there are no frames
+/// * `ReachyError::LimitExceeded` or `ReachyError::InvalidArgument` if a
+///   frame is out of range or not finite, before anything is sent
+pub async fn replay_frames(port: &impl MotorBus, frames: &[Vec<f32>]) -> Result<(), ReachyError> {
     if frames.is_empty() {
-        return Err(JsValue::from_str("No recorded frames to replay"));
+        return Err(ReachyError::InvalidState(
+            "No recorded frames to replay".into(),
+        ));
+    }
+
+    let mut packets = Vec::with_capacity(frames.len());
+    for frame in frames {
+        let mut frame = frame.clone();
+        enforce_joint_limits(&ALL_MOTOR_IDS, &mut frame)?;
+        packets.push(build_sync_write_position_radians(&ALL_MOTOR_IDS, &frame));
     }
 
     STOP_FLAG.store(false, Ordering::Relaxed);
-    let port = get_port()?;
 
-    for frame in frames.iter() {
-        let packet = build_sync_write_position_radians(&ALL_MOTOR_IDS.to_vec(), frame);
-        port.write(&packet).await?;
-        sleep(20).await?;
+    let result = match write_torque(port, true).await {
+        Ok(()) => send_frames(port, &packets).await,
+        Err(e) => Err(e),
+    };
+    let disabled = write_torque(port, false).await;
+    result.and(disabled)
+}
+
+/// Send prepared position packets 20 ms apart until done or stopped.
+async fn send_frames(port: &impl MotorBus, packets: &[Vec<u8>]) -> Result<(), ReachyError> {
+    for packet in packets {
+        port.send(packet).await?;
+        port.sleep(20).await;
 
         if STOP_FLAG.load(Ordering::Relaxed) {
             break;
         }
     }
-
-    disable_torque().await?;
     Ok(())
 }
 
@@ -1406,44 +3059,456 @@ pub fn get_recording_length() -> usize {
 // ============================================================================
 
 /// Get the current port or return an error.
-fn get_port() -> Result<Arc<GenericPort>, JsValue> {
+fn get_port() -> Result<Rc<GenericPort>, ReachyError> {
     GENERIC_PORT
         .with_borrow(|port| port.clone())
-        .ok_or_else(|| JsValue::from_str("Not connected to Reachy Mini. Call connect() first."))
+        .ok_or(ReachyError::NotConnected)
+}
+
+/// Make `port` the current connection and start monitoring it.
+fn install_port(mut port: GenericPort) {
+    let generation = LINK_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    port.generation = generation;
+    port.set_io_policy(IO_POLICY.get());
+    GENERIC_PORT.with_borrow_mut(|p| *p = Some(Rc::new(port)));
+    update_link(generation, LinkMonitor::opened);
+    wasm_bindgen_futures::spawn_local(heartbeat(generation));
+}
+
+/// Reopen the current WebSerial connection at `baud_rate` and make it the
+/// current connection.
+async fn reopen_serial_port(baud_rate: u32) -> Result<Rc<GenericPort>, ReachyError> {
+    let port = get_port()?.reopen_serial(baud_rate).await?;
+    install_port(port);
+    get_port()
+}
+
+/// Apply an event to the connection state if `generation` is the current
+/// connection, notifying the JS callback of any change.
+///
+/// Returns whether the state changed.
+fn update_link(
+    generation: u32,
+    event: impl FnOnce(&mut LinkMonitor) -> Option<StateChange>,
+) -> bool {
+    if generation != LINK_GENERATION.load(Ordering::Relaxed) {
+        return false;
+    }
+    let Some(change) = LINK.with_borrow_mut(event) else {
+        return false;
+    };
+
+    console::log_1(&format!("Connection {} -> {}", change.previous, change.current).into());
+    // Cloned so the callback can replace itself
+    if let Some(callback) = LINK_CALLBACK.with_borrow(|c| c.clone()) {
+        let current = JsValue::from_str(change.current.as_str());
+        let previous = JsValue::from_str(change.previous.as_str());
+        if let Err(e) = callback.call2(&JsValue::NULL, &current, &previous) {
+            console::error_1(&e);
+        }
+    }
+    true
+}
+
+/// Whether connection `generation` can send requests.
+///
+/// Ports not installed by `connect` (generation 0) are not monitored.
+fn link_usable(generation: u32) -> bool {
+    generation == 0
+        || (generation == LINK_GENERATION.load(Ordering::Relaxed)
+            && LINK.with_borrow(|link| link.state().is_usable()))
+}
+
+/// Mark connection `generation` as lost, and reopen it if it was a WebSocket
+/// and auto-reconnect is enabled.
+fn link_lost(generation: u32) {
+    if !update_link(generation, LinkMonitor::lost) {
+        return;
+    }
+
+    let url = GENERIC_PORT.with_borrow(|port| {
+        port.as_ref()
+            .and_then(|p| p.websocket_url().map(str::to_string))
+    });
+    match url {
+        Some(url) if AUTO_RECONNECT.load(Ordering::Relaxed) => {
+            wasm_bindgen_futures::spawn_local(reconnect(generation, url))
+        }
+        Some(_) => {}
+        None => console::warn_1(&"Serial connection lost, call connect() again".into()),
+    }
+}
+
+/// Reopen the WebSocket of connection `generation` with backoff, until it
+/// succeeds, the policy gives up, or another connection replaces it.
+async fn reconnect(generation: u32, url: String) {
+    let policy = RECONNECT_POLICY.get();
+    update_link(generation, LinkMonitor::connecting);
+
+    let mut attempt = 1;
+    while policy.allows(attempt) {
+        let _ = sleep(policy.delay(attempt)).await;
+        if generation != LINK_GENERATION.load(Ordering::Relaxed) {
+            return;
+        }
+
+        match GenericPort::from_websocket(&url).await {
+            Ok(port) if generation == LINK_GENERATION.load(Ordering::Relaxed) => {
+                install_port(port);
+                console::log_1(&format!("Reconnected to {}", url).into());
+                return;
+            }
+            Ok(_) => return,
+            Err(e) => {
+                console::log_1(&format!("Reconnect attempt {} failed: {:?}", attempt, e).into())
+            }
+        }
+        attempt += 1;
+    }
+
+    console::warn_1(&format!("Gave up reconnecting to {}", url).into());
+    update_link(generation, LinkMonitor::closed);
+}
+
+/// Probe the motors of connection `generation` whenever the bus has been idle
+/// for the heartbeat interval, until the connection is replaced.
+async fn heartbeat(generation: u32) {
+    loop {
+        let interval = HEARTBEAT_INTERVAL_MS.load(Ordering::Relaxed);
+        let _ = sleep(if interval == 0 {
+            DEFAULT_HEARTBEAT_MS
+        } else {
+            interval
+        })
+        .await;
+        if generation != LINK_GENERATION.load(Ordering::Relaxed) {
+            return;
+        }
+        if interval == 0 || !link_usable(generation) {
+            continue;
+        }
+
+        let Ok(port) = get_port() else {
+            return;
+        };
+        let idle_ms = js_sys::Date::now() - port.last_activity_ms.get();
+        if port.arbiter.is_busy() || idle_ms < interval as f64 {
+            continue;
+        }
+        // Transport closure is reported by the port itself
+        match check_link(&*port).await {
+            Ok(answered) => {
+                update_link(generation, |link| link.heartbeat(answered));
+            }
+            Err(ReachyError::TransportClosed) => {}
+            Err(_) => {
+                update_link(generation, |link| link.heartbeat(false));
+            }
+        }
+    }
 }
 
-/// Read motor positions from specified motor IDs.
+/// Read motor positions from specified motor IDs, in radians.
 ///
 /// Uses resilient parsing that scans for packet headers,
 /// so missing motor responses don't affect other results.
-async fn read_motor_positions(port: &GenericPort, motor_ids: &[u8]) -> Result<Vec<f32>, JsValue> {
-    let packet = build_sync_current_position(motor_ids);
-    let response = port.write_read(&packet, Some(DEFAULT_WAIT_MS)).await?;
+/// Motors that didn't respond are reported as NaN.
+/// Uses Fast Sync Read when enabled with `set_fast_sync_read`.
+///
+/// # Errors
+/// `ReachyError::MotorStatusError` if a motor answered with an error byte
+/// and `set_fail_on_motor_alert(true)` is in effect on this thread.
+pub async fn read_motor_positions(
+    port: &impl MotorBus,
+    motor_ids: &[u8],
+) -> Result<Vec<f32>, ReachyError> {
+    read_motor_positions_checked(port, motor_ids, FAIL_ON_MOTOR_ALERT.get()).await
+}
+
+/// Read motor positions like [`read_motor_positions`], with the motor
+/// alert check given explicitly instead of by `set_fail_on_motor_alert`.
+///
+/// # Errors
+/// `ReachyError::MotorStatusError` if a motor answered with an error byte
+/// and `fail_on_alert` is `true`.
+pub async fn read_motor_positions_checked(
+    port: &impl MotorBus,
+    motor_ids: &[u8],
+    fail_on_alert: bool,
+) -> Result<Vec<f32>, ReachyError> {
+    let states = read_motor_states(port, motor_ids).await?;
+
+    if fail_on_alert {
+        for (&id, state) in motor_ids.iter().zip(&states) {
+            if let Some((_, status)) = state {
+                if !status.is_ok() {
+                    return Err(ReachyError::MotorStatusError {
+                        id,
+                        status: status.0,
+                    });
+                }
+            }
+        }
+    }
+
+    // NaN for missing motors, so they can't be mistaken for a real angle
+    Ok(states
+        .into_iter()
+        .map(|state| state.map_or(f32::NAN, |(rad, _)| rad))
+        .collect())
+}
+
+/// Read motor positions along with each motor's status error byte.
+///
+/// # Returns
+/// One entry per requested motor, in order: `(radians, status)`, or `None`
+/// if the motor didn't respond.
+async fn read_motor_states(
+    port: &impl MotorBus,
+    motor_ids: &[u8],
+) -> Result<Vec<Option<(f32, StatusError)>>, ReachyError> {
+    let fast = FAST_SYNC_READ.load(Ordering::Relaxed);
+    let packet = if fast {
+        build_fast_sync_read::<register::PresentPosition>(motor_ids)
+    } else {
+        build_sync_current_position(motor_ids)
+    };
+    let response = port.write_read(&packet, None).await?;
 
     // Parse all valid packets from response
-    let parsed = parse_position_packets(&response);
-
-    // Map results by motor ID, defaulting to 0.0 for missing motors
-    let mut positions = vec![0.0f32; motor_ids.len()];
-    for (id, raw_pos) in parsed {
-        // Find index of this motor in our request
-        if let Some(idx) = motor_ids.iter().position(|&m| m == id) {
-            positions[idx] = raw_to_radians(raw_pos);
+    let parsed = if fast {
+        parse_fast_sync_position_with_status(&response)
+    } else {
+        parse_position_packets_with_status(&response)
+    };
+
+    Ok(values_by_id(
+        motor_ids,
+        parsed
+            .into_iter()
+            .map(|(id, raw_pos, status)| (id, (raw_to_radians(raw_pos), status))),
+    ))
+}
+
+/// Read the temperature of each motor of `motor_ids` (`None` if missing).
+async fn read_temperatures(
+    port: &impl MotorBus,
+    motor_ids: &[u8],
+) -> Result<Vec<Option<u8>>, ReachyError> {
+    let packet = build_sync_read_temperature(motor_ids);
+    let response = port.write_read(&packet, None).await?;
+    Ok(values_by_id(motor_ids, parse_1byte_packets(&response)))
+}
+
+/// Read the load of each motor of `motor_ids` (`None` if missing).
+async fn read_loads(
+    port: &impl MotorBus,
+    motor_ids: &[u8],
+) -> Result<Vec<Option<i16>>, ReachyError> {
+    let packet = build_sync_read_load(motor_ids);
+    let response = port.write_read(&packet, None).await?;
+    Ok(values_by_id(
+        motor_ids,
+        parse_2byte_signed_packets(&response),
+    ))
+}
+
+/// Build a `{values, missing_ids}` object from per-motor readings.
+///
+/// Missing readings become `null` in `values`.
+fn readings_to_js<T: Into<JsValue>>(
+    motor_ids: &[u8],
+    readings: Vec<Option<T>>,
+) -> Result<JsValue, JsValue> {
+    let missing = missing_ids(motor_ids, &readings);
+
+    let values = js_sys::Array::new();
+    for reading in readings {
+        values.push(&reading.map_or(JsValue::NULL, Into::into));
+    }
+    let missing_ids = js_sys::Array::new();
+    for id in missing {
+        missing_ids.push(&JsValue::from(id));
+    }
+
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(&result, &"values".into(), &values)?;
+    js_sys::Reflect::set(&result, &"missing_ids".into(), &missing_ids)?;
+    Ok(result.into())
+}
+
+/// PING a single motor, returning `None` if it doesn't answer.
+async fn ping_motor_internal(
+    port: &impl MotorBus,
+    motor_id: u8,
+) -> Result<Option<PingResponse>, ReachyError> {
+    let packet = build_ping_packet(motor_id);
+    let response = port.try_write_read(&packet, None).await?;
+    Ok(parse_ping_responses(&response)
+        .into_iter()
+        .find(|ping| ping.id == motor_id))
+}
+
+/// Reboot a motor and wait for it to come back up.
+async fn reboot_motor_internal(port: &impl MotorBus, motor_id: u8) -> Result<(), ReachyError> {
+    // Read the acknowledgement so it isn't mistaken for a later response
+    port.try_write_read(&build_reboot_packet(motor_id), None)
+        .await?;
+    port.sleep(500).await;
+    Ok(())
+}
+
+/// Write a register of a single motor and check its acknowledgement.
+async fn write_register_internal(
+    port: &impl MotorBus,
+    motor_id: u8,
+    reg: &RegisterInfo,
+    raw: i32,
+) -> Result<(), ReachyError> {
+    let packet = build_write_info(motor_id, reg, raw);
+    let response = port.write_read(&packet, None).await?;
+    parse_write_status(&response)?;
+    Ok(())
+}
+
+/// Write EEPROM settings to a single motor after safety checks.
+///
+/// See `configure_motor_eeprom` for the checks performed.
+async fn write_eeprom(
+    port: &impl MotorBus,
+    motor_id: u8,
+    config: &EepromConfig,
+) -> Result<(), ReachyError> {
+    config.validate()?;
+
+    if ping_motor_internal(port, motor_id).await?.is_none() {
+        return Err(ReachyError::MissingMotors {
+            ids: vec![motor_id],
+        });
+    }
+
+    let torque = <register::TorqueEnable as Register>::INFO;
+    let packet = build_read_packet(motor_id, torque.address, torque.size);
+    let response = port.write_read(&packet, None).await?;
+    if parse_status_packet_1byte(&response)? != 0 {
+        return Err(ReachyError::InvalidState(format!(
+            "Motor {} has torque enabled; disable torque before writing EEPROM",
+            motor_id
+        )));
+    }
+
+    if let Some(new_id) = config.id.filter(|&id| id != motor_id) {
+        if ping_motor_internal(port, new_id).await?.is_some() {
+            return Err(ReachyError::InvalidState(format!(
+                "ID {} is already used by another motor",
+                new_id
+            )));
+        }
+    }
+
+    let id_reg = <register::Id as Register>::INFO;
+    let baud_reg = <register::BaudRate as Register>::INFO;
+    let mut id = motor_id;
+    for (reg, raw) in config.writes() {
+        if reg == baud_reg {
+            // The motor may switch speed before its acknowledgement gets through
+            let packet = build_write_info(id, &reg, raw);
+            port.try_write_read(&packet, None).await?;
+        } else {
+            write_register_internal(port, id, &reg, raw).await?;
+        }
+        if reg == id_reg {
+            id = raw as u8;
         }
     }
 
-    Ok(positions)
+    Ok(())
 }
 
 /// Set torque on all motors.
 async fn set_torque_internal(enable: bool) -> Result<(), JsValue> {
     let port = get_port()?;
-    let packet = build_sync_write_torque(&ALL_MOTOR_IDS.to_vec(), enable);
-    port.write(&packet).await?;
+    write_torque(&port, enable).await?;
     Ok(())
 }
 
+/// Enable or disable torque on every motor on `port`, body included.
+///
+/// Rust counterpart of `enable_torque()` and `disable_torque()`, for any
+/// [`MotorBus`].
+pub async fn write_torque(port: &impl MotorBus, enable: bool) -> Result<(), ReachyError> {
+    let packet = build_sync_write_torque(&ROBOT_MOTOR_IDS, enable);
+    port.send(&packet).await
+}
+
+/// Move the motors of `ids` to angles in degrees, one per motor, within the
+/// joint limits.
+async fn write_joint_angles(
+    port: &impl MotorBus,
+    ids: &[u8],
+    angles_deg: &[f32],
+) -> Result<(), ReachyError> {
+    let mut angles_rad: Vec<f32> = angles_deg.iter().map(|d| d.to_radians()).collect();
+    enforce_joint_limits(ids, &mut angles_rad)?;
+
+    let packet = build_sync_write_position_radians(ids, &angles_rad);
+    port.send(&packet).await
+}
+
+/// Head pose `[x, y, z, roll, pitch, yaw]` for 6 head joint angles in
+/// radians, iterating forward kinematics from the neutral pose.
+///
+/// With a body yaw in radians, the pose is turned with the body and the
+/// body yaw in degrees is appended.
+fn compute_forward_kinematics(angles_rad: &[f32], body_yaw: Option<f32>) -> Vec<f32> {
+    let mut kinematics = create_kinematics();
+
+    // Initialize with default position
+    let t_init =
+        nalgebra::Matrix4::new_translation(&nalgebra::Vector3::new(0.0, 0.0, HEAD_Z_OFFSET_M));
+    kinematics.reset_forward_kinematics(t_init);
+
+    // Iterate to converge
+    for _ in 0..100 {
+        kinematics.forward_kinematics(angles_rad, None);
+    }
+
+    let t = kinematics.forward_kinematics(angles_rad, body_yaw);
+
+    let mut pose = pose_to_xyzrpy(&t);
+    pose.extend(body_yaw.map(f32::to_degrees));
+    pose
+}
+
+/// Apply the limits of `set_limits` to joint angles in radians, one per
+/// motor of `ids`. Returns whether an angle was clamped.
+fn enforce_joint_limits(ids: &[u8], angles_rad: &mut [f32]) -> Result<bool, ReachyError> {
+    LIMITS.with_borrow(|limits| limits.enforce_joints(ids, angles_rad))
+}
+
+/// Apply the limits of `set_limits` to a head pose in millimeters and
+/// degrees. Returns whether a value was clamped.
+fn enforce_pose_limits(pose: &mut [f32; 6]) -> Result<bool, ReachyError> {
+    LIMITS.with_borrow(|limits| limits.enforce_pose(pose))
+}
+
+/// Apply the limits of `set_limits` to a body yaw in degrees, for a head
+/// turned to `head_yaw` degrees.
+fn enforce_body_yaw_limits(body_yaw: f32, head_yaw: f32) -> Result<f32, ReachyError> {
+    LIMITS.with_borrow(|limits| limits.enforce_body_yaw(body_yaw, head_yaw))
+}
+
 /// Compute inverse kinematics for a given pose.
+///
+/// Without `body_yaw`, returns the 6 head joint angles in radians for a
+/// pose relative to the body. With a body yaw in degrees, the pose is in the
+/// world frame and the body angle comes first, `[body, head_1..6]`; the body
+/// is kept within `max_relative_body_yaw_deg` of the head's yaw and within
+/// the range of its motor, following the policy of `set_limits`.
+///
+/// # Errors
+/// `ReachyError::LimitExceeded` for a body yaw out of range with the
+/// `"reject"` policy, or the errors of `Kinematics::inverse_kinematics`.
 fn compute_inverse_kinematics(
     x: f32,
     y: f32,
@@ -1451,9 +3516,61 @@ fn compute_inverse_kinematics(
     roll: f32,
     pitch: f32,
     yaw: f32,
-) -> Result<Vec<f32>, JsValue> {
-    let mut kinematics = create_kinematics();
+    body_yaw: Option<f32>,
+) -> Result<Vec<f32>, ReachyError> {
+    let kinematics = create_kinematics();
+    let t = pose_from_xyzrpy(x, y, z, roll, pitch, yaw);
+
+    let Some(body_yaw) = body_yaw else {
+        return kinematics.inverse_kinematics(t, None);
+    };
+    let body_yaw_rad = enforce_body_yaw_limits(body_yaw, yaw)?.to_radians();
+    let mut joint_angles = kinematics.inverse_kinematics(t, Some(body_yaw_rad))?;
+    joint_angles.insert(0, body_yaw_rad);
+    Ok(joint_angles)
+}
+
+/// Head transform and body yaw in radians for a pose `[x, y, z, roll,
+/// pitch, yaw]` in millimeters and degrees, optionally followed by the body
+/// yaw in degrees.
+fn pose_argument(xyzrpy: &[f32]) -> Result<(nalgebra::Matrix4<f32>, Option<f32>), ReachyError> {
+    if xyzrpy.len() != 6 && xyzrpy.len() != 7 {
+        return Err(ReachyError::InvalidArgument(
+            "Expected 6 or 7 values: [x, y, z, roll, pitch, yaw, body_yaw?]".into(),
+        ));
+    }
+    let t = pose_from_xyzrpy(
+        xyzrpy[0], xyzrpy[1], xyzrpy[2], xyzrpy[3], xyzrpy[4], xyzrpy[5],
+    );
+    Ok((t, xyzrpy.get(6).map(|d| d.to_radians())))
+}
+
+/// Weights of pose distances, one millimeter being worth `mm_per_degree`
+/// degrees (default: 1).
+fn pose_weights(mm_per_degree: Option<f32>) -> Result<PoseWeights, ReachyError> {
+    let mm_per_degree = mm_per_degree.unwrap_or(1.0);
+    if !(mm_per_degree.is_finite() && mm_per_degree > 0.0) {
+        return Err(ReachyError::InvalidArgument(format!(
+            "mm_per_degree must be positive, got {}",
+            mm_per_degree
+        )));
+    }
+    Ok(PoseWeights {
+        position: 1000.0,
+        orientation: mm_per_degree * 1.0_f32.to_degrees(),
+    })
+}
 
+/// Head transform for a pose in millimeters and degrees, `z` relative to
+/// the minimum height.
+fn pose_from_xyzrpy(
+    x: f32,
+    y: f32,
+    z: f32,
+    roll: f32,
+    pitch: f32,
+    yaw: f32,
+) -> nalgebra::Matrix4<f32> {
     let rotation = nalgebra::Rotation3::from_euler_angles(
         roll.to_radians(),
         pitch.to_radians(),
@@ -1464,8 +3581,28 @@ fn compute_inverse_kinematics(
     t[(0, 3)] = x / 1000.0;
     t[(1, 3)] = y / 1000.0;
     t[(2, 3)] = (z + HEAD_Z_OFFSET_MM) / 1000.0;
+    t
+}
+
+/// `[x, y, z, roll, pitch, yaw]` in millimeters and degrees of a head
+/// transform, inverse of [`pose_from_xyzrpy`].
+fn pose_to_xyzrpy(t: &nalgebra::Matrix4<f32>) -> Vec<f32> {
+    // Extract position (convert to mm and apply Z offset)
+    let x = t[(0, 3)] * 1000.0;
+    let y = t[(1, 3)] * 1000.0;
+    let z = t[(2, 3)] * 1000.0 - HEAD_Z_OFFSET_MM;
+
+    // Extract orientation (Euler XYZ)
+    let (roll, pitch, yaw) = extract_euler_angles(t);
 
-    Ok(kinematics.inverse_kinematics(t, None))
+    vec![
+        x,
+        y,
+        z,
+        roll.to_degrees(),
+        pitch.to_degrees(),
+        yaw.to_degrees(),
+    ]
 }
 
 /// Extract Euler angles (roll, pitch, yaw) from a transformation matrix.
@@ -1541,7 +3678,7 @@ pub async fn fk(duration: Option<f64>) -> Result<(), JsValue> {
     let port = get_port()?;
     let mut kinematics = create_kinematics();
 
-    let mut results = vec![0.0f32; 8];
+    let mut results = vec![0.0f32; ALL_MOTOR_IDS.len()];
     let start_time = js_sys::Date::now();
 
     STOP_FLAG.store(false, Ordering::Relaxed);
@@ -1549,7 +3686,7 @@ pub async fn fk(duration: Option<f64>) -> Result<(), JsValue> {
 
     loop {
         let ping_current = build_sync_current_position(&ALL_MOTOR_IDS);
-        let result = port.write_read(&ping_current, Some(DEFAULT_WAIT_MS)).await;
+        let result = port.write_read(&ping_current, None).await;
 
         match result {
             Err(err) => {
@@ -1558,8 +3695,8 @@ pub async fn fk(duration: Option<f64>) -> Result<(), JsValue> {
             Ok(res) => {
                 // Use resilient parsing that handles missing motor responses
                 for (id, pos) in parse_position_packets(&res) {
-                    if id >= 11 && id <= 18 {
-                        results[(id - 11) as usize] = raw_to_radians(pos);
+                    if let Some(i) = ALL_MOTOR_IDS.iter().position(|&m| m == id) {
+                        results[i] = raw_to_radians(pos);
                     }
                 }
 
@@ -1571,7 +3708,7 @@ pub async fn fk(duration: Option<f64>) -> Result<(), JsValue> {
                     PLAYBACK_FRAMES.with_borrow_mut(|f| f.push(results.clone()));
                 }
 
-                let t = kinematics.forward_kinematics(&results[0..6].to_vec(), None);
+                let t = kinematics.forward_kinematics(&results[0..6], None);
                 let x = t[(0, 3)] * 1000.0;
                 let y = t[(1, 3)] * 1000.0;
                 let z = t[(2, 3)] * 1000.0 - HEAD_Z_OFFSET_MM;
@@ -1635,14 +3772,26 @@ pub async fn replay() -> Result<(), JsValue> {
 /// Generic port wrapper supporting both WebSocket and WebSerial connections.
 pub struct GenericPort {
     connection: Connection,
+    policy: Cell<IoPolicy>,
+    /// `LINK_GENERATION` when installed by `connect`, 0 otherwise
+    generation: u32,
+    /// Time of the last write or received chunk
+    last_activity_ms: Cell<f64>,
+    /// Serializes exchanges of concurrent JS calls
+    arbiter: Arbiter,
 }
 
 enum Connection {
     WebSerial {
+        /// The WebSerial `SerialPort`, to reopen it at another baud rate
+        port: JsValue,
         reader: ReadableStreamDefaultReader,
         writer: WritableStreamDefaultWriter,
+        /// `reader.read()` left pending by a read that timed out
+        pending_read: Cell<Option<Promise>>,
     },
     WebSocket {
+        url: String,
         sender: Arc<Mutex<futures_util::stream::SplitSink<WebSocket, Message>>>,
         receiver: Arc<Mutex<futures_util::stream::SplitStream<WebSocket>>>,
     },
@@ -1711,10 +3860,11 @@ impl GenericPort {
                 } else {
                     // Check if this is Safari - provide specific error message with underlying error
                     if Self::is_safari() {
-                        return Err(JsValue::from_str(&format!(
+                        return Err(ReachyError::ConnectionFailed(format!(
                             "Safari has known issues connecting to localhost from HTTPS websites. Please use Chrome or Firefox for the best experience. (Error: {:?})",
                             e
-                        )));
+                        ))
+                        .into());
                     }
 
                     // Non-Chrome browser: show cleaner error about WebSocket connection
@@ -1727,7 +3877,7 @@ impl GenericPort {
                     } else {
                         format!("Could not connect to {}", url)
                     };
-                    Err(JsValue::from_str(&error_msg))
+                    Err(ReachyError::ConnectionFailed(error_msg).into())
                 }
             }
         }
@@ -1768,31 +3918,45 @@ impl GenericPort {
 
     /// Connect via WebSocket.
     pub async fn from_websocket(url: &str) -> Result<Self, JsValue> {
-        let ws = WebSocket::open(url)
-            .map_err(|e| JsValue::from_str(&format!("WebSocket open failed: {:?}", e)))?;
+        let ws = WebSocket::open(url).map_err(|e| {
+            ReachyError::ConnectionFailed(format!("WebSocket open failed: {:?}", e))
+        })?;
 
         // Wait for connection
         loop {
             match ws.state() {
                 gloo::net::websocket::State::Connecting => sleep(10).await?,
                 gloo::net::websocket::State::Open => break,
-                _ => return Err(JsValue::from_str("WebSocket connection failed")),
+                _ => {
+                    return Err(
+                        ReachyError::ConnectionFailed("WebSocket connection failed".into()).into(),
+                    )
+                }
             }
         }
 
         let (sender, receiver) = ws.split();
         Ok(Self {
             connection: Connection::WebSocket {
+                url: url.to_string(),
                 sender: Arc::new(Mutex::new(sender)),
                 receiver: Arc::new(Mutex::new(receiver)),
             },
+            policy: Cell::new(IoPolicy::default()),
+            generation: 0,
+            last_activity_ms: Cell::new(js_sys::Date::now()),
+            arbiter: Arbiter::new(),
         })
     }
 
     /// Connect via WebSerial.
     pub async fn from_webserial() -> Result<Self, JsValue> {
         let port = requestSerialPort().await?;
+        Self::from_serial_port(port)
+    }
 
+    /// Wrap an open WebSerial `SerialPort`.
+    fn from_serial_port(port: JsValue) -> Result<Self, JsValue> {
         let readable: ReadableStream =
             js_sys::Reflect::get(&port, &"readable".into())?.dyn_into()?;
         let writable: WritableStream =
@@ -1802,75 +3966,229 @@ impl GenericPort {
         let writer: WritableStreamDefaultWriter = writable.get_writer()?.dyn_into()?;
 
         Ok(Self {
-            connection: Connection::WebSerial { reader, writer },
+            connection: Connection::WebSerial {
+                port,
+                reader,
+                writer,
+                pending_read: Cell::new(None),
+            },
+            policy: Cell::new(IoPolicy::default()),
+            generation: 0,
+            last_activity_ms: Cell::new(js_sys::Date::now()),
+            arbiter: Arbiter::new(),
         })
     }
 
-    /// Read data from the connection.
-    pub async fn read(&self) -> Result<Vec<u8>, JsValue> {
+    /// Release stream locks (for WebSerial cleanup).
+    pub fn release_lock(&self) -> Result<(), JsValue> {
+        if let Connection::WebSerial { reader, writer, .. } = &self.connection {
+            reader.release_lock();
+            writer.release_lock();
+        }
+        Ok(())
+    }
+
+    /// Close the WebSerial port and open it again at `baud_rate`.
+    ///
+    /// # Errors
+    /// * `ReachyError::InvalidState` for a WebSocket connection, whose link
+    ///   speed is set by the server
+    /// * `ReachyError::Transport` if the port fails to close or open
+    async fn reopen_serial(&self, baud_rate: u32) -> Result<Self, ReachyError> {
+        let Connection::WebSerial { port, reader, .. } = &self.connection else {
+            return Err(ReachyError::InvalidState(
+                "The baud rate can only be changed on a WebSerial connection".into(),
+            ));
+        };
+
+        // A pending read would keep the stream locked
+        let _ = JsFuture::from(reader.cancel()).await;
+        let _ = self.release_lock();
+        call_serial_port(port, "close", &JsValue::UNDEFINED).await?;
+
+        let options = js_sys::Object::new();
+        js_sys::Reflect::set(&options, &"baudRate".into(), &JsValue::from(baud_rate))
+            .map_err(transport_error)?;
+        call_serial_port(port, "open", &options).await?;
+        Self::from_serial_port(port.clone()).map_err(transport_error)
+    }
+
+    /// URL of a WebSocket connection, `None` for WebSerial.
+    pub fn websocket_url(&self) -> Option<&str> {
+        match &self.connection {
+            Connection::WebSocket { url, .. } => Some(url),
+            Connection::WebSerial { .. } => None,
+        }
+    }
+
+    /// Read the next chunk, recording it in the bus capture and tracking
+    /// the link.
+    async fn read_chunk(&self, timeout_ms: Option<u32>) -> Result<Option<Vec<u8>>, ReachyError> {
+        let result = self.read_connection(timeout_ms).await;
+        match &result {
+            Ok(Some(data)) => {
+                capture_chunk(Direction::Rx, data);
+                self.last_activity_ms.set(js_sys::Date::now());
+                update_link(self.generation, LinkMonitor::activity);
+            }
+            Ok(None) => {}
+            Err(ReachyError::TransportClosed) => link_lost(self.generation),
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// Read the next chunk from the connection, or `None` if nothing arrived
+    /// within `timeout_ms` (when given).
+    async fn read_connection(
+        &self,
+        timeout_ms: Option<u32>,
+    ) -> Result<Option<Vec<u8>>, ReachyError> {
         match &self.connection {
-            Connection::WebSerial { reader, .. } => {
-                let result = JsFuture::from(reader.read()).await?;
-                let value = js_sys::Reflect::get(&result, &"value".into())?;
+            Connection::WebSerial {
+                reader,
+                pending_read,
+                ..
+            } => {
+                // A timed-out `read()` still takes the next chunk when it
+                // resolves, so the next read awaits it instead of a new one
+                let promise = pending_read.take().unwrap_or_else(|| reader.read());
+                let Some(result) = with_timeout(JsFuture::from(promise.clone()), timeout_ms).await
+                else {
+                    pending_read.set(Some(promise));
+                    return Ok(None);
+                };
+                let result = result.map_err(transport_error)?;
+                let value =
+                    js_sys::Reflect::get(&result, &"value".into()).map_err(transport_error)?;
                 let data = js_sys::Uint8Array::from(value);
-                Ok(data.to_vec())
+                Ok(Some(data.to_vec()))
             }
             Connection::WebSocket { receiver, .. } => {
-                let mut rx = receiver
-                    .try_lock()
-                    .map_err(|e| JsValue::from_str(&format!("Lock failed: {:?}", e)))?;
-
-                if let Some(msg) = rx
-                    .try_next()
-                    .await
-                    .map_err(|e| JsValue::from_str(&format!("Read failed: {:?}", e)))?
-                {
-                    match msg {
-                        Message::Bytes(bytes) => Ok(bytes),
-                        _ => Err(JsValue::from_str("Unexpected message type")),
-                    }
-                } else {
-                    Err(JsValue::from_str("WebSocket closed"))
+                // Dropping `try_next` before it completes loses no message
+                let read = async { receiver.lock().await.try_next().await };
+                let Some(result) = with_timeout(read, timeout_ms).await else {
+                    return Ok(None);
+                };
+
+                match result {
+                    Ok(Some(Message::Bytes(bytes))) => Ok(Some(bytes)),
+                    Ok(Some(_)) => Err(ReachyError::Transport("Unexpected message type".into())),
+                    // An `error` event is always followed by `close`
+                    Ok(None)
+                    | Err(WebSocketError::ConnectionClose(_))
+                    | Err(WebSocketError::ConnectionError) => Err(ReachyError::TransportClosed),
+                    Err(e) => Err(ReachyError::Transport(format!("Read failed: {:?}", e))),
                 }
             }
         }
     }
+}
+
+/// Await `future`, or give up with `None` after `timeout_ms` (when given).
+async fn with_timeout<T>(
+    future: impl std::future::Future<Output = T>,
+    timeout_ms: Option<u32>,
+) -> Option<T> {
+    let Some(ms) = timeout_ms else {
+        return Some(future.await);
+    };
+    let timer = sleep(ms);
+    futures_util::pin_mut!(future, timer);
+    match futures_util::future::select(future, timer).await {
+        futures_util::future::Either::Left((value, _)) => Some(value),
+        futures_util::future::Either::Right(_) => None,
+    }
+}
+
+impl MotorBus for GenericPort {
+    async fn read(&self) -> Result<Vec<u8>, ReachyError> {
+        // Without a timeout, a chunk always comes back
+        Ok(self.read_chunk(None).await?.unwrap_or_default())
+    }
 
-    /// Write data to the connection.
-    pub async fn write(&self, packet: &[u8]) -> Result<(), JsValue> {
+    // The default races `read()` against `sleep()` and drops the read, which
+    // loses the chunk a pending WebSerial `reader.read()` resolves with later
+    async fn read_timeout(&self, ms: u32) -> Result<Option<Vec<u8>>, ReachyError> {
+        self.read_chunk(Some(ms)).await
+    }
+
+    async fn write(&self, packet: &[u8]) -> Result<(), ReachyError> {
+        // A closed WebSocket silently drops sent messages
+        if !link_usable(self.generation) {
+            return Err(ReachyError::TransportClosed);
+        }
+        capture_chunk(Direction::Tx, packet);
+        self.last_activity_ms.set(js_sys::Date::now());
         match &self.connection {
             Connection::WebSerial { writer, .. } => {
                 let chunk = js_sys::Uint8Array::from(packet);
-                JsFuture::from(writer.write_with_chunk(&chunk.into())).await?;
+                JsFuture::from(writer.write_with_chunk(&chunk.into()))
+                    .await
+                    .map_err(transport_error)?;
                 Ok(())
             }
             Connection::WebSocket { sender, .. } => {
                 sender
-                    .try_lock()
-                    .map_err(|e| JsValue::from_str(&format!("Lock failed: {:?}", e)))?
+                    .lock()
+                    .await
                     .send(Message::Bytes(packet.to_vec()))
                     .await
-                    .map_err(|e| JsValue::from_str(&format!("Send failed: {:?}", e)))?;
+                    .map_err(|e| ReachyError::Transport(format!("Send failed: {:?}", e)))?;
                 Ok(())
             }
         }
     }
 
-    /// Write data and read response.
-    pub async fn write_read(&self, packet: &[u8], wait: Option<u32>) -> Result<Vec<u8>, JsValue> {
-        self.write(packet).await?;
-        sleep(wait.unwrap_or(DEFAULT_WAIT_MS)).await?;
-        self.read().await
+    async fn sleep(&self, ms: u32) {
+        // setTimeout can't fail once the window exists
+        let _ = sleep(ms).await;
     }
 
-    /// Release stream locks (for WebSerial cleanup).
-    pub fn release_lock(&self) -> Result<(), JsValue> {
-        if let Connection::WebSerial { reader, writer, .. } = &self.connection {
-            reader.release_lock();
-            writer.release_lock();
-        }
-        Ok(())
+    fn now_ms(&self) -> f64 {
+        js_sys::Date::now()
     }
+
+    fn io_policy(&self) -> IoPolicy {
+        self.policy.get()
+    }
+
+    fn set_io_policy(&self, policy: IoPolicy) {
+        self.policy.set(policy);
+    }
+
+    fn arbiter(&self) -> Option<&Arbiter> {
+        Some(&self.arbiter)
+    }
+}
+
+/// Record a chunk in the bus capture, if one is running.
+fn capture_chunk(dir: Direction, data: &[u8]) {
+    if !BUS_CAPTURE_ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    BUS_CAPTURE.with_borrow_mut(|capture| {
+        if let Some(capture) = capture {
+            capture.record(dir, js_sys::Date::now(), data);
+        }
+    });
+}
+
+/// Call an async method of a WebSerial `SerialPort` and wait for it.
+async fn call_serial_port(port: &JsValue, method: &str, arg: &JsValue) -> Result<(), ReachyError> {
+    let function: js_sys::Function = js_sys::Reflect::get(port, &method.into())
+        .and_then(|f| f.dyn_into())
+        .map_err(transport_error)?;
+    let result = function.call1(port, arg).map_err(transport_error)?;
+    JsFuture::from(Promise::resolve(&result))
+        .await
+        .map_err(transport_error)?;
+    Ok(())
+}
+
+/// Wrap a JS exception from WebSerial into a transport error.
+fn transport_error(e: JsValue) -> ReachyError {
+    ReachyError::Transport(format!("{:?}", e))
 }
 
 // ============================================================================
@@ -0,0 +1,312 @@
+//! Limits on the positions commanded to the motors.
+//!
+//! [`Limits`] bounds each motor's angle, the head pose (a box in position and
+//! a cone about each axis in orientation), the antennas' swing and how far
+//! the body may turn away from the head. Every command of the browser API
+//! goes through them before reaching the bus, and out-of-range values are
+//! clamped or rejected depending on the [`LimitPolicy`].
+//!
+//! The defaults are loaded from `limits.json`, next to `motors.json`:
+//!
+//! ```json
+//! {
+//!   "policy": "clamp",
+//!   "joints": [{ "id": 11, "name": "head_1", "min_deg": -160.0, "max_deg": 160.0 }],
+//!   "position_mm": { "x": [-50.0, 50.0], "y": [-50.0, 50.0], "z": [-40.0, 30.0] },
+//!   "orientation_deg": { "roll": 40.0, "pitch": 40.0, "yaw": 180.0 },
+//!   "max_antenna_deg": 150.0,
+//!   "max_relative_body_yaw_deg": 65.0
+//! }
+//! ```
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ReachyError;
+use crate::{BODY_MOTOR_ID, LEFT_ANTENNA_ID, RIGHT_ANTENNA_ID};
+
+/// Default limits of the Reachy Mini (loaded at compile time)
+pub const DEFAULT_LIMITS_JSON: &str = include_str!("limits.json");
+
+/// What happens to a command outside the limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LimitPolicy {
+    /// Move the value to the nearest limit and send the command
+    #[default]
+    Clamp,
+    /// Fail with `ReachyError::LimitExceeded`; nothing is sent
+    Reject,
+}
+
+impl LimitPolicy {
+    /// Name used in JSON and JavaScript: `"clamp"` or `"reject"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LimitPolicy::Clamp => "clamp",
+            LimitPolicy::Reject => "reject",
+        }
+    }
+
+    /// Parse a name returned by [`as_str`](Self::as_str).
+    ///
+    /// # Errors
+    /// `ReachyError::InvalidArgument` for any other name.
+    pub fn from_name(name: &str) -> Result<Self, ReachyError> {
+        match name {
+            "clamp" => Ok(LimitPolicy::Clamp),
+            "reject" => Ok(LimitPolicy::Reject),
+            _ => Err(ReachyError::InvalidArgument(format!(
+                "Unknown limit policy {:?}, expected \"clamp\" or \"reject\"",
+                name
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for LimitPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Angle range of one motor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JointLimit {
+    pub id: u8,
+    /// Joint name used in errors, e.g. `"head_1"`
+    pub name: String,
+    pub min_deg: f32,
+    pub max_deg: f32,
+}
+
+/// Bounds of the head position in millimeters, as `[min, max]`, with the
+/// same origin as `set_head_pose()`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionLimits {
+    pub x: [f32; 2],
+    pub y: [f32; 2],
+    pub z: [f32; 2],
+}
+
+/// Largest head rotation about each axis in degrees, either way.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrientationLimits {
+    pub roll: f32,
+    pub pitch: f32,
+    pub yaw: f32,
+}
+
+/// Limits on the joint angles and head pose that may be commanded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Limits {
+    #[serde(default)]
+    pub policy: LimitPolicy,
+    /// Per-motor ranges; motors not listed are only bound by
+    /// `max_antenna_deg` if they are antennas
+    pub joints: Vec<JointLimit>,
+    pub position_mm: PositionLimits,
+    pub orientation_deg: OrientationLimits,
+    /// Largest antenna angle in degrees, either way
+    pub max_antenna_deg: f32,
+    /// Largest angle in degrees between the body yaw and the head yaw,
+    /// either way
+    #[serde(default = "default_max_relative_body_yaw_deg")]
+    pub max_relative_body_yaw_deg: f32,
+}
+
+fn default_max_relative_body_yaw_deg() -> f32 {
+    65.0
+}
+
+impl Default for Limits {
+    /// The limits of [`DEFAULT_LIMITS_JSON`].
+    fn default() -> Self {
+        Self::from_json(DEFAULT_LIMITS_JSON).expect("Failed to parse default limits JSON")
+    }
+}
+
+impl Limits {
+    /// Load limits from JSON, in the format of `limits.json`.
+    ///
+    /// # Errors
+    /// `ReachyError::InvalidArgument` if the JSON doesn't match the format,
+    /// or a range is empty, negative or not finite.
+    pub fn from_json(text: &str) -> Result<Self, ReachyError> {
+        let limits: Limits = serde_json::from_str(text)
+            .map_err(|e| ReachyError::InvalidArgument(format!("Invalid limits: {}", e)))?;
+        limits.validate()?;
+        Ok(limits)
+    }
+
+    /// Export as JSON, in the format of `limits.json`.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    fn validate(&self) -> Result<(), ReachyError> {
+        let ranges = self
+            .joints
+            .iter()
+            .map(|j| (j.name.as_str(), [j.min_deg, j.max_deg]))
+            .chain([
+                ("x", self.position_mm.x),
+                ("y", self.position_mm.y),
+                ("z", self.position_mm.z),
+            ]);
+        for (name, [min, max]) in ranges {
+            if !(min.is_finite() && max.is_finite() && min <= max) {
+                return Err(ReachyError::InvalidArgument(format!(
+                    "Invalid limits for {}: [{}, {}]",
+                    name, min, max
+                )));
+            }
+        }
+
+        let OrientationLimits { roll, pitch, yaw } = self.orientation_deg;
+        let maxima = [
+            ("roll", roll),
+            ("pitch", pitch),
+            ("yaw", yaw),
+            ("max_antenna_deg", self.max_antenna_deg),
+            ("max_relative_body_yaw_deg", self.max_relative_body_yaw_deg),
+        ];
+        for (name, max) in maxima {
+            if !(max.is_finite() && max >= 0.0) {
+                return Err(ReachyError::InvalidArgument(format!(
+                    "Invalid limit for {}: {}",
+                    name, max
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Range of motor `id` in degrees, `None` if it is unbounded.
+    pub fn joint_range(&self, id: u8) -> Option<(&str, [f32; 2])> {
+        let joint = self.joints.iter().find(|j| j.id == id);
+        let antenna = [LEFT_ANTENNA_ID, RIGHT_ANTENNA_ID].contains(&id);
+
+        let mut range = joint.map_or([f32::NEG_INFINITY, f32::INFINITY], |j| {
+            [j.min_deg, j.max_deg]
+        });
+        if antenna {
+            range[0] = range[0].max(-self.max_antenna_deg);
+            range[1] = range[1].min(self.max_antenna_deg);
+        }
+
+        let name = match (joint, id) {
+            (Some(j), _) => j.name.as_str(),
+            (None, LEFT_ANTENNA_ID) => "l_antenna",
+            (None, RIGHT_ANTENNA_ID) => "r_antenna",
+            (None, _) => return None,
+        };
+        Some((name, range))
+    }
+
+    /// Apply the limits to joint angles in radians, one per motor of `ids`.
+    ///
+    /// Returns whether an angle was clamped.
+    ///
+    /// # Errors
+    /// * `ReachyError::LimitExceeded` for the first angle out of range, with
+    ///   the `Reject` policy
+    /// * `ReachyError::InvalidArgument` if an angle is NaN or infinite
+    pub fn enforce_joints(&self, ids: &[u8], angles_rad: &mut [f32]) -> Result<bool, ReachyError> {
+        let mut clamped = false;
+        for (&id, angle) in ids.iter().zip(angles_rad.iter_mut()) {
+            let value = angle.to_degrees();
+            let limited = match self.joint_range(id) {
+                Some((name, range)) => self.enforce(name, value, range)?,
+                None => self.enforce(&format!("motor {}", id), value, [f32::MIN, f32::MAX])?,
+            };
+            if limited != value {
+                *angle = limited.to_radians();
+                clamped = true;
+            }
+        }
+        Ok(clamped)
+    }
+
+    /// Apply the limits to a head pose `[x, y, z, roll, pitch, yaw]` in
+    /// millimeters and degrees.
+    ///
+    /// Returns whether a value was clamped.
+    ///
+    /// # Errors
+    /// Same as [`enforce_joints`](Self::enforce_joints).
+    pub fn enforce_pose(&self, pose: &mut [f32; 6]) -> Result<bool, ReachyError> {
+        let OrientationLimits { roll, pitch, yaw } = self.orientation_deg;
+        let PositionLimits { x, y, z } = self.position_mm;
+        let ranges = [
+            ("x", x),
+            ("y", y),
+            ("z", z),
+            ("roll", [-roll, roll]),
+            ("pitch", [-pitch, pitch]),
+            ("yaw", [-yaw, yaw]),
+        ];
+
+        let mut clamped = false;
+        for (&(name, range), value) in ranges.iter().zip(pose.iter_mut()) {
+            let limited = self.enforce(name, *value, range)?;
+            if limited != *value {
+                *value = limited;
+                clamped = true;
+            }
+        }
+        Ok(clamped)
+    }
+
+    /// Apply the limits to a body yaw in degrees, given the yaw of the head
+    /// in degrees: the range of the body motor, and at most
+    /// `max_relative_body_yaw_deg` away from the head.
+    ///
+    /// # Errors
+    /// Same as [`enforce_joints`](Self::enforce_joints), naming `"body_yaw"`.
+    pub fn enforce_body_yaw(
+        &self,
+        body_yaw_deg: f32,
+        head_yaw_deg: f32,
+    ) -> Result<f32, ReachyError> {
+        let relative = self.max_relative_body_yaw_deg;
+        let mut range = [head_yaw_deg - relative, head_yaw_deg + relative];
+        let mut name = "body_yaw";
+        if let Some((joint, [min, max])) = self.joint_range(BODY_MOTOR_ID) {
+            range = [range[0].max(min), range[1].min(max)];
+            name = joint;
+        }
+        // The head is turned past the body's range: the body is as close as it gets
+        if range[0] > range[1] {
+            range = if range[0] > head_yaw_deg {
+                [range[0], range[0]]
+            } else {
+                [range[1], range[1]]
+            };
+        }
+        self.enforce(name, body_yaw_deg, range)
+    }
+
+    /// `value` within `[min, max]` following the policy.
+    fn enforce(&self, name: &str, value: f32, [min, max]: [f32; 2]) -> Result<f32, ReachyError> {
+        if !value.is_finite() {
+            return Err(ReachyError::InvalidArgument(format!(
+                "{} is {}",
+                name, value
+            )));
+        }
+        if (min..=max).contains(&value) {
+            return Ok(value);
+        }
+        match self.policy {
+            LimitPolicy::Clamp => Ok(value.clamp(min, max)),
+            LimitPolicy::Reject => Err(ReachyError::LimitExceeded {
+                limit: name.to_string(),
+                value,
+                min,
+                max,
+            }),
+        }
+    }
+}
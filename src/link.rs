@@ -0,0 +1,213 @@
+//! Health of the connection to the robot.
+//!
+//! [`LinkMonitor`] tracks the connection through its [`ConnectionState`]s
+//! from transport events and heartbeats:
+//!
+//! ```text
+//! Connecting --opened--> Open <--answer / heartbeat missed twice--> Degraded
+//!                          \                                           /
+//!                           +----------------lost---------------------+--> Closed
+//! ```
+//!
+//! The browser API runs [`check_link`] as a heartbeat while the bus is idle,
+//! and reconnects lost WebSocket connections following a [`ReconnectPolicy`].
+
+use std::fmt;
+
+use crate::bus::MotorBus;
+use crate::dynamixel::{address, build_read_packet};
+use crate::error::ReachyError;
+
+/// Motor probed by [`check_link`]
+pub const HEARTBEAT_MOTOR_ID: u8 = 11;
+
+/// Consecutive missed heartbeats before an open link is degraded
+const DEGRADED_AFTER_MISSED: u32 = 2;
+
+/// State of the connection to the robot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Opening the connection, or reconnecting after it was lost
+    Connecting,
+    /// Connected, and the motors answer
+    Open,
+    /// Connected, but the motors stopped answering heartbeats
+    Degraded,
+    /// Not connected
+    Closed,
+}
+
+impl ConnectionState {
+    /// Name exposed to JavaScript: `"connecting"`, `"open"`, `"degraded"`
+    /// or `"closed"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConnectionState::Connecting => "connecting",
+            ConnectionState::Open => "open",
+            ConnectionState::Degraded => "degraded",
+            ConnectionState::Closed => "closed",
+        }
+    }
+
+    /// Whether requests can be sent in this state.
+    pub fn is_usable(&self) -> bool {
+        matches!(self, ConnectionState::Open | ConnectionState::Degraded)
+    }
+}
+
+impl fmt::Display for ConnectionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A transition between two connection states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateChange {
+    pub previous: ConnectionState,
+    pub current: ConnectionState,
+}
+
+/// Connection state machine.
+///
+/// Each event returns the resulting [`StateChange`], or `None` if the state
+/// didn't change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkMonitor {
+    state: ConnectionState,
+    missed_heartbeats: u32,
+}
+
+impl Default for LinkMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LinkMonitor {
+    /// A monitor in the [`Closed`](ConnectionState::Closed) state.
+    pub fn new() -> Self {
+        Self {
+            state: ConnectionState::Closed,
+            missed_heartbeats: 0,
+        }
+    }
+
+    pub fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    /// Number of heartbeats missed in a row.
+    pub fn missed_heartbeats(&self) -> u32 {
+        self.missed_heartbeats
+    }
+
+    /// A connection attempt started.
+    pub fn connecting(&mut self) -> Option<StateChange> {
+        self.transition(ConnectionState::Connecting)
+    }
+
+    /// The connection opened.
+    pub fn opened(&mut self) -> Option<StateChange> {
+        self.missed_heartbeats = 0;
+        self.transition(ConnectionState::Open)
+    }
+
+    /// The connection was closed on purpose, or connecting failed.
+    pub fn closed(&mut self) -> Option<StateChange> {
+        self.transition(ConnectionState::Closed)
+    }
+
+    /// The transport reported the connection closed.
+    ///
+    /// Only affects an open or degraded connection, so late errors from a
+    /// connection being replaced are ignored.
+    pub fn lost(&mut self) -> Option<StateChange> {
+        if !self.state.is_usable() {
+            return None;
+        }
+        self.transition(ConnectionState::Closed)
+    }
+
+    /// Data arrived from the robot.
+    pub fn activity(&mut self) -> Option<StateChange> {
+        self.missed_heartbeats = 0;
+        if self.state != ConnectionState::Degraded {
+            return None;
+        }
+        self.transition(ConnectionState::Open)
+    }
+
+    /// A heartbeat was answered, or not.
+    pub fn heartbeat(&mut self, answered: bool) -> Option<StateChange> {
+        if answered {
+            return self.activity();
+        }
+        self.missed_heartbeats += 1;
+        if self.state != ConnectionState::Open || self.missed_heartbeats < DEGRADED_AFTER_MISSED {
+            return None;
+        }
+        self.transition(ConnectionState::Degraded)
+    }
+
+    fn transition(&mut self, state: ConnectionState) -> Option<StateChange> {
+        let previous = self.state;
+        if previous == state {
+            return None;
+        }
+        self.state = state;
+        Some(StateChange {
+            previous,
+            current: state,
+        })
+    }
+}
+
+/// Delays between reconnection attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// Delay before the first attempt in milliseconds, doubled on each attempt
+    pub initial_delay_ms: u32,
+    /// Upper bound of the delay in milliseconds
+    pub max_delay_ms: u32,
+    /// Number of attempts before giving up, `None` to retry forever
+    pub max_attempts: Option<u32>,
+}
+
+impl ReconnectPolicy {
+    /// Delay before attempt number `attempt` (starting at 1), in milliseconds.
+    pub fn delay(&self, attempt: u32) -> u32 {
+        let shift = attempt.saturating_sub(1).min(16);
+        self.initial_delay_ms
+            .saturating_mul(1 << shift)
+            .min(self.max_delay_ms)
+    }
+
+    /// Whether attempt number `attempt` (starting at 1) is allowed.
+    pub fn allows(&self, attempt: u32) -> bool {
+        self.max_attempts.is_none_or(|n| attempt <= n)
+    }
+}
+
+impl Default for ReconnectPolicy {
+    /// 500 ms doubling up to 10 s, forever.
+    fn default() -> Self {
+        Self {
+            initial_delay_ms: 500,
+            max_delay_ms: 10_000,
+            max_attempts: None,
+        }
+    }
+}
+
+/// Send a cheap read to one motor and report whether it answered.
+///
+/// Reads the 1-byte Hardware Error Status register of [`HEARTBEAT_MOTOR_ID`].
+///
+/// # Errors
+/// Transport errors from the bus; silence is `Ok(false)`.
+pub async fn check_link(bus: &impl MotorBus) -> Result<bool, ReachyError> {
+    let packet = build_read_packet(HEARTBEAT_MOTOR_ID, address::HARDWARE_ERROR_STATUS, 1);
+    let response = bus.try_write_read(&packet, None).await?;
+    Ok(!response.is_empty())
+}
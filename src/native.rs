@@ -0,0 +1,229 @@
+//! Native [`MotorBus`] backends, enabled with the `native` feature.
+//!
+//! These drive the robot from a regular Linux process (e.g. a daemon on the
+//! Reachy Mini itself) instead of a browser.
+//!
+//! The backends are blocking-only: their futures never return `Pending`, and
+//! reads, writes and [`MotorBus::sleep`] block the calling thread. Run the
+//! async API with `futures::executor::block_on` or on a dedicated thread,
+//! never on an async runtime shared with other tasks, which would stall.
+//!
+//! ```ignore
+//! use reachy_mini::native::SerialBus;
+//! use reachy_mini::{dynamixel, MotorBus};
+//!
+//! let bus = SerialBus::open("/dev/ttyACM0")?;
+//! let packet = dynamixel::build_sync_current_position(&[11, 12, 13, 14, 15, 16]);
+//! let response = futures::executor::block_on(bus.write_read(&packet, None))?;
+//!
+//! futures::executor::block_on(reachy_mini::write_torque(&bus, true))?;
+//! futures::executor::block_on(reachy_mini::write_head_pose(&bus, [0.0, 0.0, 10.0, 0.0, 0.0, 0.0], None))?;
+//! ```
+
+use std::cell::Cell;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serialport::SerialPort;
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::Message;
+
+use crate::bus::{IoPolicy, MotorBus};
+use crate::error::ReachyError;
+
+/// Baud rate of the serial link to the motors
+const LINK_BAUD_RATE: u32 = 1_000_000;
+
+/// Read timeout used by [`MotorBus::read`], which loops until data arrives
+const BLOCKING_READ_MS: u32 = 1000;
+
+/// Whether an I/O error just means no data arrived in time.
+fn is_timeout(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock
+    )
+}
+
+// ============================================================================
+// Serial Port
+// ============================================================================
+
+/// Motor bus over a serial port (e.g. the Reachy Mini's USB adapter).
+///
+/// Blocking-only, see the module documentation.
+pub struct SerialBus {
+    port: Mutex<Box<dyn SerialPort>>,
+    policy: Cell<IoPolicy>,
+    start: Instant,
+}
+
+impl SerialBus {
+    /// Open a serial port at the motors' 1 Mbps link speed.
+    pub fn open(path: &str) -> Result<Self, ReachyError> {
+        Self::open_with_baud_rate(path, LINK_BAUD_RATE)
+    }
+
+    /// Open a serial port at a given baud rate.
+    pub fn open_with_baud_rate(path: &str, baud_rate: u32) -> Result<Self, ReachyError> {
+        let port = serialport::new(path, baud_rate)
+            .timeout(Duration::from_millis(BLOCKING_READ_MS as u64))
+            .open()
+            .map_err(|e| {
+                ReachyError::ConnectionFailed(format!("Could not open {}: {}", path, e))
+            })?;
+
+        Ok(Self {
+            port: Mutex::new(port),
+            policy: Cell::new(IoPolicy::default()),
+            start: Instant::now(),
+        })
+    }
+}
+
+impl MotorBus for SerialBus {
+    async fn write(&self, packet: &[u8]) -> Result<(), ReachyError> {
+        let mut port = self
+            .port
+            .lock()
+            .map_err(|e| ReachyError::Transport(format!("Lock failed: {:?}", e)))?;
+        port.write_all(packet)
+            .and_then(|_| port.flush())
+            .map_err(|e| ReachyError::Transport(format!("Write failed: {}", e)))
+    }
+
+    async fn read(&self) -> Result<Vec<u8>, ReachyError> {
+        loop {
+            if let Some(data) = self.read_timeout(BLOCKING_READ_MS).await? {
+                return Ok(data);
+            }
+        }
+    }
+
+    async fn read_timeout(&self, ms: u32) -> Result<Option<Vec<u8>>, ReachyError> {
+        let mut port = self
+            .port
+            .lock()
+            .map_err(|e| ReachyError::Transport(format!("Lock failed: {:?}", e)))?;
+        port.set_timeout(Duration::from_millis(ms.max(1) as u64))
+            .map_err(|e| ReachyError::Transport(format!("Set timeout failed: {}", e)))?;
+
+        let mut buf = [0u8; 1024];
+        match port.read(&mut buf) {
+            Ok(0) => Ok(None),
+            Ok(n) => Ok(Some(buf[..n].to_vec())),
+            Err(e) if is_timeout(&e) => Ok(None),
+            Err(e) => Err(ReachyError::Transport(format!("Read failed: {}", e))),
+        }
+    }
+
+    /// Blocks the calling thread (see the module documentation).
+    async fn sleep(&self, ms: u32) {
+        std::thread::sleep(Duration::from_millis(ms as u64));
+    }
+
+    fn now_ms(&self) -> f64 {
+        self.start.elapsed().as_secs_f64() * 1000.0
+    }
+
+    fn io_policy(&self) -> IoPolicy {
+        self.policy.get()
+    }
+
+    fn set_io_policy(&self, policy: IoPolicy) {
+        self.policy.set(policy);
+    }
+}
+
+// ============================================================================
+// WebSocket
+// ============================================================================
+
+type Socket = tungstenite::WebSocket<MaybeTlsStream<TcpStream>>;
+
+/// Motor bus over the Reachy Mini daemon's raw WebSocket
+/// (`ws://<host>:8000/api/move/ws/raw/write`).
+///
+/// Blocking-only, see the module documentation.
+pub struct WebSocketBus {
+    socket: Mutex<Socket>,
+    policy: Cell<IoPolicy>,
+    start: Instant,
+}
+
+impl WebSocketBus {
+    /// Connect to a WebSocket URL.
+    pub fn connect(url: &str) -> Result<Self, ReachyError> {
+        let (socket, _) = tungstenite::connect(url).map_err(|e| {
+            ReachyError::ConnectionFailed(format!("Could not connect to {}: {}", url, e))
+        })?;
+
+        Ok(Self {
+            socket: Mutex::new(socket),
+            policy: Cell::new(IoPolicy::default()),
+            start: Instant::now(),
+        })
+    }
+}
+
+impl MotorBus for WebSocketBus {
+    async fn write(&self, packet: &[u8]) -> Result<(), ReachyError> {
+        self.socket
+            .lock()
+            .map_err(|e| ReachyError::Transport(format!("Lock failed: {:?}", e)))?
+            .send(Message::Binary(packet.to_vec()))
+            .map_err(|e| ReachyError::Transport(format!("Send failed: {}", e)))
+    }
+
+    async fn read(&self) -> Result<Vec<u8>, ReachyError> {
+        loop {
+            if let Some(data) = self.read_timeout(BLOCKING_READ_MS).await? {
+                return Ok(data);
+            }
+        }
+    }
+
+    async fn read_timeout(&self, ms: u32) -> Result<Option<Vec<u8>>, ReachyError> {
+        let mut socket = self
+            .socket
+            .lock()
+            .map_err(|e| ReachyError::Transport(format!("Lock failed: {:?}", e)))?;
+        if let MaybeTlsStream::Plain(stream) = socket.get_mut() {
+            stream
+                .set_read_timeout(Some(Duration::from_millis(ms.max(1) as u64)))
+                .map_err(|e| ReachyError::Transport(format!("Set timeout failed: {}", e)))?;
+        }
+
+        match socket.read() {
+            Ok(Message::Binary(bytes)) => Ok(Some(bytes)),
+            // Pings are answered by tungstenite; not data, but not a timeout either
+            Ok(Message::Ping(_)) | Ok(Message::Pong(_)) => Ok(Some(Vec::new())),
+            Ok(Message::Close(_)) => Err(ReachyError::TransportClosed),
+            Ok(_) => Err(ReachyError::Transport("Unexpected message type".into())),
+            Err(tungstenite::Error::Io(e)) if is_timeout(&e) => Ok(None),
+            Err(tungstenite::Error::ConnectionClosed) | Err(tungstenite::Error::AlreadyClosed) => {
+                Err(ReachyError::TransportClosed)
+            }
+            Err(e) => Err(ReachyError::Transport(format!("Read failed: {}", e))),
+        }
+    }
+
+    /// Blocks the calling thread (see the module documentation).
+    async fn sleep(&self, ms: u32) {
+        std::thread::sleep(Duration::from_millis(ms as u64));
+    }
+
+    fn now_ms(&self) -> f64 {
+        self.start.elapsed().as_secs_f64() * 1000.0
+    }
+
+    fn io_policy(&self) -> IoPolicy {
+        self.policy.get()
+    }
+
+    fn set_io_policy(&self, policy: IoPolicy) {
+        self.policy.set(policy);
+    }
+}
@@ -0,0 +1,769 @@
+//! In-memory simulation of the Reachy Mini motor bus.
+//!
+//! [`SimulatedBus`] implements [`MotorBus`] by decoding the instruction
+//! packets written to it and answering the way motors 10-18 would, so the
+//! protocol and robot logic can be tested natively without a robot.
+//!
+//! Each simulated motor has a full XL330 control table with access checks
+//! (read-only registers, EEPROM locked while torque is on, position limits).
+//! Time is virtual: it only advances through [`MotorBus::sleep`], read
+//! timeouts and [`SimulatedBus::advance`]. Motors follow a simple model:
+//! - Position: first-order response towards Goal Position while torque is on
+//! - Load: proportional to the tracking error
+//! - Temperature: first-order towards ambient plus a load-dependent rise.
+//!   Above the Temperature Limit, the overheating bit is set and, as with the
+//!   real Shutdown register, torque is disabled.
+//!
+//! [`Faults`] make the transport unreliable (lost, corrupted, split or late
+//! status packets) and [`SimulatedBus::inject_hardware_error`] makes motors
+//! report hardware errors, to test error handling.
+//!
+//! ```ignore
+//! use reachy_mini::sim::SimulatedBus;
+//! use reachy_mini::{dynamixel, MotorBus};
+//!
+//! let bus = SimulatedBus::new();
+//! bus.write(&dynamixel::build_sync_write_torque(&[11], true)).await?;
+//! bus.write(&dynamixel::build_sync_write_position(&[11], &[3072])).await?;
+//! bus.sleep(500).await;
+//! let response = bus.write_read(&dynamixel::build_sync_current_position(&[11]), None).await?;
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::bus::{IoPolicy, MotorBus};
+use crate::dynamixel::{
+    self, address, instruction, register, Access, HardwareErrorStatus, InstructionPacket,
+    PacketFramer, ProtocolError, Register, StatusError, BROADCAST_ID, CONTROL_TABLE,
+};
+use crate::error::ReachyError;
+
+/// Motor IDs simulated by default (body 10, head 11-16, antennas 17-18)
+pub const SIM_MOTOR_IDS: [u8; 9] = [10, 11, 12, 13, 14, 15, 16, 17, 18];
+
+/// Control table size in bytes (up to Backup Ready at 147)
+const TABLE_SIZE: usize = 148;
+
+/// Model number reported by simulated motors (XL330-M288)
+const MODEL_NUMBER: i32 = 1200;
+
+/// Firmware version reported by simulated motors
+const FIRMWARE_VERSION: i32 = 46;
+
+/// Baud Rate register value for 1 Mbps
+const LINK_BAUD_RATE_CODE: u8 = 3;
+
+/// Time constant of the position response in milliseconds
+const POSITION_TAU_MS: f64 = 40.0;
+
+/// Time constant of the temperature response in milliseconds
+const TEMPERATURE_TAU_MS: f64 = 60_000.0;
+
+/// Ambient temperature in °C
+const AMBIENT_TEMPERATURE: f64 = 30.0;
+
+/// Steady-state temperature rise in °C per unit of load (0.1 %)
+const TEMPERATURE_PER_LOAD: f64 = 0.05;
+
+/// Load (0.1 % units) per tick of position error
+const LOAD_PER_TICK: f64 = 2.0;
+
+/// Largest integration step in milliseconds
+const MAX_STEP_MS: f64 = 10.0;
+
+// ============================================================================
+// Simulated Motor
+// ============================================================================
+
+/// A single simulated XL330 motor.
+#[derive(Debug, Clone)]
+struct SimMotor {
+    table: [u8; TABLE_SIZE],
+    /// Exact position in ticks (Present Position is its rounded value)
+    position: f64,
+    /// Exact temperature in °C
+    temperature: f64,
+    /// Write stored by REG_WRITE, applied by ACTION
+    registered: Option<(u16, Vec<u8>)>,
+}
+
+impl SimMotor {
+    /// A motor configured like on Reachy Mini: given ID, 1 Mbps, no return delay.
+    fn new(id: u8) -> Self {
+        let mut motor = Self {
+            table: [0; TABLE_SIZE],
+            position: 2048.0,
+            temperature: AMBIENT_TEMPERATURE,
+            registered: None,
+        };
+        motor.factory_reset(id, LINK_BAUD_RATE_CODE);
+        motor.set::<register::ReturnDelayTime>(0);
+        motor
+    }
+
+    fn get<R: Register>(&self) -> i32 {
+        let info = R::INFO;
+        let start = info.address as usize;
+        info.decode(&self.table[start..start + info.size as usize])
+    }
+
+    fn set<R: Register>(&mut self, raw: i32) {
+        let info = R::INFO;
+        let start = info.address as usize;
+        self.table[start..start + info.size as usize].copy_from_slice(&info.encode(raw));
+    }
+
+    fn id(&self) -> u8 {
+        self.get::<register::Id>() as u8
+    }
+
+    fn torque_enabled(&self) -> bool {
+        self.get::<register::TorqueEnable>() != 0
+    }
+
+    fn hardware_error(&self) -> HardwareErrorStatus {
+        HardwareErrorStatus(self.get::<register::HardwareErrorStatus>() as u8)
+    }
+
+    /// Alert bit to report in status packets.
+    fn alert(&self) -> u8 {
+        if self.hardware_error().is_ok() {
+            0
+        } else {
+            StatusError::ALERT
+        }
+    }
+
+    /// Restore factory defaults, keeping the given ID and baud rate.
+    fn factory_reset(&mut self, id: u8, baud_rate: u8) {
+        self.table = [0; TABLE_SIZE];
+        self.registered = None;
+
+        self.set::<register::ModelNumber>(MODEL_NUMBER);
+        self.set::<register::FirmwareVersion>(FIRMWARE_VERSION);
+        self.set::<register::Id>(id as i32);
+        self.set::<register::BaudRate>(baud_rate as i32);
+        self.set::<register::ReturnDelayTime>(250);
+        self.set::<register::OperatingMode>(3);
+        self.set::<register::ProtocolType>(2);
+        self.set::<register::MovingThreshold>(10);
+        self.set::<register::TemperatureLimit>(70);
+        self.set::<register::MaxVoltageLimit>(70);
+        self.set::<register::MinVoltageLimit>(35);
+        self.set::<register::PwmLimit>(885);
+        self.set::<register::CurrentLimit>(1750);
+        self.set::<register::VelocityLimit>(445);
+        self.set::<register::MaxPositionLimit>(4095);
+        self.set::<register::MinPositionLimit>(0);
+        self.set::<register::Shutdown>(
+            (HardwareErrorStatus::OVERHEATING
+                | HardwareErrorStatus::ELECTRICAL_SHOCK
+                | HardwareErrorStatus::OVERLOAD) as i32,
+        );
+        self.reset_ram();
+    }
+
+    /// Reset the RAM area to its power-on values, as after a reboot.
+    fn reset_ram(&mut self) {
+        let ram = address::TORQUE_ENABLE as usize;
+        self.table[ram..].fill(0);
+        self.registered = None;
+
+        self.set::<register::StatusReturnLevel>(2);
+        self.set::<register::VelocityPGain>(180);
+        self.set::<register::VelocityIGain>(1600);
+        self.set::<register::PositionPGain>(400);
+        self.set::<register::PresentInputVoltage>(50);
+        self.set::<register::GoalPosition>(self.position.round() as i32);
+        self.update_present(0.0, 0.0);
+    }
+
+    /// Reboot: reload EEPROM, reset RAM and clear hardware errors.
+    fn reboot(&mut self) {
+        self.reset_ram();
+    }
+
+    /// Check that `len` bytes at `addr` exist in the control table.
+    fn check_range(addr: u16, len: usize) -> Result<(), ProtocolError> {
+        if len == 0 {
+            return Err(ProtocolError::DataLength);
+        }
+        if addr as usize + len > TABLE_SIZE {
+            return Err(ProtocolError::Access);
+        }
+        Ok(())
+    }
+
+    fn read(&self, addr: u16, len: usize) -> Result<Vec<u8>, ProtocolError> {
+        Self::check_range(addr, len)?;
+        let start = addr as usize;
+        Ok(self.table[start..start + len].to_vec())
+    }
+
+    /// Check a write without applying it.
+    fn check_write(&self, addr: u16, data: &[u8]) -> Result<(), ProtocolError> {
+        Self::check_range(addr, data.len())?;
+        let end = addr + data.len() as u16;
+
+        for reg in CONTROL_TABLE {
+            if reg.address + reg.size <= addr || reg.address >= end {
+                continue;
+            }
+            if reg.access == Access::ReadOnly {
+                return Err(ProtocolError::Access);
+            }
+            if reg.is_eeprom() && self.torque_enabled() {
+                return Err(ProtocolError::Access);
+            }
+        }
+
+        let goal = <register::GoalPosition as Register>::INFO;
+        if addr <= goal.address && goal.address + goal.size <= end {
+            let offset = (goal.address - addr) as usize;
+            let value = goal.decode(&data[offset..offset + goal.size as usize]);
+            let min = self.get::<register::MinPositionLimit>();
+            let max = self.get::<register::MaxPositionLimit>();
+            if value < min || value > max {
+                return Err(ProtocolError::DataLimit);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write(&mut self, addr: u16, data: &[u8]) -> Result<(), ProtocolError> {
+        self.check_write(addr, data)?;
+        let start = addr as usize;
+        self.table[start..start + data.len()].copy_from_slice(data);
+        Ok(())
+    }
+
+    /// Advance the physical model by `dt` milliseconds, `now` being the
+    /// simulation time at the end of the step.
+    fn step(&mut self, dt: f64, now: f64) {
+        let previous = self.position;
+        let goal = self.get::<register::GoalPosition>() as f64;
+
+        let load = if self.torque_enabled() {
+            self.position += (goal - self.position) * (1.0 - (-dt / POSITION_TAU_MS).exp());
+            (LOAD_PER_TICK * (goal - self.position)).clamp(-1000.0, 1000.0)
+        } else {
+            0.0
+        };
+
+        let target = AMBIENT_TEMPERATURE + TEMPERATURE_PER_LOAD * load.abs();
+        self.temperature += (target - self.temperature) * (1.0 - (-dt / TEMPERATURE_TAU_MS).exp());
+
+        let mut error = self.hardware_error().0;
+        if self.temperature > self.get::<register::TemperatureLimit>() as f64 {
+            error |= HardwareErrorStatus::OVERHEATING;
+        }
+        self.set::<register::HardwareErrorStatus>(error as i32);
+        if error & self.get::<register::Shutdown>() as u8 != 0 {
+            self.set::<register::TorqueEnable>(0);
+        }
+
+        let velocity = if dt > 0.0 {
+            (self.position - previous) / dt
+        } else {
+            0.0
+        };
+        self.set::<register::PresentLoad>(load.round() as i32);
+        self.set::<register::RealtimeTick>((now as i64 % 32768) as i32);
+        self.update_present(velocity, goal);
+    }
+
+    /// Refresh the present-value registers from the model state.
+    ///
+    /// `velocity` is in ticks per millisecond.
+    fn update_present(&mut self, velocity: f64, goal: f64) {
+        let rpm = velocity * 60_000.0 / 4096.0;
+        let threshold = self.get::<register::MovingThreshold>() as f64;
+        let moving = self.torque_enabled() && (goal - self.position).abs() > threshold;
+
+        self.set::<register::PresentPosition>(self.position.round() as i32);
+        self.set::<register::PositionTrajectory>(self.position.round() as i32);
+        self.set::<register::PresentVelocity>((rpm / 0.229).round() as i32);
+        self.set::<register::Moving>(moving as i32);
+        self.set::<register::PresentTemperature>(self.temperature.round() as i32);
+    }
+}
+
+// ============================================================================
+// Fault Injection
+// ============================================================================
+
+/// How queued status packets are split into the chunks returned by reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Chunking {
+    /// Status packets arriving at the same time are concatenated in one
+    /// chunk, as USB serial adapters usually deliver them
+    #[default]
+    Joined,
+    /// One chunk per status packet
+    PerPacket,
+    /// Chunks of at most this many bytes, splitting packets across reads
+    Bytes(usize),
+}
+
+/// Transport faults applied by a [`SimulatedBus`] to the status packets it
+/// returns, to test how the robot logic copes with an unreliable bus.
+///
+/// Replies delayed past a read timeout stay queued and show up in later
+/// exchanges, like late bytes on a real bus.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Faults {
+    /// Motors whose status packets are lost
+    pub dropped_ids: Vec<u8>,
+    /// Motors whose status packets arrive with a corrupted CRC
+    pub corrupted_ids: Vec<u8>,
+    /// How status packets are split into chunks
+    pub chunking: Chunking,
+    /// Delay before every status packet arrives, in milliseconds
+    pub delay_ms: f64,
+    /// Additional delay per motor ID, in milliseconds
+    pub slow_ids: Vec<(u8, f64)>,
+}
+
+impl Faults {
+    /// Additional delay for status packets from motor `id`.
+    fn extra_delay(&self, id: u8) -> f64 {
+        self.slow_ids
+            .iter()
+            .filter(|(slow_id, _)| *slow_id == id)
+            .map(|(_, ms)| ms)
+            .sum()
+    }
+}
+
+// ============================================================================
+// Simulated Bus
+// ============================================================================
+
+/// In-memory motor bus simulating Reachy Mini's motors.
+///
+/// Writes are executed immediately and their status packets queued for the
+/// next reads, after applying the configured [`Faults`]. Packets with a bad
+/// CRC are ignored, like on real motors.
+pub struct SimulatedBus {
+    motors: RefCell<BTreeMap<u8, SimMotor>>,
+    /// Chunks waiting to be read, with the virtual time they become readable
+    pending: RefCell<VecDeque<(f64, Vec<u8>)>>,
+    faults: RefCell<Faults>,
+    /// Virtual time in milliseconds
+    clock: Cell<f64>,
+    /// Time up to which motors have been simulated
+    stepped: Cell<f64>,
+    policy: Cell<IoPolicy>,
+}
+
+impl Default for SimulatedBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulatedBus {
+    /// Simulate the 9 Reachy Mini motors (IDs 10-18), torque off at center.
+    pub fn new() -> Self {
+        Self::with_motors(&SIM_MOTOR_IDS)
+    }
+
+    /// Simulate motors with the given IDs.
+    pub fn with_motors(ids: &[u8]) -> Self {
+        Self {
+            motors: RefCell::new(ids.iter().map(|&id| (id, SimMotor::new(id))).collect()),
+            pending: RefCell::new(VecDeque::new()),
+            faults: RefCell::new(Faults::default()),
+            clock: Cell::new(0.0),
+            stepped: Cell::new(0.0),
+            policy: Cell::new(IoPolicy::default()),
+        }
+    }
+
+    /// IDs of the motors on the bus.
+    pub fn motor_ids(&self) -> Vec<u8> {
+        self.motors.borrow().keys().copied().collect()
+    }
+
+    /// Connect a new motor with factory settings except ID and 1 Mbps link.
+    pub fn add_motor(&self, id: u8) {
+        self.motors.borrow_mut().insert(id, SimMotor::new(id));
+    }
+
+    /// Disconnect a motor. Returns `false` if there was none with this ID.
+    pub fn remove_motor(&self, id: u8) -> bool {
+        self.motors.borrow_mut().remove(&id).is_some()
+    }
+
+    /// Advance virtual time by `ms` milliseconds, stepping every motor.
+    pub fn advance(&self, ms: f64) {
+        self.clock.set(self.clock.get() + ms);
+        self.catch_up();
+    }
+
+    /// Read a register of a motor, bypassing the bus.
+    pub fn register<R: Register>(&self, id: u8) -> Option<i32> {
+        self.motors.borrow().get(&id).map(|m| m.get::<R>())
+    }
+
+    /// Set a register of a motor, bypassing the bus and its access checks.
+    ///
+    /// Present values are overwritten by the model on the next step; use
+    /// [`set_position`](Self::set_position) or
+    /// [`set_temperature`](Self::set_temperature) for those.
+    pub fn set_register<R: Register>(&self, id: u8, raw: i32) -> bool {
+        self.with_motor(id, |m| m.set::<R>(raw))
+    }
+
+    /// Move a motor's shaft, as if turned by hand.
+    pub fn set_position(&self, id: u8, raw: i32) -> bool {
+        self.with_motor(id, |m| {
+            m.position = raw as f64;
+            m.update_present(0.0, m.get::<register::GoalPosition>() as f64);
+        })
+    }
+
+    /// Set a motor's temperature in °C.
+    pub fn set_temperature(&self, id: u8, celsius: f64) -> bool {
+        self.with_motor(id, |m| {
+            m.temperature = celsius;
+            m.update_present(0.0, m.get::<register::GoalPosition>() as f64);
+        })
+    }
+
+    /// Set hardware error bits on a motor, as if it had detected a fault.
+    ///
+    /// Bits in the motor's Shutdown mask disable torque on the next step.
+    /// A reboot clears them.
+    pub fn inject_hardware_error(&self, id: u8, bits: u8) -> bool {
+        self.with_motor(id, |m| {
+            let error = m.hardware_error().0 | bits;
+            m.set::<register::HardwareErrorStatus>(error as i32);
+        })
+    }
+
+    /// Current transport faults.
+    pub fn faults(&self) -> Faults {
+        self.faults.borrow().clone()
+    }
+
+    /// Replace the transport faults applied to status packets read through
+    /// [`MotorBus`]. [`handle`](Self::handle) is not affected.
+    pub fn set_faults(&self, faults: Faults) {
+        *self.faults.borrow_mut() = faults;
+    }
+
+    fn with_motor(&self, id: u8, f: impl FnOnce(&mut SimMotor)) -> bool {
+        self.catch_up();
+        match self.motors.borrow_mut().get_mut(&id) {
+            Some(motor) => {
+                f(motor);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Step every motor up to the current virtual time.
+    fn catch_up(&self) {
+        let now = self.clock.get();
+        let mut t = self.stepped.get();
+        let mut motors = self.motors.borrow_mut();
+        while t < now {
+            let dt = (now - t).min(MAX_STEP_MS);
+            t += dt;
+            for motor in motors.values_mut() {
+                motor.step(dt, t);
+            }
+        }
+        self.stepped.set(now);
+    }
+
+    /// Execute the instruction packets in `data` and return the motors'
+    /// status packets, concatenated.
+    pub fn handle(&self, data: &[u8]) -> Vec<u8> {
+        self.catch_up();
+
+        let mut response = Vec::new();
+        for packet in dynamixel::parse_instruction_packets(data) {
+            response.extend(self.execute(&packet));
+        }
+
+        // Motors whose ID was written answer to their new ID from now on
+        let mut motors = self.motors.borrow_mut();
+        let moved: Vec<u8> = motors
+            .iter()
+            .filter(|(&id, motor)| motor.id() != id)
+            .map(|(&id, _)| id)
+            .collect();
+        for id in moved {
+            if let Some(motor) = motors.remove(&id) {
+                motors.insert(motor.id(), motor);
+            }
+        }
+
+        response
+    }
+
+    /// Execute one instruction packet, returning the status packets to send.
+    fn execute(&self, packet: &InstructionPacket) -> Vec<u8> {
+        let mut motors = self.motors.borrow_mut();
+        let params = &packet.params;
+        let broadcast = packet.id == BROADCAST_ID;
+        let mut response = Vec::new();
+
+        let status = |motor: &SimMotor, result: Result<Vec<u8>, ProtocolError>| match result {
+            Ok(data) => dynamixel::build_status_packet(motor.id(), motor.alert(), &data),
+            Err(e) => dynamixel::build_status_packet(motor.id(), motor.alert() | e as u8, &[]),
+        };
+        let u16_at = |i: usize| u16::from_le_bytes([params[i], params[i + 1]]);
+
+        match packet.instruction {
+            instruction::PING => {
+                for motor in motors.values().filter(|m| broadcast || m.id() == packet.id) {
+                    let mut data = (motor.get::<register::ModelNumber>() as u16)
+                        .to_le_bytes()
+                        .to_vec();
+                    data.push(motor.get::<register::FirmwareVersion>() as u8);
+                    response.extend(status(motor, Ok(data)));
+                }
+            }
+            instruction::READ if params.len() == 4 && !broadcast => {
+                if let Some(motor) = motors.get(&packet.id) {
+                    response.extend(status(motor, motor.read(u16_at(0), u16_at(2) as usize)));
+                }
+            }
+            instruction::WRITE | instruction::REG_WRITE if params.len() > 2 => {
+                let (addr, data) = (u16_at(0), &params[2..]);
+                for motor in motors
+                    .values_mut()
+                    .filter(|m| broadcast || m.id() == packet.id)
+                {
+                    // Answer from the addressed ID, even if the write changes it
+                    let id = motor.id();
+                    let result = if packet.instruction == instruction::WRITE {
+                        motor.write(addr, data)
+                    } else {
+                        motor.check_write(addr, data).map(|()| {
+                            motor.registered = Some((addr, data.to_vec()));
+                        })
+                    };
+                    if !broadcast {
+                        let error = motor.alert() | result.err().map_or(0, |e| e as u8);
+                        response.extend(dynamixel::build_status_packet(id, error, &[]));
+                    }
+                }
+            }
+            instruction::ACTION => {
+                for motor in motors
+                    .values_mut()
+                    .filter(|m| broadcast || m.id() == packet.id)
+                {
+                    let result = match motor.registered.take() {
+                        Some((addr, data)) => motor.write(addr, &data),
+                        None => Err(ProtocolError::Instruction),
+                    };
+                    if !broadcast {
+                        response.extend(status(motor, result.map(|()| Vec::new())));
+                    }
+                }
+            }
+            instruction::REBOOT => {
+                for motor in motors
+                    .values_mut()
+                    .filter(|m| broadcast || m.id() == packet.id)
+                {
+                    if !broadcast {
+                        response.extend(status(motor, Ok(Vec::new())));
+                    }
+                    motor.reboot();
+                }
+            }
+            instruction::FACTORY_RESET if params.len() == 1 => {
+                // Broadcast full resets are ignored, since every motor would end up at ID 1
+                if broadcast && params[0] == 0xFF {
+                    return response;
+                }
+                for motor in motors
+                    .values_mut()
+                    .filter(|m| broadcast || m.id() == packet.id)
+                {
+                    if !broadcast {
+                        response.extend(status(motor, Ok(Vec::new())));
+                    }
+                    let (id, baud_rate) = match params[0] {
+                        0x01 => (motor.id(), 1),
+                        0x02 => (motor.id(), motor.get::<register::BaudRate>() as u8),
+                        _ => (1, 1),
+                    };
+                    motor.factory_reset(id, baud_rate);
+                }
+            }
+            instruction::SYNC_READ if params.len() >= 4 => {
+                let (addr, len) = (u16_at(0), u16_at(2) as usize);
+                for id in &params[4..] {
+                    if let Some(motor) = motors.get(id) {
+                        response.extend(status(motor, motor.read(addr, len)));
+                    }
+                }
+            }
+            instruction::FAST_SYNC_READ if params.len() >= 4 => {
+                let (addr, len) = (u16_at(0), u16_at(2) as usize);
+                // Each motor appends to the previous one's answer, so the
+                // chain stops at the first motor that doesn't answer
+                let segments: Vec<(u8, u8, Vec<u8>)> = params[4..]
+                    .iter()
+                    .map_while(|id| motors.get(id))
+                    .map(|motor| match motor.read(addr, len) {
+                        Ok(data) => (motor.id(), motor.alert(), data),
+                        Err(e) => (motor.id(), motor.alert() | e as u8, vec![0; len]),
+                    })
+                    .collect();
+                if !segments.is_empty() {
+                    let segments: Vec<(u8, u8, &[u8])> = segments
+                        .iter()
+                        .map(|(id, error, data)| (*id, *error, data.as_slice()))
+                        .collect();
+                    response.extend(dynamixel::build_fast_sync_status_packet(&segments));
+                }
+            }
+            instruction::SYNC_WRITE if params.len() >= 4 => {
+                let (addr, len) = (u16_at(0), u16_at(2) as usize);
+                for chunk in params[4..].chunks_exact(1 + len) {
+                    if let Some(motor) = motors.get_mut(&chunk[0]) {
+                        let _ = motor.write(addr, &chunk[1..]);
+                    }
+                }
+            }
+            instruction::BULK_READ => {
+                for entry in params.chunks_exact(5) {
+                    if let Some(motor) = motors.get(&entry[0]) {
+                        let addr = u16::from_le_bytes([entry[1], entry[2]]);
+                        let len = u16::from_le_bytes([entry[3], entry[4]]) as usize;
+                        response.extend(status(motor, motor.read(addr, len)));
+                    }
+                }
+            }
+            instruction::BULK_WRITE => {
+                let mut rest = &params[..];
+                while rest.len() >= 5 {
+                    let addr = u16::from_le_bytes([rest[1], rest[2]]);
+                    let len = u16::from_le_bytes([rest[3], rest[4]]) as usize;
+                    let Some(data) = rest.get(5..5 + len) else {
+                        break;
+                    };
+                    if let Some(motor) = motors.get_mut(&rest[0]) {
+                        let _ = motor.write(addr, data);
+                    }
+                    rest = &rest[5 + len..];
+                }
+            }
+            _ if !broadcast => {
+                if let Some(motor) = motors.get(&packet.id) {
+                    response.extend(status(motor, Err(ProtocolError::Instruction)));
+                }
+            }
+            _ => {}
+        }
+
+        response
+    }
+
+    /// Queue a response for reading, applying the configured faults.
+    fn deliver(&self, response: &[u8]) {
+        let faults = self.faults.borrow();
+        let now = self.clock.get();
+        let mut pending = self.pending.borrow_mut();
+
+        for mut status in PacketFramer::new().push(response) {
+            let id = status[4];
+            if faults.dropped_ids.contains(&id) {
+                continue;
+            }
+            if faults.corrupted_ids.contains(&id) {
+                if let Some(crc) = status.last_mut() {
+                    *crc ^= 0xFF;
+                }
+            }
+
+            let ready = now + faults.delay_ms + faults.extra_delay(id);
+            // Keep the queue ordered by arrival time
+            let index = pending.partition_point(|(t, _)| *t <= ready);
+            match index.checked_sub(1).and_then(|i| pending.get_mut(i)) {
+                Some((t, chunk)) if *t == ready && faults.chunking != Chunking::PerPacket => {
+                    chunk.extend(status)
+                }
+                _ => pending.insert(index, (ready, status)),
+            }
+        }
+
+        if let Chunking::Bytes(size) = faults.chunking {
+            let size = size.max(1);
+            *pending = pending
+                .drain(..)
+                .flat_map(|(t, chunk)| {
+                    chunk
+                        .chunks(size)
+                        .map(|piece| (t, piece.to_vec()))
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+        }
+    }
+
+    /// Pop the next chunk if it arrives within `ms` milliseconds, advancing
+    /// virtual time to its arrival.
+    fn next_chunk(&self, ms: f64) -> Option<Vec<u8>> {
+        let now = self.clock.get();
+        let ready = self.pending.borrow().front().map(|(t, _)| *t)?;
+        if ready > now + ms {
+            return None;
+        }
+        if ready > now {
+            self.advance(ready - now);
+        }
+        self.pending
+            .borrow_mut()
+            .pop_front()
+            .map(|(_, chunk)| chunk)
+    }
+}
+
+impl MotorBus for SimulatedBus {
+    async fn write(&self, packet: &[u8]) -> Result<(), ReachyError> {
+        let response = self.handle(packet);
+        self.deliver(&response);
+        Ok(())
+    }
+
+    async fn read(&self) -> Result<Vec<u8>, ReachyError> {
+        self.next_chunk(f64::INFINITY)
+            .ok_or_else(|| ReachyError::Transport("No data pending on the simulated bus".into()))
+    }
+
+    async fn read_timeout(&self, ms: u32) -> Result<Option<Vec<u8>>, ReachyError> {
+        let chunk = self.next_chunk(ms as f64);
+        if chunk.is_none() {
+            self.advance(ms as f64);
+        }
+        Ok(chunk)
+    }
+
+    async fn sleep(&self, ms: u32) {
+        self.advance(ms as f64);
+    }
+
+    fn now_ms(&self) -> f64 {
+        self.clock.get()
+    }
+
+    fn io_policy(&self) -> IoPolicy {
+        self.policy.get()
+    }
+
+    fn set_io_policy(&self, policy: IoPolicy) {
+        self.policy.set(policy);
+    }
+}
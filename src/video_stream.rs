@@ -15,6 +15,7 @@ use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{console, HtmlCanvasElement, HtmlVideoElement, MediaStream, MediaStreamConstraints};
 
+use crate::error::ReachyError;
 use crate::sleep;
 
 /// Default WebSocket host
@@ -59,14 +60,14 @@ impl WebSocketStream {
         console::log_1(&format!("Connecting to video stream: {}", url).into());
 
         let ws = WebSocket::open(&url)
-            .map_err(|e| JsValue::from_str(&format!("Video WebSocket open failed: {:?}", e)))?;
+            .map_err(|e| ReachyError::ConnectionFailed(format!("Video WebSocket open failed: {:?}", e)))?;
 
         // Wait for connection
         loop {
             match ws.state() {
                 gloo::net::websocket::State::Connecting => sleep(10).await?,
                 gloo::net::websocket::State::Open => break,
-                _ => return Err(JsValue::from_str("Video WebSocket connection failed")),
+                _ => return Err(ReachyError::ConnectionFailed("Video WebSocket connection failed".into()).into()),
             }
         }
 
@@ -90,12 +91,12 @@ impl CameraStream {
             "WebSocket failed, falling back to browser camera...",
         ));
 
-        let window = web_sys::window().ok_or("No window")?;
-        let document = window.document().ok_or("No document")?;
+        let window = web_sys::window().ok_or_else(|| ReachyError::Media("No window".into()))?;
+        let document = window.document().ok_or_else(|| ReachyError::Media("No document".into()))?;
         let navigator = window.navigator();
         let media_devices = navigator
             .media_devices()
-            .map_err(|_| "No media devices")?;
+            .map_err(|_| ReachyError::Media("No media devices".into()))?;
 
         // Request camera access
         let constraints = MediaStreamConstraints::new();
@@ -104,7 +105,7 @@ impl CameraStream {
 
         let promise = media_devices
             .get_user_media_with_constraints(&constraints)
-            .map_err(|e| JsValue::from_str(&format!("getUserMedia failed: {:?}", e)))?;
+            .map_err(|e| ReachyError::Media(format!("getUserMedia failed: {:?}", e)))?;
 
         let media_stream: MediaStream = JsFuture::from(promise).await?.dyn_into()?;
 
@@ -112,7 +113,7 @@ impl CameraStream {
         let video_element: HtmlVideoElement = document
             .create_element("video")?
             .dyn_into()
-            .map_err(|_| "Failed to create video element")?;
+            .map_err(|_| ReachyError::Media("Failed to create video element".into()))?;
 
         video_element.set_autoplay(true);
         video_element.set_muted(true);
@@ -123,7 +124,7 @@ impl CameraStream {
         // Append to document body (required for some browsers)
         document
             .body()
-            .ok_or("No body")?
+            .ok_or_else(|| ReachyError::Media("No body".into()))?
             .append_child(&video_element)?;
 
         // Wait for video to be ready
@@ -139,7 +140,7 @@ impl CameraStream {
 
         // Start playing
         let play_promise = video_element.play().map_err(|e| {
-            JsValue::from_str(&format!("Video play failed: {:?}", e))
+            ReachyError::Media(format!("Video play failed: {:?}", e))
         })?;
         JsFuture::from(play_promise).await?;
 
@@ -147,7 +148,7 @@ impl CameraStream {
         let canvas: HtmlCanvasElement = document
             .create_element("canvas")?
             .dyn_into()
-            .map_err(|_| "Failed to create canvas")?;
+            .map_err(|_| ReachyError::Media("Failed to create canvas".into()))?;
 
         let width = video_element.video_width();
         let height = video_element.video_height();
@@ -157,9 +158,9 @@ impl CameraStream {
 
         let context: web_sys::CanvasRenderingContext2d = canvas
             .get_context("2d")?
-            .ok_or("No 2d context")?
+            .ok_or_else(|| ReachyError::Media("No 2d context".into()))?
             .dyn_into()
-            .map_err(|_| "Failed to get 2d context")?;
+            .map_err(|_| ReachyError::Media("Failed to get 2d context".into()))?;
 
         console::log_1(
             &format!(
@@ -310,9 +311,7 @@ pub async fn read_video_frame() -> Result<Option<Vec<u8>>, JsValue> {
     });
 
     match source_type {
-        None => Err(JsValue::from_str(
-            "Not connected to video stream. Call connect_video_stream() first.",
-        )),
+        None => Err(ReachyError::StreamNotConnected("video").into()),
         Some("websocket") => {
             let receiver = VIDEO_STREAM
                 .with_borrow(|s| {
@@ -322,11 +321,11 @@ pub async fn read_video_frame() -> Result<Option<Vec<u8>>, JsValue> {
                         None
                     }
                 })
-                .ok_or_else(|| JsValue::from_str("WebSocket stream not available"))?;
+                .ok_or_else(|| ReachyError::InvalidState("WebSocket stream not available".into()))?;
 
             let mut rx = receiver
                 .try_lock()
-                .map_err(|e| JsValue::from_str(&format!("Lock failed: {:?}", e)))?;
+                .map_err(|e| ReachyError::Transport(format!("Lock failed: {:?}", e)))?;
 
             match rx.try_next().await {
                 Ok(Some(Message::Bytes(bytes))) => {
@@ -341,8 +340,8 @@ pub async fn read_video_frame() -> Result<Option<Vec<u8>>, JsValue> {
                     Ok(Some(bytes))
                 }
                 Ok(Some(_)) => Ok(None),
-                Ok(None) => Err(JsValue::from_str("Video stream closed")),
-                Err(e) => Err(JsValue::from_str(&format!("Read error: {:?}", e))),
+                Ok(None) => Err(ReachyError::TransportClosed.into()),
+                Err(e) => Err(ReachyError::Transport(format!("Read error: {:?}", e)).into()),
             }
         }
         Some("camera") => {
@@ -358,7 +357,7 @@ pub async fn read_video_frame() -> Result<Option<Vec<u8>>, JsValue> {
                 }
             })
         }
-        Some(_) => Err(JsValue::from_str("Unknown video source")),
+        Some(_) => Err(ReachyError::InvalidState("Unknown video source".into()).into()),
     }
 }
 
@@ -371,7 +370,7 @@ pub async fn capture_camera_frame() -> Result<Option<Vec<u8>>, JsValue> {
     });
 
     if !is_camera {
-        return Err(JsValue::from_str("Not using camera fallback"));
+        return Err(ReachyError::InvalidState("Not using camera fallback".into()).into());
     }
 
     // We need to get the camera stream and capture
@@ -387,7 +386,7 @@ pub async fn capture_camera_frame() -> Result<Option<Vec<u8>>, JsValue> {
         } else {
             None
         }
-    }).ok_or_else(|| JsValue::from_str("Camera not available"))?;
+    }).ok_or_else(|| ReachyError::Media("Camera not available".into()))?;
 
     // Update canvas size if needed
     let width = video_element.video_width();
@@ -405,7 +404,7 @@ pub async fn capture_camera_frame() -> Result<Option<Vec<u8>>, JsValue> {
     // Draw video frame to canvas
     context
         .draw_image_with_html_video_element(&video_element, 0.0, 0.0)
-        .map_err(|e| JsValue::from_str(&format!("Draw failed: {:?}", e)))?;
+        .map_err(|e| ReachyError::Media(format!("Draw failed: {:?}", e)))?;
 
     // Convert to JPEG blob
     let (tx, rx) = futures_channel::oneshot::channel();
@@ -423,13 +422,13 @@ pub async fn capture_camera_frame() -> Result<Option<Vec<u8>>, JsValue> {
             "image/jpeg",
             &JsValue::from_f64(0.8),
         )
-        .map_err(|e| JsValue::from_str(&format!("toBlob failed: {:?}", e)))?;
+        .map_err(|e| ReachyError::Media(format!("toBlob failed: {:?}", e)))?;
 
     closure.forget();
 
     let blob_js = rx
         .await
-        .map_err(|_| JsValue::from_str("Blob channel closed"))?;
+        .map_err(|_| ReachyError::Media("Blob channel closed".into()))?;
 
     if blob_js.is_null() || blob_js.is_undefined() {
         return Ok(None);
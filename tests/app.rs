@@ -19,13 +19,42 @@ fn web_test() {
 // ============================================================================
 
 use reachy_mini::dynamixel::{
-    address, build_read_packet, build_reboot_packet, build_sync_current_position,
+    address, baud_rate_bps, baud_rate_code, broadcast_ping_window_ms, build_action_packet, build_bulk_read, build_bulk_write,
+    build_factory_reset_packet, build_fast_sync_read, build_fast_sync_status_packet,
+    build_ping_packet, build_read_packet, build_reboot_packet, build_reg_write,
+    build_status_packet, build_sync_current_position, build_sync_read,
     build_sync_read_hardware_error, build_sync_read_load, build_sync_read_temperature,
-    build_sync_write_position, build_sync_write_position_radians, build_sync_write_torque,
+    build_sync_write, build_sync_write_position, build_sync_write_position_radians,
+    build_sync_write_torque, build_write, build_write_info, build_write_packet, crc16,
+    decode_instruction_packet, describe_packets, expected_responders, expected_responses, missing_ids, model_name,
     parse_1byte_packets, parse_1byte_packets_with_errors, parse_2byte_signed_packets,
-    parse_position_packets, parse_status_packet, radians_to_raw, raw_to_radians, BROADCAST_ID,
+    parse_bulk_read_response, parse_fast_sync_position, parse_fast_sync_position_with_status,
+    parse_fast_sync_read, parse_instruction_packets, parse_ping_responses, parse_position_packets,
+    parse_position_packets_with_status, parse_register_packets, parse_status_packet,
+    parse_status_packet_1byte, parse_status_packets, parse_write_status, radians_to_raw,
+    raw_to_radians, register, register_at, rejected_packet_count, values_by_id, Access,
+    BulkReadEntry, BulkWriteEntry, EepromConfig, FactoryResetMode, HardwareErrorStatus,
+    PacketFramer, ParseError, PingResponse, ProtocolError, Register, StatusError, BROADCAST_ID,
+    CONTROL_TABLE, MAX_ID,
 };
+use reachy_mini::capture::{Capture, Direction, ReplayBus, TapBus};
+use reachy_mini::error::ReachyError;
 use reachy_mini::kinematics::Kinematics;
+use reachy_mini::limits::{LimitPolicy, Limits, DEFAULT_LIMITS_JSON};
+use reachy_mini::link::{
+    check_link, ConnectionState, LinkMonitor, ReconnectPolicy, StateChange, HEARTBEAT_MOTOR_ID,
+};
+use reachy_mini::sim::{Chunking, Faults, SimulatedBus, SIM_MOTOR_IDS};
+use reachy_mini::{
+    check_and_reboot, provision_motor, read_head_pose, read_motor_positions,
+    read_motor_positions_checked, replay_frames, set_limits, stage_head_joints_on, trigger_staged_writes_on, write_all_joints, write_antennas, write_body_yaw, write_head_pose, write_torque,
+    Arbiter, IoPolicy, MotorBus, Priority,
+};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::future::{poll_fn, Future};
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
 use nalgebra::{Matrix4, Rotation3, Vector3};
 
 // Motor configuration (same as src/motors.json)
@@ -77,7 +106,7 @@ fn create_test_kinematics() -> Kinematics {
 
 /// Compute joint angles for a given Cartesian pose using inverse kinematics
 fn compute_joint_angles(x_mm: f32, y_mm: f32, z_mm: f32, roll_deg: f32, pitch_deg: f32, yaw_deg: f32) -> Vec<f32> {
-    let mut kinematics = create_test_kinematics();
+    let kinematics = create_test_kinematics();
 
     // Build transformation matrix
     let rotation = Rotation3::from_euler_angles(
@@ -93,7 +122,7 @@ fn compute_joint_angles(x_mm: f32, y_mm: f32, z_mm: f32, roll_deg: f32, pitch_de
     t[(2, 3)] = (z_mm / 1000.0) + HEAD_Z_OFFSET_M;
 
     // Compute inverse kinematics
-    kinematics.inverse_kinematics(t, None)
+    kinematics.inverse_kinematics(t, None).unwrap()
 }
 
 /// Verify packet header structure (FF FF FD 00)
@@ -410,7 +439,7 @@ fn test_parse_position_packets_single() {
         0x55,                   // STATUS instruction
         0x00,                   // Error (no error)
         0x00, 0x08, 0x00, 0x00, // Position = 2048 (little-endian)
-        0x00, 0x00,             // CRC (placeholder)
+        0xDC, 0x07,             // CRC
     ];
 
     let results = parse_position_packets(&packet);
@@ -425,9 +454,9 @@ fn test_parse_position_packets_multiple() {
     // Two position status packets concatenated
     let packet = vec![
         // First packet - Motor 11, Position 2048
-        0xFF, 0xFF, 0xFD, 0x00, 11, 0x08, 0x00, 0x55, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00,
+        0xFF, 0xFF, 0xFD, 0x00, 11, 0x08, 0x00, 0x55, 0x00, 0x00, 0x08, 0x00, 0x00, 0xDC, 0x07,
         // Second packet - Motor 12, Position 3000 (0x0BB8)
-        0xFF, 0xFF, 0xFD, 0x00, 12, 0x08, 0x00, 0x55, 0x00, 0xB8, 0x0B, 0x00, 0x00, 0x00, 0x00,
+        0xFF, 0xFF, 0xFD, 0x00, 12, 0x08, 0x00, 0x55, 0x00, 0xB8, 0x0B, 0x00, 0x00, 0xF5, 0xF4,
     ];
 
     let results = parse_position_packets(&packet);
@@ -450,7 +479,7 @@ fn test_parse_position_packets_with_garbage() {
         0x55,                   // STATUS
         0x00,                   // Error
         0x00, 0x10, 0x00, 0x00, // Position = 4096
-        0x00, 0x00,             // CRC
+        0x7C, 0x13,             // CRC
     ];
 
     let results = parse_position_packets(&packet);
@@ -471,7 +500,7 @@ fn test_parse_1byte_packets() {
         0x55,                   // STATUS
         0x00,                   // Error (no error)
         42,                     // Temperature = 42°C
-        0x00, 0x00,             // CRC
+        0xA3, 0x89,             // CRC
     ];
 
     let results = parse_1byte_packets(&packet);
@@ -486,7 +515,7 @@ fn test_parse_1byte_packets_with_error_skipped() {
     // Packet with motor error - should be skipped by parse_1byte_packets
     let packet = vec![
         0xFF, 0xFF, 0xFD, 0x00, 15, 0x05, 0x00, 0x55, 0x04, // Error = 0x04 (overheating)
-        50, 0x00, 0x00,
+        50, 0xF3, 0xE9,
     ];
 
     let results = parse_1byte_packets(&packet);
@@ -498,7 +527,7 @@ fn test_parse_1byte_packets_with_errors_included() {
     // Same packet but using parse_1byte_packets_with_errors
     let packet = vec![
         0xFF, 0xFF, 0xFD, 0x00, 15, 0x05, 0x00, 0x55, 0x04, // Error = 0x04
-        50, 0x00, 0x00,
+        50, 0xF3, 0xE9,
     ];
 
     let results = parse_1byte_packets_with_errors(&packet);
@@ -518,7 +547,7 @@ fn test_parse_2byte_signed_packets() {
         0x55,                   // STATUS
         0x00,                   // Error
         0x64, 0x00,             // Load = 100 (little-endian)
-        0x00, 0x00,             // CRC
+        0xD0, 0x93,             // CRC
     ];
 
     let results = parse_2byte_signed_packets(&packet);
@@ -533,7 +562,7 @@ fn test_parse_2byte_signed_packets_negative() {
     // Negative load value (-100 = 0xFF9C)
     let packet = vec![
         0xFF, 0xFF, 0xFD, 0x00, 17, 0x06, 0x00, 0x55, 0x00, 0x9C, 0xFF, // -100 little-endian
-        0x00, 0x00,
+        0xCD, 0x00,
     ];
 
     let results = parse_2byte_signed_packets(&packet);
@@ -548,15 +577,81 @@ fn test_parse_status_packet() {
     // Position status packet at offset 0
     let packet = vec![
         0xFF, 0xFF, 0xFD, 0x00, 18, 0x08, 0x00, 0x55, 0x00, 0x00, 0x0C, 0x00, 0x00, // Pos = 3072
-        0x00, 0x00,
+        0xEC, 0x54,
     ];
 
     let result = parse_status_packet(&packet, 0);
     assert!(result.is_ok());
 
-    let (id, pos) = result.unwrap();
+    let (id, pos, status) = result.unwrap();
     assert_eq!(id, 18);
     assert_eq!(pos, 3072);
+    assert!(status.is_ok());
+}
+
+#[test]
+fn test_parse_status_packet_keeps_alert() {
+    let packet = status_packet(13, 0x80, &2048i32.to_le_bytes());
+
+    let (id, pos, status) = parse_status_packet(&packet, 0).unwrap();
+    assert_eq!((id, pos), (13, 2048));
+    assert!(status.alert());
+    assert_eq!(status.protocol_error(), None);
+}
+
+#[test]
+fn test_parse_position_packets_with_status() {
+    let mut response = status_packet(11, 0x00, &2048i32.to_le_bytes());
+    response.extend(status_packet(12, 0x80, &3072i32.to_le_bytes()));
+    response.extend(status_packet(13, 0x84, &1024i32.to_le_bytes()));
+
+    let parsed = parse_position_packets_with_status(&response);
+    assert_eq!(
+        parsed,
+        vec![
+            (11, 2048, StatusError(0x00)),
+            (12, 3072, StatusError(0x80)),
+            (13, 1024, StatusError(0x84)),
+        ]
+    );
+
+    // The plain variant still returns every position
+    assert_eq!(parse_position_packets(&response), vec![(11, 2048), (12, 3072), (13, 1024)]);
+}
+
+#[test]
+fn test_parse_fast_sync_position_with_status() {
+    let response = fast_sync_response(&[
+        (11, 0x00, 2048i32.to_le_bytes().to_vec()),
+        (12, 0x80, 1000i32.to_le_bytes().to_vec()),
+    ]);
+
+    let parsed = parse_fast_sync_position_with_status(&response);
+    assert_eq!(parsed, vec![(11, 2048, StatusError(0)), (12, 1000, StatusError(0x80))]);
+}
+
+#[test]
+fn test_status_error_decoding() {
+    assert!(StatusError(0).is_ok());
+    assert_eq!(StatusError(0).to_string(), "OK");
+
+    let alert = StatusError(0x80);
+    assert!(alert.alert());
+    assert_eq!(alert.protocol_code(), 0);
+    assert_eq!(alert.to_string(), "Alert");
+
+    let range = StatusError(0x04);
+    assert!(!range.alert());
+    assert_eq!(range.protocol_error(), Some(ProtocolError::DataRange));
+    assert_eq!(range.to_string(), "Data Range Error");
+
+    assert_eq!(StatusError(0x87).to_string(), "Alert, Access Error");
+    assert_eq!(StatusError(0x09).protocol_error(), None);
+    assert_eq!(StatusError(0x09).to_string(), "Unknown Error (9)");
+
+    for code in 1..=7 {
+        assert_eq!(ProtocolError::from_code(code).map(|e| e as u8), Some(code));
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -590,6 +685,82 @@ fn test_parse_wrong_instruction() {
     assert!(results.is_empty(), "Wrong instruction should not parse");
 }
 
+#[test]
+fn test_parse_status_packet_errors() {
+    let mut corrupted = status_packet(11, 0, &[40]);
+    let n = corrupted.len();
+    corrupted[n - 1] ^= 0xFF;
+    assert_eq!(
+        parse_status_packet_1byte(&corrupted),
+        Err(ReachyError::Parse(ParseError::CrcMismatch))
+    );
+
+    let alert = status_packet(12, 0x80, &[40]);
+    let err = parse_status_packet_1byte(&alert).unwrap_err();
+    assert_eq!(err, ReachyError::MotorStatusError { id: 12, status: 0x80 });
+    assert_eq!(err.code(), "MOTOR_STATUS_ERROR");
+
+    let wrong_size = status_packet(13, 0, &[1, 2]);
+    assert_eq!(
+        parse_status_packet_1byte(&wrong_size),
+        Err(ReachyError::Parse(ParseError::InvalidLength))
+    );
+}
+
+#[test]
+fn test_reachy_error_codes_and_messages() {
+    assert_eq!(ReachyError::NotConnected.code(), "NOT_CONNECTED");
+    assert_eq!(
+        ReachyError::NotConnected.to_string(),
+        "Not connected to Reachy Mini. Call connect() first."
+    );
+    assert_eq!(
+        ReachyError::StreamNotConnected("video").to_string(),
+        "Not connected to video stream. Call connect_video_stream() first."
+    );
+
+    let timeout = ReachyError::Timeout { timeout_ms: 100, attempts: 3 };
+    assert_eq!(timeout.code(), "TIMEOUT");
+    assert_eq!(timeout.to_string(), "No response within 100 ms after 3 attempt(s)");
+
+    let hw = ReachyError::MotorHardwareError { id: 14, bits: 0x24 };
+    assert_eq!(hw.code(), "MOTOR_HARDWARE_ERROR");
+    assert_eq!(hw.to_string(), "Motor 14 hardware error: 0x24");
+
+    assert_eq!(ReachyError::from(ParseError::CrcMismatch).code(), "PARSE_ERROR");
+    assert_eq!(ParseError::CrcMismatch.code(), "CRC_MISMATCH");
+    let unreachable = ReachyError::UnreachablePose { branches: vec![2, 3] };
+    assert_eq!(unreachable.code(), "UNREACHABLE_POSE");
+    assert_eq!(unreachable.to_string(), "Pose is unreachable by head branches [2, 3]");
+    let limit = ReachyError::LimitExceeded { limit: "head_3".into(), value: 170.0, min: -160.0, max: 160.0 };
+    assert_eq!(limit.code(), "LIMIT_EXCEEDED");
+    assert_eq!(limit.to_string(), "head_3 = 170 is outside the limits [-160, 160]");
+}
+
+#[test]
+fn test_values_by_id_reports_missing() {
+    let mut response = status_packet(11, 0, &[35]);
+    response.extend(status_packet(13, 0, &[41]));
+    response.extend(status_packet(42, 0, &[50])); // Not requested
+
+    let ids = [11, 12, 13];
+    let temps = values_by_id(&ids, parse_1byte_packets(&response));
+    assert_eq!(temps, vec![Some(35), None, Some(41)]);
+    assert_eq!(missing_ids(&ids, &temps), vec![12]);
+
+    // A real reading of 0 is not mistaken for a missing motor
+    let zero = values_by_id(&[11], parse_position_packets(&status_packet(11, 0, &0i32.to_le_bytes())));
+    assert_eq!(zero, vec![Some(0)]);
+    assert!(missing_ids(&[11], &zero).is_empty());
+}
+
+#[test]
+fn test_missing_motors_error() {
+    let err = ReachyError::MissingMotors { ids: vec![14, 16] };
+    assert_eq!(err.code(), "MISSING_MOTORS");
+    assert_eq!(err.to_string(), "No response from motors [14, 16]");
+}
+
 #[test]
 fn test_broadcast_id_constant() {
     assert_eq!(BROADCAST_ID, 0xFE);
@@ -605,11 +776,1755 @@ fn test_address_constants() {
     assert_eq!(address::PRESENT_TEMPERATURE, 146);
 }
 
+// ----------------------------------------------------------------------------
+// Control Table Tests
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_control_table_matches_address_constants() {
+    assert_eq!(register::TorqueEnable::INFO.address, address::TORQUE_ENABLE);
+    assert_eq!(register::HardwareErrorStatus::INFO.address, address::HARDWARE_ERROR_STATUS);
+    assert_eq!(register::GoalPosition::INFO.address, address::GOAL_POSITION);
+    assert_eq!(register::PresentLoad::INFO.address, address::PRESENT_LOAD);
+    assert_eq!(register::PresentPosition::INFO.address, address::PRESENT_POSITION);
+    assert_eq!(register::PresentTemperature::INFO.address, address::PRESENT_TEMPERATURE);
+}
+
+#[test]
+fn test_control_table_sorted_and_sized() {
+    for pair in CONTROL_TABLE.windows(2) {
+        assert!(pair[0].address <= pair[1].address, "{} out of order", pair[1].name);
+    }
+    for reg in CONTROL_TABLE {
+        assert!(matches!(reg.size, 1 | 2 | 4), "{} has invalid size", reg.name);
+    }
+    assert_eq!(register_at(11).unwrap().name, "Operating Mode");
+    assert_eq!(register_at(126).unwrap().name, "Present Load");
+    assert_eq!(register::PresentVelocity::INFO.access, Access::ReadOnly);
+}
+
+#[test]
+fn test_generic_sync_read_matches_legacy_builders() {
+    let ids = [11, 12, 13, 14, 15, 16, 17, 18];
+    assert_eq!(build_sync_read::<register::PresentPosition>(&ids), build_sync_current_position(&ids));
+    assert_eq!(build_sync_read::<register::PresentTemperature>(&ids), build_sync_read_temperature(&ids));
+    assert_eq!(build_sync_read::<register::PresentLoad>(&ids), build_sync_read_load(&ids));
+    assert_eq!(build_sync_read::<register::HardwareErrorStatus>(&ids), build_sync_read_hardware_error(&ids));
+}
+
+#[test]
+fn test_generic_sync_write_2byte_register() {
+    let packet = build_sync_write::<register::PositionPGain>(&[11, 12], &[800, 0x1234]);
+
+    assert_valid_header(&packet, BROADCAST_ID);
+    assert_eq!(packet[7], 0x83, "Instruction should be SYNC_WRITE");
+    assert_eq!(packet[8], 84, "Position P Gain address");
+    assert_eq!(packet[10], 2, "Data length");
+    // n*(id + 2 bytes)
+    assert_eq!(&packet[12..18], &[11, 0x20, 0x03, 12, 0x34, 0x12]);
+    // header(4) + id(1) + len(2) + instr(1) + addr(2) + data_len(2) + 6 + crc(2)
+    assert_eq!(packet.len(), 20);
+}
+
+#[test]
+fn test_parse_register_packets_signed_velocity() {
+    // Present Velocity = -10 ticks (4 bytes, signed)
+    let packet = vec![
+        0xFF, 0xFF, 0xFD, 0x00, 12, 0x08, 0x00, 0x55, 0x00, 0xF6, 0xFF, 0xFF, 0xFF, 0x75, 0x20,
+    ];
+
+    let results = parse_register_packets::<register::PresentVelocity>(&packet);
+    assert_eq!(results, vec![(12, -10)]);
+
+    let rpm = register::PresentVelocity::INFO.to_unit(results[0].1);
+    assert!((rpm + 2.29).abs() < 1e-4);
+}
+
+#[test]
+fn test_parse_register_packets_unsigned_voltage() {
+    // Present Input Voltage = 50 (5.0 V, 2 bytes, unsigned)
+    let packet = vec![
+        0xFF, 0xFF, 0xFD, 0x00, 11, 0x06, 0x00, 0x55, 0x00, 0x32, 0x00, 0x65, 0xFD,
+    ];
+
+    let results = parse_register_packets::<register::PresentInputVoltage>(&packet);
+    assert_eq!(results, vec![(11, 50)]);
+    assert!((register::PresentInputVoltage::INFO.to_unit(50) - 5.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_register_unit_conversion_position() {
+    let info = register::GoalPosition::INFO;
+    assert_eq!(info.from_unit(0.0), 2048);
+    assert!((info.to_unit(3072) - std::f32::consts::FRAC_PI_2).abs() < 1e-3);
+    assert_eq!(info.encode(-1), vec![0xFF, 0xFF, 0xFF, 0xFF]);
+    assert_eq!(register::TorqueEnable::INFO.encode(1), vec![1]);
+}
+
+// ----------------------------------------------------------------------------
+// Hardware Error Status Tests
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_hardware_error_status_flags() {
+    let status = HardwareErrorStatus(0x88);
+    assert!(!status.is_ok());
+    assert!(status.overheating());
+    assert!(status.overload());
+    assert!(!status.input_voltage());
+    assert!(!status.hall_sensor());
+    assert!(!status.encoder());
+    assert!(!status.electrical_shock());
+    assert_eq!(status.labels(), vec!["Overheating Error", "Overload Error"]);
+}
+
+#[test]
+fn test_hardware_error_status_display() {
+    assert!(HardwareErrorStatus(0).is_ok());
+    assert_eq!(HardwareErrorStatus(0).to_string(), "OK");
+    assert_eq!(
+        HardwareErrorStatus(0x21).to_string(),
+        "Input Voltage Error, Electrical Shock Error"
+    );
+    // Bits 1 and 6 are reserved
+    assert_eq!(HardwareErrorStatus(0x42).to_string(), "Unknown Error (0x42)");
+}
+
+#[test]
+fn test_hardware_error_status_read_with_alert() {
+    // A motor with a hardware error answers with the alert bit set
+    let mut response = status_packet(11, 0x00, &[0x00]);
+    response.extend(status_packet(12, 0x80, &[HardwareErrorStatus::OVERLOAD]));
+
+    let parsed = parse_1byte_packets_with_errors(&response);
+    assert_eq!(parsed, vec![(11, 0x00), (12, HardwareErrorStatus::OVERLOAD)]);
+    assert!(HardwareErrorStatus(parsed[1].1).overload());
+}
+
+// ----------------------------------------------------------------------------
+// Ping Tests
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_build_ping_packet() {
+    let packet = build_ping_packet(BROADCAST_ID);
+    assert_eq!(packet, vec![0xFF, 0xFF, 0xFD, 0x00, 0xFE, 0x03, 0x00, 0x01, 0x31, 0x42]);
+}
+
+#[test]
+fn test_ping_expected_responses() {
+    assert_eq!(expected_responses(&build_ping_packet(11)), Some(1));
+    // Any number of motors may answer a broadcast PING
+    assert_eq!(expected_responses(&build_ping_packet(BROADCAST_ID)), None);
+}
+
+#[test]
+fn test_broadcast_ping_window() {
+    // Motors answer 3 ms apart per ID, starting with ID 0
+    assert_eq!(broadcast_ping_window_ms(0), 3);
+    assert_eq!(broadcast_ping_window_ms(18), 57);
+    assert_eq!(broadcast_ping_window_ms(MAX_ID), 759);
+}
+
+#[test]
+fn test_parse_ping_responses() {
+    // XL330-M288 (1200 = 0x04B0), firmware 46
+    let mut response = status_packet(11, 0, &[0xB0, 0x04, 46]);
+    response.extend(status_packet(1, 0, &[0xA6, 0x04, 46])); // Replacement at factory ID
+    response.extend(status_packet(12, 0, &2048i32.to_le_bytes())); // Not a PING answer
+
+    let pings = parse_ping_responses(&response);
+    assert_eq!(pings.len(), 2);
+    assert_eq!(
+        pings[0],
+        PingResponse { id: 11, model_number: 1200, firmware_version: 46, error: 0 }
+    );
+    assert_eq!(pings[0].model_name(), Some("XL330-M288"));
+    assert_eq!(pings[1].id, 1);
+    assert_eq!(pings[1].model_name(), Some("XL330-M077"));
+    assert_eq!(model_name(9999), None);
+}
+
+// ----------------------------------------------------------------------------
+// Provisioning, Staged Write and Factory Reset Tests
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_build_write_packet() {
+    // Write ID 14 to motor 1
+    let packet = build_write_packet(1, 7, &[14]);
+
+    assert_valid_header(&packet, 1);
+    assert_eq!(packet[7], 0x03, "Instruction should be WRITE");
+    // Length = instr + addr(2) + data(1) + crc(2) = 6
+    assert_eq!(packet[5], 6);
+    assert_eq!(&packet[8..11], &[7, 0, 14]);
+    let crc = crc16(&packet[..11]);
+    assert_eq!(&packet[11..], &crc.to_le_bytes());
+
+    assert_eq!(expected_responses(&packet), Some(1));
+    // Broadcast writes get no response
+    assert_eq!(expected_responses(&build_write_packet(BROADCAST_ID, 7, &[14])), Some(0));
+}
+
+#[test]
+fn test_build_write_info_uses_register_size() {
+    let reg = <register::MinPositionLimit as Register>::INFO;
+    let packet = build_write_info(11, &reg, 1024);
+    assert_eq!(&packet[8..10], &[52, 0]);
+    assert_eq!(&packet[10..14], &1024i32.to_le_bytes());
+
+    let reg = <register::BaudRate as Register>::INFO;
+    assert_eq!(build_write_info(11, &reg, 3), build_write_packet(11, 8, &[3]));
+}
+
+#[test]
+fn test_parse_write_status() {
+    assert_eq!(parse_write_status(&status_packet(14, 0, &[])), Ok(14));
+    assert_eq!(
+        parse_write_status(&status_packet(14, 0x07, &[])),
+        Err(ReachyError::MotorStatusError { id: 14, status: 0x07 })
+    );
+    // Alert only: the write went through
+    assert_eq!(parse_write_status(&status_packet(14, 0x80, &[])), Ok(14));
+    assert!(parse_write_status(&[]).is_err());
+}
+
+#[test]
+fn test_register_is_eeprom() {
+    assert!(<register::Id as Register>::INFO.is_eeprom());
+    assert!(<register::BaudRate as Register>::INFO.is_eeprom());
+    assert!(<register::MaxPositionLimit as Register>::INFO.is_eeprom());
+    assert!(!<register::TorqueEnable as Register>::INFO.is_eeprom());
+    assert!(!<register::GoalPosition as Register>::INFO.is_eeprom());
+}
+
+#[test]
+fn test_baud_rate_codes() {
+    assert_eq!(baud_rate_code(57_600), Some(1));
+    assert_eq!(baud_rate_code(1_000_000), Some(3));
+    assert_eq!(baud_rate_code(250_000), None);
+    assert_eq!(baud_rate_bps(3), Some(1_000_000));
+    assert_eq!(baud_rate_bps(7), None);
+}
+
+#[test]
+fn test_eeprom_config_write_order() {
+    let config = EepromConfig {
+        id: Some(14),
+        baud_rate: Some(3),
+        return_delay_time: Some(0),
+        ..EepromConfig::default()
+    };
+    let names: Vec<&str> = config.writes().iter().map(|(reg, _)| reg.name).collect();
+    // ID after the other settings, baud rate last
+    assert_eq!(names, vec!["Return Delay Time", "ID", "Baud Rate"]);
+    assert_eq!(config.writes()[1].1, 14);
+
+    assert!(EepromConfig::default().writes().is_empty());
+}
+
+#[test]
+fn test_eeprom_config_validate() {
+    assert!(EepromConfig { id: Some(14), ..EepromConfig::default() }.validate().is_ok());
+    assert!(EepromConfig { id: Some(BROADCAST_ID), ..EepromConfig::default() }.validate().is_err());
+    assert!(EepromConfig { baud_rate: Some(9), ..EepromConfig::default() }.validate().is_err());
+    assert!(EepromConfig { max_position_limit: Some(5000), ..EepromConfig::default() }
+        .validate()
+        .is_err());
+
+    let inverted = EepromConfig {
+        min_position_limit: Some(3000),
+        max_position_limit: Some(1000),
+        ..EepromConfig::default()
+    };
+    match inverted.validate() {
+        Err(e) => assert_eq!(e.code(), "INVALID_ARGUMENT"),
+        Ok(()) => panic!("Inverted limits should be rejected"),
+    }
+}
+
+#[test]
+fn test_build_reg_write_packet() {
+    let packet = build_reg_write::<register::GoalPosition>(11, 2048);
+
+    assert_valid_header(&packet, 11);
+    assert_eq!(packet[7], 0x04, "Instruction should be REG_WRITE");
+    // Length = instr + addr(2) + data(4) + crc(2) = 9
+    assert_eq!(packet[5], 9);
+    assert_eq!(&packet[8..10], &[116, 0]);
+    assert_eq!(&packet[10..14], &2048i32.to_le_bytes());
+    assert_eq!(expected_responses(&packet), Some(1));
+
+    // Same payload as WRITE, only the instruction differs
+    let write = build_write::<register::GoalPosition>(11, 2048);
+    assert_eq!(&packet[..7], &write[..7]);
+    assert_eq!(&packet[8..14], &write[8..14]);
+}
+
+#[test]
+fn test_build_action_packet() {
+    let packet = build_action_packet(BROADCAST_ID);
+    assert_valid_header(&packet, BROADCAST_ID);
+    assert_eq!(packet[7], 0x05, "Instruction should be ACTION");
+    assert_eq!(packet.len(), 10);
+    assert_eq!(expected_responses(&packet), Some(0));
+    assert_eq!(expected_responses(&build_action_packet(11)), Some(1));
+}
+
+#[test]
+fn test_build_factory_reset_packet() {
+    let packet = build_factory_reset_packet(14, FactoryResetMode::ExceptIdAndBaudRate);
+    assert_valid_header(&packet, 14);
+    assert_eq!(packet[7], 0x06, "Instruction should be FACTORY_RESET");
+    assert_eq!(packet[8], 0x02);
+    assert_eq!(packet.len(), 11);
+    assert_eq!(expected_responses(&packet), Some(1));
+
+    assert_eq!(build_factory_reset_packet(14, FactoryResetMode::All)[8], 0xFF);
+    assert_eq!(build_factory_reset_packet(14, FactoryResetMode::ExceptId)[8], 0x01);
+}
+
+#[test]
+fn test_factory_reset_mode() {
+    assert_eq!(FactoryResetMode::from_name("all"), Some(FactoryResetMode::All));
+    assert_eq!(
+        FactoryResetMode::from_name("except_id_and_baud"),
+        Some(FactoryResetMode::ExceptIdAndBaudRate)
+    );
+    assert_eq!(FactoryResetMode::from_name("everything"), None);
+
+    assert!(!FactoryResetMode::All.keeps_id());
+    assert!(FactoryResetMode::ExceptId.keeps_id());
+    assert!(!FactoryResetMode::ExceptId.keeps_baud_rate());
+    assert!(FactoryResetMode::ExceptIdAndBaudRate.keeps_baud_rate());
+}
+
+#[test]
+fn test_parse_staged_write_ack() {
+    // REG_WRITE, ACTION and FACTORY_RESET are acknowledged like WRITE
+    assert_eq!(parse_write_status(&status_packet(11, 0, &[])), Ok(11));
+    // ACTION without a registered write
+    assert_eq!(
+        parse_write_status(&status_packet(11, 0x02, &[])),
+        Err(ReachyError::MotorStatusError { id: 11, status: 0x02 })
+    );
+}
+
+// ----------------------------------------------------------------------------
+// Bulk Read/Write Tests
+// ----------------------------------------------------------------------------
+
+/// Build a status packet with the given parameters
+fn status_packet(id: u8, error: u8, params: &[u8]) -> Vec<u8> {
+    build_status_packet(id, error, params)
+}
+
+#[test]
+fn test_build_bulk_read_structure() {
+    let entries = [
+        BulkReadEntry { id: 11, address: address::PRESENT_POSITION, length: 4 },
+        BulkReadEntry { id: 17, address: address::PRESENT_TEMPERATURE, length: 1 },
+    ];
+    let packet = build_bulk_read(&entries);
+
+    assert_valid_header(&packet, BROADCAST_ID);
+    assert_eq!(packet[7], 0x92, "Instruction should be BULK_READ");
+    // Length = instr + 2*(id + addr(2) + len(2)) + crc(2) = 13
+    assert_eq!(packet[5], 13);
+    assert_eq!(&packet[8..13], &[11, 132, 0, 4, 0]);
+    assert_eq!(&packet[13..18], &[17, 146, 0, 1, 0]);
+    assert_eq!(packet.len(), 20);
+}
+
+#[test]
+fn test_build_bulk_write_structure() {
+    let entries = [
+        BulkWriteEntry::register(11, &register::GoalPosition::INFO, 2048),
+        BulkWriteEntry::register(17, &register::TorqueEnable::INFO, 1),
+    ];
+    let packet = build_bulk_write(&entries);
+
+    assert_valid_header(&packet, BROADCAST_ID);
+    assert_eq!(packet[7], 0x93, "Instruction should be BULK_WRITE");
+    // Length = instr + (5 + 4) + (5 + 1) + crc(2) = 18
+    assert_eq!(packet[5], 18);
+    assert_eq!(&packet[8..17], &[11, 116, 0, 4, 0, 0x00, 0x08, 0x00, 0x00]);
+    assert_eq!(&packet[17..23], &[17, 64, 0, 1, 0, 1]);
+    assert_eq!(packet.len(), 25);
+}
+
+#[test]
+fn test_bulk_read_entry_covering() {
+    let regs = [
+        register::HardwareErrorStatus::INFO,
+        register::PresentPosition::INFO,
+        register::PresentTemperature::INFO,
+    ];
+    let entry = BulkReadEntry::covering(12, &regs);
+
+    assert_eq!(entry.address, 70);
+    assert_eq!(entry.length, 77); // 70..=146
+
+    let mut block = vec![0u8; 77];
+    block[0] = 0x20; // Hardware error (address 70)
+    block[62..66].copy_from_slice(&3000i32.to_le_bytes()); // Position (address 132)
+    block[76] = 41; // Temperature (address 146)
+
+    assert_eq!(entry.value(&register::HardwareErrorStatus::INFO, &block), Some(0x20));
+    assert_eq!(entry.value(&register::PresentPosition::INFO, &block), Some(3000));
+    assert_eq!(entry.value(&register::PresentTemperature::INFO, &block), Some(41));
+    assert_eq!(entry.value(&register::GoalPosition::INFO, &block), Some(0));
+    assert_eq!(entry.value(&register::BackupReady::INFO, &block), None);
+}
+
+#[test]
+fn test_parse_bulk_read_response() {
+    let entries = [
+        BulkReadEntry { id: 11, address: address::PRESENT_POSITION, length: 4 },
+        BulkReadEntry { id: 12, address: address::PRESENT_LOAD, length: 2 },
+        BulkReadEntry { id: 13, address: address::PRESENT_TEMPERATURE, length: 1 },
+    ];
+
+    let mut response = status_packet(11, 0, &[0x00, 0x08, 0x00, 0x00]);
+    response.extend(status_packet(12, 0x80, &[0x9C, 0xFF]));
+    // Motor 13 answered with the wrong length and must be ignored
+    response.extend(status_packet(13, 0, &[40, 0]));
+
+    let parsed = parse_bulk_read_response(&response, &entries);
+
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed[0].0.id, 11);
+    assert_eq!(parsed[0].2, vec![0x00, 0x08, 0x00, 0x00]);
+    assert_eq!(parsed[1].0.id, 12);
+    assert_eq!(parsed[1].1, 0x80, "Error byte is preserved");
+    assert_eq!(parsed[1].0.value(&register::PresentLoad::INFO, &parsed[1].2), Some(-100));
+}
+
+#[test]
+fn test_parse_status_packets_variable_length() {
+    let mut response = vec![0x00, 0x01]; // Garbage
+    response.extend(status_packet(14, 0, &[]));
+    response.extend(status_packet(15, 0, &[1, 2, 3, 4, 5, 6, 7]));
+    response.extend(&[0xFF, 0xFF, 0xFD, 0x00, 16, 0x20, 0x00, 0x55]); // Truncated
+
+    let packets = parse_status_packets(&response);
+
+    assert_eq!(packets.len(), 2);
+    assert_eq!(packets[0].id, 14);
+    assert!(packets[0].params.is_empty());
+    assert_eq!(packets[1].id, 15);
+    assert_eq!(packets[1].params, vec![1, 2, 3, 4, 5, 6, 7]);
+}
+
+// ----------------------------------------------------------------------------
+// Fast Sync Read Tests
+// ----------------------------------------------------------------------------
+
+/// Build a combined FAST_SYNC_READ status packet from (id, error, data) segments
+fn fast_sync_response(segments: &[(u8, u8, Vec<u8>)]) -> Vec<u8> {
+    let length = segments.iter().map(|(_, _, data)| data.len() + 4).sum::<usize>() + 1;
+    let mut packet = vec![0xFF, 0xFF, 0xFD, 0x00, BROADCAST_ID, (length & 0xFF) as u8, (length >> 8) as u8, 0x55];
+    for (id, error, data) in segments {
+        packet.push(*error);
+        packet.push(*id);
+        packet.extend_from_slice(data);
+        // Each motor's CRC covers the packet so far; the last one is the packet CRC
+        let crc = crc16(&packet);
+        packet.extend_from_slice(&crc.to_le_bytes());
+    }
+    packet
+}
+
+#[test]
+fn test_build_fast_sync_read_structure() {
+    let motor_ids = [11, 12, 13];
+    let packet = build_fast_sync_read::<register::PresentPosition>(&motor_ids);
+    let sync = build_sync_current_position(&motor_ids);
+
+    assert_valid_header(&packet, BROADCAST_ID);
+    assert_eq!(packet[7], 0x8A, "Instruction should be FAST_SYNC_READ");
+    assert_eq!(packet.len(), sync.len());
+    assert_eq!(packet[8..15], sync[8..15], "Same parameters as SYNC_READ");
+}
+
+#[test]
+fn test_parse_fast_sync_read_positions() {
+    let response = fast_sync_response(&[
+        (11, 0, 2048i32.to_le_bytes().to_vec()),
+        (12, 0, 3000i32.to_le_bytes().to_vec()),
+        (13, 0x80, 1024i32.to_le_bytes().to_vec()),
+    ]);
+    // Length = instr + 3*(err + id + 4 + crc) - crc + crc = 1 + 3*8
+    assert_eq!(response[5], 25);
+
+    let positions = parse_fast_sync_position(&response);
+    assert_eq!(positions, vec![(11, 2048), (12, 3000), (13, 1024)]);
+
+    let packets = parse_fast_sync_read(&response, 4);
+    assert_eq!(packets[2].error, 0x80, "Per-motor error byte is preserved");
+}
+
+#[test]
+fn test_parse_fast_sync_read_1byte_with_garbage() {
+    let mut response = vec![0x00, 0xFF];
+    response.extend(fast_sync_response(&[(17, 0, vec![38]), (18, 0, vec![41])]));
+
+    let packets = parse_fast_sync_read(&response, 1);
+
+    assert_eq!(packets.len(), 2);
+    assert_eq!((packets[0].id, packets[0].params[0]), (17, 38));
+    assert_eq!((packets[1].id, packets[1].params[0]), (18, 41));
+}
+
+#[test]
+fn test_parse_fast_sync_read_truncated() {
+    let mut response = fast_sync_response(&[
+        (11, 0, 2048i32.to_le_bytes().to_vec()),
+        (12, 0, 3000i32.to_le_bytes().to_vec()),
+    ]);
+    // Cut in the middle of the second motor's data
+    response.truncate(response.len() - 5);
+
+    let positions = parse_fast_sync_position(&response);
+    assert_eq!(positions, vec![(11, 2048)]);
+}
+
+#[test]
+fn test_parse_fast_sync_read_ignores_regular_status() {
+    // Regular per-motor status packet (non-broadcast ID)
+    let packet = vec![
+        0xFF, 0xFF, 0xFD, 0x00, 11, 0x08, 0x00, 0x55, 0x00, 0x00, 0x08, 0x00, 0x00, 0xDC, 0x07,
+    ];
+    assert!(parse_fast_sync_position(&packet).is_empty());
+}
+
+// ----------------------------------------------------------------------------
+// CRC and Byte Stuffing Tests
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_built_packets_have_valid_crc() {
+    let packets = [
+        build_read_packet(11, address::PRESENT_POSITION, 4),
+        build_reboot_packet(12),
+        build_sync_current_position(&ALL_MOTOR_IDS),
+        build_sync_write_torque(&ALL_MOTOR_IDS, true),
+        build_status_packet(13, 0, &[1, 2, 3]),
+    ];
+    for packet in &packets {
+        let n = packet.len();
+        assert_eq!(crc16(&packet[..n - 2]), u16::from_le_bytes([packet[n - 2], packet[n - 1]]));
+    }
+}
+
+#[test]
+fn test_parse_rejects_corrupted_crc() {
+    let mut response = status_packet(11, 0, &2048i32.to_le_bytes());
+    let mut corrupted = status_packet(12, 0, &3000i32.to_le_bytes());
+    corrupted[10] ^= 0x04; // Flip a bit in the position
+    response.extend(corrupted);
+    response.extend(status_packet(13, 0, &1024i32.to_le_bytes()));
+
+    let before = rejected_packet_count();
+    let positions = parse_position_packets(&response);
+
+    assert_eq!(positions, vec![(11, 2048), (13, 1024)], "Corrupted packet is dropped");
+    assert!(rejected_packet_count() > before, "Rejected packet is counted");
+}
+
+#[test]
+fn test_parse_fast_sync_read_stops_at_corrupted_segment() {
+    let mut response = fast_sync_response(&[
+        (11, 0, 2048i32.to_le_bytes().to_vec()),
+        (12, 0, 3000i32.to_le_bytes().to_vec()),
+        (13, 0, 1024i32.to_le_bytes().to_vec()),
+    ]);
+    response[20] ^= 0x01; // Second motor's data
+
+    assert_eq!(parse_fast_sync_position(&response), vec![(11, 2048)]);
+}
+
+#[test]
+fn test_build_applies_byte_stuffing() {
+    // 0x00FDFFFF encodes as FF FF FD 00, which must be stuffed
+    let packet = build_sync_write_position(&[11], &[0x00FD_FFFF]);
+
+    // Length = instr + addr(2) + len(2) + id + 4 + stuffing(1) + crc(2) = 13
+    assert_eq!(u16::from_le_bytes([packet[5], packet[6]]), 13);
+    assert_eq!(&packet[13..18], &[0xFF, 0xFF, 0xFD, 0xFD, 0x00]);
+    assert_eq!(packet.len(), 20);
+
+    let n = packet.len();
+    assert_eq!(crc16(&packet[..n - 2]), u16::from_le_bytes([packet[n - 2], packet[n - 1]]));
+}
+
+#[test]
+fn test_parse_removes_byte_stuffing() {
+    let packet = build_status_packet(14, 0, &[0xFF, 0xFF, 0xFD, 0x00]);
+    assert_eq!(packet.len(), 16, "One stuffing byte added");
+
+    assert_eq!(parse_position_packets(&packet), vec![(14, 0x00FD_FFFF)]);
+}
+
+// ----------------------------------------------------------------------------
+// Stream Framing Tests
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_framer_reassembles_split_packet() {
+    let packet = status_packet(11, 0, &2048i32.to_le_bytes());
+    let mut framer = PacketFramer::new();
+
+    assert!(framer.push(&packet[..3]).is_empty());
+    assert!(framer.push(&packet[3..9]).is_empty());
+    let packets = framer.push(&packet[9..]);
+
+    assert_eq!(packets, vec![packet]);
+    assert_eq!(framer.pending(), 0);
+}
+
+#[test]
+fn test_framer_splits_concatenated_packets() {
+    let first = status_packet(11, 0, &2048i32.to_le_bytes());
+    let second = status_packet(12, 0, &3000i32.to_le_bytes());
+    let third = status_packet(13, 0, &1024i32.to_le_bytes());
+
+    let mut chunk = vec![0x00, 0x42]; // Garbage
+    chunk.extend(&first);
+    chunk.extend(&second);
+    chunk.extend(&third[..5]);
+
+    let mut framer = PacketFramer::new();
+    assert_eq!(framer.push(&chunk), vec![first, second]);
+    assert_eq!(framer.push(&third[5..]), vec![third]);
+}
+
+#[test]
+fn test_framer_drops_corrupted_packet() {
+    let mut corrupted = status_packet(11, 0, &2048i32.to_le_bytes());
+    corrupted[9] ^= 0x10;
+    let valid = status_packet(12, 0, &[40]);
+
+    let mut chunk = corrupted;
+    chunk.extend(&valid);
+
+    let mut framer = PacketFramer::new();
+    assert_eq!(framer.push(&chunk), vec![valid]);
+}
+
+#[test]
+fn test_framer_skips_instruction_echo_and_keeps_partial_header() {
+    // Half-duplex adapters can echo the request back
+    let mut chunk = build_sync_current_position(&[11]);
+    chunk.extend(&[0x01, 0xFF, 0xFF]);
+
+    let mut framer = PacketFramer::new();
+    assert!(framer.push(&chunk).is_empty());
+    assert_eq!(framer.pending(), 2, "Possible header start is kept");
+
+    let packet = status_packet(11, 0, &2048i32.to_le_bytes());
+    assert_eq!(framer.push(&packet[2..]), vec![packet]);
+
+    framer.push(&[0xFF]);
+    framer.clear();
+    assert_eq!(framer.pending(), 0);
+}
+
+#[test]
+fn test_framer_rejects_oversized_length() {
+    // Header followed by a bogus 60000-byte length must not stall the framer
+    let mut chunk = vec![0xFF, 0xFF, 0xFD, 0x00, 11, 0x60, 0xEA, 0x55, 0x00];
+    let valid = status_packet(12, 0, &[40]);
+    chunk.extend(&valid);
+
+    let mut framer = PacketFramer::new();
+    assert_eq!(framer.push(&chunk), vec![valid]);
+}
+
+#[test]
+fn test_expected_responses() {
+    assert_eq!(expected_responses(&build_read_packet(11, address::PRESENT_POSITION, 4)), Some(1));
+    assert_eq!(expected_responses(&build_sync_current_position(&ALL_MOTOR_IDS)), Some(9));
+    assert_eq!(expected_responses(&build_fast_sync_read::<register::PresentPosition>(&ALL_MOTOR_IDS)), Some(1));
+    assert_eq!(expected_responses(&build_sync_write_torque(&ALL_MOTOR_IDS, true)), Some(0));
+
+    let entries = [
+        BulkReadEntry { id: 11, address: address::PRESENT_POSITION, length: 4 },
+        BulkReadEntry { id: 12, address: address::PRESENT_LOAD, length: 2 },
+    ];
+    assert_eq!(expected_responses(&build_bulk_read(&entries)), Some(2));
+    assert_eq!(expected_responses(&[0x00]), None);
+}
+
+#[test]
+fn test_expected_responders() {
+    assert_eq!(expected_responders(&build_read_packet(11, address::PRESENT_POSITION, 4)), Some(vec![11]));
+    assert_eq!(expected_responders(&build_sync_current_position(&[11, 13])), Some(vec![11, 13]));
+    assert_eq!(expected_responders(&build_fast_sync_read::<register::PresentPosition>(&[11, 12])), Some(vec![BROADCAST_ID]));
+    assert_eq!(expected_responders(&build_sync_write_torque(&[11, 12], true)), Some(vec![]));
+    assert_eq!(expected_responders(&build_ping_packet(BROADCAST_ID)), None);
+
+    let entries = [
+        BulkReadEntry { id: 11, address: address::PRESENT_POSITION, length: 4 },
+        BulkReadEntry { id: 17, address: address::PRESENT_LOAD, length: 2 },
+    ];
+    assert_eq!(expected_responders(&build_bulk_read(&entries)), Some(vec![11, 17]));
+    assert_eq!(expected_responders(&[0x00]), None);
+}
+
+// ----------------------------------------------------------------------------
+// I/O Policy Tests
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_io_policy_default() {
+    let policy = IoPolicy::default();
+    assert_eq!(policy.timeout_ms, 100);
+    assert_eq!(policy.retries, 0, "No retries unless configured");
+}
+
+#[test]
+fn test_io_policy_backoff_doubles() {
+    let policy = IoPolicy { timeout_ms: 100, retries: 3, backoff_ms: 20 };
+    assert_eq!(policy.backoff(1), 20);
+    assert_eq!(policy.backoff(2), 40);
+    assert_eq!(policy.backoff(3), 80);
+
+    // Large attempt counts saturate instead of overflowing
+    assert_eq!(policy.backoff(100), 20 << 16);
+    let huge = IoPolicy { backoff_ms: u32::MAX, ..policy };
+    assert_eq!(huge.backoff(5), u32::MAX);
+}
+
+// ----------------------------------------------------------------------------
+// Motor Bus Tests
+// ----------------------------------------------------------------------------
+
+/// In-memory bus answering each write with canned chunks, on a fake clock.
+struct ScriptedBus {
+    /// Chunks returned for each successive write (empty: no answer)
+    replies: RefCell<VecDeque<Vec<Vec<u8>>>>,
+    pending: RefCell<VecDeque<Vec<u8>>>,
+    writes: Cell<u32>,
+    clock: Cell<f64>,
+    policy: Cell<IoPolicy>,
+}
+
+impl ScriptedBus {
+    fn new(replies: Vec<Vec<Vec<u8>>>) -> Self {
+        Self {
+            replies: RefCell::new(replies.into()),
+            pending: RefCell::new(VecDeque::new()),
+            writes: Cell::new(0),
+            clock: Cell::new(0.0),
+            policy: Cell::new(IoPolicy::default()),
+        }
+    }
+}
+
+impl MotorBus for ScriptedBus {
+    async fn write(&self, _packet: &[u8]) -> Result<(), ReachyError> {
+        self.writes.set(self.writes.get() + 1);
+        let reply = self.replies.borrow_mut().pop_front().unwrap_or_default();
+        self.pending.borrow_mut().extend(reply);
+        Ok(())
+    }
+
+    async fn read(&self) -> Result<Vec<u8>, ReachyError> {
+        Err(ReachyError::TransportClosed)
+    }
+
+    async fn read_timeout(&self, ms: u32) -> Result<Option<Vec<u8>>, ReachyError> {
+        match self.pending.borrow_mut().pop_front() {
+            Some(chunk) => Ok(Some(chunk)),
+            None => {
+                self.clock.set(self.clock.get() + ms as f64);
+                Ok(None)
+            }
+        }
+    }
+
+    async fn sleep(&self, ms: u32) {
+        self.clock.set(self.clock.get() + ms as f64);
+    }
+
+    fn now_ms(&self) -> f64 {
+        self.clock.get()
+    }
+
+    fn io_policy(&self) -> IoPolicy {
+        self.policy.get()
+    }
+
+    fn set_io_policy(&self, policy: IoPolicy) {
+        self.policy.set(policy);
+    }
+}
+
+/// Run a future that never waits on real I/O.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+#[test]
+fn test_bus_write_read_reassembles_split_responses() {
+    let mut response = status_packet(11, 0, &2048i32.to_le_bytes());
+    response.extend(status_packet(12, 0, &1024i32.to_le_bytes()));
+    let (first, second) = response.split_at(7);
+    let bus = ScriptedBus::new(vec![vec![first.to_vec(), second.to_vec()]]);
+
+    let packet = build_sync_current_position(&[11, 12]);
+    let data = block_on(bus.write_read(&packet, None)).unwrap();
+    assert_eq!(parse_position_packets(&data), vec![(11, 2048), (12, 1024)]);
+    assert_eq!(bus.writes.get(), 1);
+}
+
+#[test]
+fn test_bus_write_read_timeout() {
+    let bus = ScriptedBus::new(vec![]);
+    let packet = build_read_packet(11, address::PRESENT_POSITION, 4);
+
+    assert_eq!(
+        block_on(bus.write_read(&packet, Some(50))),
+        Err(ReachyError::Timeout { timeout_ms: 50, attempts: 1 })
+    );
+    // Silence is a valid answer for try_write_read
+    assert_eq!(block_on(bus.try_write_read(&packet, None)), Ok(vec![]));
+}
+
+#[test]
+fn test_bus_write_read_retries_missing_responses() {
+    let answer = status_packet(11, 0, &2048i32.to_le_bytes());
+    let bus = ScriptedBus::new(vec![vec![], vec![answer.clone()]]);
+    bus.set_io_policy(IoPolicy { retries: 2, ..IoPolicy::default() });
+
+    let packet = build_read_packet(11, address::PRESENT_POSITION, 4);
+    assert_eq!(block_on(bus.write_read(&packet, None)), Ok(answer));
+    assert_eq!(bus.writes.get(), 2, "Stops retrying once every motor answered");
+}
+
+// ----------------------------------------------------------------------------
+// Simulated Bus Tests
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_decode_instruction_packet() {
+    let packet = build_write::<register::GoalPosition>(11, 1024);
+    let (decoded, len) = decode_instruction_packet(&packet).unwrap();
+    assert_eq!(len, packet.len());
+    assert_eq!(decoded.id, 11);
+    assert_eq!(decoded.instruction, 0x03);
+    assert_eq!(decoded.params, vec![116, 0, 0x00, 0x04, 0x00, 0x00]);
+
+    let mut corrupted = packet.clone();
+    corrupted[9] ^= 0xFF;
+    assert_eq!(decode_instruction_packet(&corrupted), Err(ParseError::CrcMismatch));
+    assert_eq!(decode_instruction_packet(&status_packet(11, 0, &[])), Err(ParseError::InvalidInstruction));
+
+    let mut stream = vec![0x00];
+    stream.extend(build_ping_packet(BROADCAST_ID));
+    stream.extend(corrupted);
+    stream.extend(build_reboot_packet(12));
+    let ids: Vec<u8> = parse_instruction_packets(&stream).iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![BROADCAST_ID, 12]);
+}
+
+#[test]
+fn test_build_fast_sync_status_packet() {
+    let segments: [(u8, u8, &[u8]); 2] = [(11, 0, &[0x00, 0x08, 0x00, 0x00]), (12, 0x80, &[0x00, 0x04, 0x00, 0x00])];
+    let owned: Vec<(u8, u8, Vec<u8>)> = segments.iter().map(|(id, e, d)| (*id, *e, d.to_vec())).collect();
+    assert_eq!(build_fast_sync_status_packet(&segments), fast_sync_response(&owned));
+}
+
+#[test]
+fn test_sim_ping_and_sync_read() {
+    let bus = SimulatedBus::new();
+
+    let response = block_on(bus.try_write_read(&build_ping_packet(BROADCAST_ID), None)).unwrap();
+    let pings = parse_ping_responses(&response);
+    assert_eq!(pings.iter().map(|p| p.id).collect::<Vec<_>>(), ALL_MOTOR_IDS.to_vec());
+    assert_eq!(pings[0].model_name(), Some("XL330-M288"));
+
+    let response = block_on(bus.write_read(&build_sync_current_position(&ALL_MOTOR_IDS), None)).unwrap();
+    let positions = parse_position_packets(&response);
+    assert_eq!(positions.len(), 9);
+    assert!(positions.iter().all(|&(_, pos)| pos == 2048));
+
+    let response = block_on(bus.write_read(&build_fast_sync_read::<register::PresentPosition>(&ALL_MOTOR_IDS), None)).unwrap();
+    assert_eq!(parse_fast_sync_position(&response).len(), 9);
+}
+
+#[test]
+fn test_sim_position_dynamics() {
+    let bus = SimulatedBus::new();
+    block_on(bus.write(&build_sync_write_torque(&[11], true))).unwrap();
+    block_on(bus.write(&build_sync_write_position(&[11], &[3072]))).unwrap();
+
+    block_on(bus.sleep(40));
+    let position = bus.register::<register::PresentPosition>(11).unwrap();
+    // One time constant: about 63% of the way
+    assert!((2650..2720).contains(&position), "position {}", position);
+    assert!(bus.register::<register::PresentLoad>(11).unwrap() > 0, "Load while tracking");
+    assert_eq!(bus.register::<register::Moving>(11), Some(1));
+
+    block_on(bus.sleep(500));
+    let response = block_on(bus.write_read(&build_read_packet(11, address::PRESENT_POSITION, 4), None)).unwrap();
+    assert_eq!(parse_position_packets(&response), vec![(11, 3072)]);
+    assert_eq!(bus.register::<register::Moving>(11), Some(0));
+
+    // Without torque, the goal is stored but nothing moves
+    block_on(bus.write(&build_sync_write_position(&[12], &[1024]))).unwrap();
+    block_on(bus.sleep(500));
+    assert_eq!(bus.register::<register::PresentPosition>(12), Some(2048));
+}
+
+#[test]
+fn test_sim_head_pose_follows_body() {
+    let bus = SimulatedBus::new();
+    let reference = block_on(read_head_pose(&bus)).unwrap();
+    assert_eq!(reference.len(), 7);
+    assert!(reference[6].abs() < 1e-3);
+
+    block_on(bus.write(&build_sync_write_torque(&[10], true))).unwrap();
+    block_on(bus.write(&build_sync_write_position_radians(&[10], &[30f32.to_radians()]))).unwrap();
+    block_on(bus.sleep(1000));
+
+    // The head turns with the body, keeping its height
+    let pose = block_on(read_head_pose(&bus)).unwrap();
+    assert!((pose[6] - 30.0).abs() < 0.1, "{:?}", pose);
+    assert!((pose[5] - reference[5] - pose[6]).abs() < 1e-2, "{:?}", pose);
+    assert!((pose[2] - reference[2]).abs() < 1e-3, "{:?}", pose);
+}
+
+#[test]
+fn test_sim_command_paths() {
+    let bus = SimulatedBus::new();
+    block_on(write_torque(&bus, true)).unwrap();
+    assert!(SIM_MOTOR_IDS.iter().all(|&id| bus.register::<register::TorqueEnable>(id) == Some(1)));
+
+    block_on(write_head_pose(&bus, [0.0, 0.0, 10.0, 0.0, 0.0, 20.0], None)).unwrap();
+    block_on(write_antennas(&bus, 30.0, -30.0)).unwrap();
+    block_on(bus.sleep(1000));
+    let expected = compute_joint_angles(0.0, 0.0, 10.0, 0.0, 0.0, 20.0);
+    let head = block_on(read_motor_positions(&bus, &HEAD_MOTOR_IDS)).unwrap();
+    assert!(head.iter().zip(&expected).all(|(a, e)| (a - e).abs() < 2e-3), "{:?} vs {:?}", head, expected);
+    let antennas = block_on(read_motor_positions(&bus, &[17, 18])).unwrap();
+    assert!((antennas[0].to_degrees() - 30.0).abs() < 0.1 && (antennas[1].to_degrees() + 30.0).abs() < 0.1, "{:?}", antennas);
+
+    // Same limits as the browser API
+    block_on(write_body_yaw(&bus, 200.0)).unwrap();
+    block_on(bus.sleep(1000));
+    let body = block_on(read_motor_positions(&bus, &[10])).unwrap();
+    assert!((body[0].to_degrees() - 160.0).abs() < 0.1, "{:?}", body);
+    assert!(matches!(block_on(write_all_joints(&bus, &[0.0; 9])), Err(ReachyError::InvalidArgument(_))));
+
+    block_on(write_torque(&bus, false)).unwrap();
+    assert!(SIM_MOTOR_IDS.iter().all(|&id| bus.register::<register::TorqueEnable>(id) == Some(0)));
+}
+
+#[test]
+fn test_sim_head_pose_asymmetric_body_range() {
+    // The body reaches further one way: a yaw allowed by the range is kept
+    let mut limits = Limits { policy: LimitPolicy::Reject, ..Limits::default() };
+    limits.joints.iter_mut().find(|j| j.id == 10).unwrap().min_deg = -20.0;
+    set_limits(&limits.to_json()).unwrap();
+
+    let bus = SimulatedBus::new();
+    block_on(write_torque(&bus, true)).unwrap();
+    block_on(write_head_pose(&bus, [0.0, 0.0, 0.0, 0.0, 0.0, 30.0], Some(60.0))).unwrap();
+    block_on(bus.sleep(1000));
+    let body = block_on(read_motor_positions(&bus, &[10])).unwrap();
+    assert!((body[0].to_degrees() - 60.0).abs() < 0.1, "{:?}", body);
+
+    assert!(matches!(
+        block_on(write_head_pose(&bus, [0.0, 0.0, 0.0, 0.0, 0.0, 0.0], Some(-30.0))),
+        Err(ReachyError::LimitExceeded { min, .. }) if min == -20.0
+    ));
+}
+
+#[test]
+fn test_sim_replay_frames() {
+    let bus = SimulatedBus::new();
+    let frame = |antenna: f32| vec![0.0, 0.0, 0.0, 0.0, 0.0, 0.0, antenna, 0.0];
+
+    // A bad frame is rejected before torque is enabled or anything is sent
+    let frames = vec![frame(0.5), frame(f32::NAN)];
+    assert!(matches!(block_on(replay_frames(&bus, &frames)), Err(ReachyError::InvalidArgument(_))));
+    assert!(SIM_MOTOR_IDS.iter().all(|&id| bus.register::<register::TorqueEnable>(id) == Some(0)));
+    assert_eq!(bus.register::<register::GoalPosition>(17), Some(2048));
+
+    // Torque is released after a full replay
+    block_on(replay_frames(&bus, &[frame(0.5), frame(0.5)])).unwrap();
+    assert!(SIM_MOTOR_IDS.iter().all(|&id| bus.register::<register::TorqueEnable>(id) == Some(0)));
+    assert_ne!(bus.register::<register::GoalPosition>(17), Some(2048));
+}
+
+#[test]
+fn test_sim_provision_motor() {
+    let bus = SimulatedBus::with_motors(&[1, 11]);
+    assert!(matches!(block_on(provision_motor(&bus, 1, 42)), Err(ReachyError::InvalidArgument(_))));
+    assert!(matches!(block_on(provision_motor(&bus, 1, 11)), Err(ReachyError::InvalidState(_))));
+    assert_eq!(block_on(provision_motor(&bus, 2, 14)), Err(ReachyError::MissingMotors { ids: vec![2] }));
+
+    block_on(provision_motor(&bus, 1, 14)).unwrap();
+    assert_eq!(bus.motor_ids(), vec![11, 14]);
+    assert_eq!(bus.register::<register::ReturnDelayTime>(14), Some(0));
+    assert_eq!(bus.register::<register::BaudRate>(14), Some(baud_rate_code(1_000_000).unwrap() as i32));
+}
+
+#[test]
+fn test_sim_write_access_checks() {
+    let bus = SimulatedBus::new();
+    let write = |packet: Vec<u8>| parse_write_status(&block_on(bus.write_read(&packet, None)).unwrap());
+
+    assert_eq!(write(build_write::<register::ReturnDelayTime>(11, 0)), Ok(11));
+    // Read-only register
+    assert_eq!(
+        write(build_write_packet(11, address::PRESENT_POSITION, &[0, 0, 0, 0])),
+        Err(ReachyError::MotorStatusError { id: 11, status: 7 })
+    );
+    // Goal outside the position limits
+    assert_eq!(
+        write(build_write::<register::GoalPosition>(11, 5000)),
+        Err(ReachyError::MotorStatusError { id: 11, status: 6 })
+    );
+    // EEPROM is locked while torque is on
+    assert_eq!(write(build_write::<register::TorqueEnable>(11, 1)), Ok(11));
+    assert_eq!(
+        write(build_write::<register::ReturnDelayTime>(11, 0)),
+        Err(ReachyError::MotorStatusError { id: 11, status: 7 })
+    );
+}
+
+#[test]
+fn test_sim_missing_motor() {
+    let bus = SimulatedBus::new();
+    assert!(bus.remove_motor(14));
+
+    let response = block_on(bus.write_read(&build_sync_read_temperature(&ALL_MOTOR_IDS), None)).unwrap();
+    let temperatures = values_by_id(&ALL_MOTOR_IDS, parse_1byte_packets(&response));
+    assert_eq!(missing_ids(&ALL_MOTOR_IDS, &temperatures), vec![14]);
+    assert_eq!(temperatures[0], Some(30));
+
+    let read = build_read_packet(14, address::PRESENT_POSITION, 4);
+    assert!(matches!(block_on(bus.write_read(&read, Some(10))), Err(ReachyError::Timeout { .. })));
+}
+
+#[test]
+fn test_sim_overheating_and_reboot() {
+    let bus = SimulatedBus::new();
+    block_on(bus.write(&build_sync_write_torque(&[13], true))).unwrap();
+    bus.set_temperature(13, 75.0);
+    block_on(bus.sleep(10));
+
+    assert_eq!(bus.register::<register::TorqueEnable>(13), Some(0), "Shutdown disables torque");
+    let response = block_on(bus.write_read(&build_sync_read_hardware_error(&[13]), None)).unwrap();
+    let packets = parse_status_packets(&response);
+    assert!(packets[0].status().alert());
+    assert!(HardwareErrorStatus(packets[0].params[0]).overheating());
+
+    // Rebooting clears the error once the motor has cooled down
+    bus.set_temperature(13, 40.0);
+    let response = block_on(bus.write_read(&build_reboot_packet(13), None)).unwrap();
+    assert_eq!(parse_write_status(&response), Ok(13));
+    assert_eq!(bus.register::<register::HardwareErrorStatus>(13), Some(0));
+}
+
+#[test]
+fn test_sim_staged_writes_and_id_change() {
+    let bus = SimulatedBus::new();
+    block_on(bus.write(&build_sync_write_torque(&ALL_MOTOR_IDS, true))).unwrap();
+    for id in [11, 12] {
+        let response = block_on(bus.write_read(&build_reg_write::<register::GoalPosition>(id, 1024), None)).unwrap();
+        assert_eq!(parse_write_status(&response), Ok(id));
+    }
+    assert_eq!(bus.register::<register::GoalPosition>(11), Some(2048), "Staged, not applied");
+    block_on(bus.write(&build_action_packet(BROADCAST_ID))).unwrap();
+    assert_eq!(bus.register::<register::GoalPosition>(11), Some(1024));
+    assert_eq!(bus.register::<register::GoalPosition>(12), Some(1024));
+
+    // Motors answer to their new ID after it is written
+    block_on(bus.write(&build_sync_write_torque(&[18], false))).unwrap();
+    let response = block_on(bus.write_read(&build_write::<register::Id>(18, 20), None)).unwrap();
+    assert_eq!(parse_write_status(&response), Ok(18));
+    assert!(!bus.motor_ids().contains(&18));
+    let response = block_on(bus.write_read(&build_ping_packet(20), None)).unwrap();
+    assert_eq!(parse_ping_responses(&response)[0].id, 20);
+}
+
+#[test]
+fn test_sim_stage_head_joints() {
+    let bus = SimulatedBus::with_motors(&[11, 12, 14, 15, 16]);
+    block_on(write_torque(&bus, true)).unwrap();
+    let angles = [10.0, -10.0, 0.0, 20.0, 0.0, -20.0];
+    assert_eq!(block_on(stage_head_joints_on(&bus, &angles)), Err(ReachyError::MissingMotors { ids: vec![13] }));
+    assert_eq!(bus.register::<register::GoalPosition>(11), Some(2048), "Staged, not applied");
+
+    block_on(trigger_staged_writes_on(&bus)).unwrap();
+    block_on(bus.sleep(1000));
+    let head = block_on(read_motor_positions(&bus, &[11, 12, 15, 16])).unwrap();
+    let expected = [10.0f32, -10.0, 0.0, -20.0];
+    assert!(head.iter().zip(&expected).all(|(a, e)| (a.to_degrees() - e).abs() < 0.1), "{:?}", head);
+    assert!(matches!(block_on(stage_head_joints_on(&bus, &[0.0; 5])), Err(ReachyError::InvalidArgument(_))));
+}
+
+// ----------------------------------------------------------------------------
+// Fault Injection Tests
+// ----------------------------------------------------------------------------
+
+/// Simulated bus with the given transport faults.
+fn faulty_bus(faults: Faults) -> SimulatedBus {
+    let bus = SimulatedBus::new();
+    bus.set_faults(faults);
+    bus
+}
+
+#[test]
+fn test_fault_dropped_responses() {
+    let bus = faulty_bus(Faults { dropped_ids: vec![13, 17], ..Faults::default() });
+
+    let positions = block_on(read_motor_positions(&bus, &ALL_MOTOR_IDS)).unwrap();
+    assert_eq!(positions.len(), 9);
+    assert!(positions[3].is_nan() && positions[7].is_nan(), "Missing motors are NaN");
+    assert!(positions.iter().filter(|p| !p.is_nan()).all(|p| p.abs() < 1e-3), "Others unaffected");
+
+    assert_eq!(block_on(read_head_pose(&bus)), Err(ReachyError::MissingMotors { ids: vec![13] }));
+
+    let report = block_on(check_and_reboot(&bus)).unwrap();
+    assert_eq!(report.motors_checked, 7);
+    assert_eq!(report.motors_no_response, vec![13, 17]);
+    assert!(report.motors_rebooted.is_empty());
+
+    // The pose depends on the body too
+    bus.set_faults(Faults { dropped_ids: vec![10], ..Faults::default() });
+    assert_eq!(block_on(read_head_pose(&bus)), Err(ReachyError::MissingMotors { ids: vec![10] }));
+
+    // Every motor lost: nothing to return at all
+    bus.set_faults(Faults { dropped_ids: ALL_MOTOR_IDS.to_vec(), ..Faults::default() });
+    assert!(matches!(block_on(read_motor_positions(&bus, &ALL_MOTOR_IDS)), Err(ReachyError::Timeout { .. })));
+}
+
+#[test]
+fn test_fault_corrupted_crc() {
+    let bus = faulty_bus(Faults { corrupted_ids: vec![12], ..Faults::default() });
+    let before = rejected_packet_count();
+
+    let positions = block_on(read_motor_positions(&bus, &ALL_MOTOR_IDS)).unwrap();
+    assert!(positions[2].is_nan(), "Corrupted packet is discarded");
+    assert_eq!(positions.iter().filter(|p| p.is_nan()).count(), 1);
+    assert!(rejected_packet_count() > before, "Rejected packet is counted");
+
+    assert_eq!(block_on(read_head_pose(&bus)), Err(ReachyError::MissingMotors { ids: vec![12] }));
+}
+
+#[test]
+fn test_fault_split_and_joined_chunks() {
+    let reference = block_on(read_head_pose(&SimulatedBus::new())).unwrap();
+
+    for chunking in [Chunking::Joined, Chunking::PerPacket, Chunking::Bytes(1), Chunking::Bytes(7)] {
+        let bus = faulty_bus(Faults { chunking, ..Faults::default() });
+        let positions = block_on(read_motor_positions(&bus, &ALL_MOTOR_IDS)).unwrap();
+        assert!(positions.iter().all(|p| !p.is_nan()), "{:?}: all motors read", chunking);
+        assert_eq!(block_on(read_head_pose(&bus)).unwrap(), reference, "{:?}", chunking);
+    }
+}
+
+#[test]
+fn test_fault_delayed_replies() {
+    // Within the 100 ms timeout: slower, but complete
+    let bus = faulty_bus(Faults { delay_ms: 60.0, ..Faults::default() });
+    let start = bus.now_ms();
+    let pose = block_on(read_head_pose(&bus)).unwrap();
+    assert_eq!(pose, block_on(read_head_pose(&SimulatedBus::new())).unwrap());
+    assert!(bus.now_ms() - start >= 60.0);
+
+    // One slow motor is reported missing
+    let bus = faulty_bus(Faults { slow_ids: vec![(15, 200.0)], ..Faults::default() });
+    let positions = block_on(read_motor_positions(&bus, &ALL_MOTOR_IDS)).unwrap();
+    assert!(positions[5].is_nan());
+    assert_eq!(positions.iter().filter(|p| p.is_nan()).count(), 1);
+
+    // Everything past the timeout
+    let bus = faulty_bus(Faults { delay_ms: 150.0, ..Faults::default() });
+    assert_eq!(
+        block_on(read_motor_positions(&bus, &ALL_MOTOR_IDS)),
+        Err(ReachyError::Timeout { timeout_ms: 100, attempts: 1 })
+    );
+
+    // A retry waits long enough for the late replies
+    bus.set_io_policy(IoPolicy { retries: 1, ..IoPolicy::default() });
+    bus.set_faults(Faults { delay_ms: 80.0, slow_ids: vec![(16, 50.0)], ..Faults::default() });
+    let positions = block_on(read_motor_positions(&bus, &HEAD_MOTOR_IDS)).unwrap();
+    assert!(positions.iter().all(|p| !p.is_nan()));
+}
+
+#[test]
+fn test_fault_late_reply_dropped() {
+    // Motor 15 answers after the read timed out...
+    let bus = faulty_bus(Faults { slow_ids: vec![(15, 150.0)], ..Faults::default() });
+    let positions = block_on(read_motor_positions(&bus, &HEAD_MOTOR_IDS)).unwrap();
+    assert!(positions[4].is_nan());
+
+    // ...while the next exchange waits for motor 11
+    bus.set_faults(Faults { delay_ms: 80.0, ..Faults::default() });
+    let response = block_on(bus.write_read(&build_read_packet(11, address::PRESENT_TEMPERATURE, 1), None)).unwrap();
+    let ids: Vec<u8> = parse_status_packets(&response).iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![11]);
+}
+
+#[test]
+fn test_fault_hardware_error_check_and_reboot() {
+    let bus = SimulatedBus::new();
+    block_on(bus.write(&build_sync_write_torque(&ALL_MOTOR_IDS, true))).unwrap();
+    assert!(bus.inject_hardware_error(14, HardwareErrorStatus::OVERLOAD));
+    assert!(!bus.inject_hardware_error(42, HardwareErrorStatus::OVERLOAD));
+    block_on(bus.sleep(10));
+    assert_eq!(bus.register::<register::TorqueEnable>(14), Some(0), "Shutdown disables torque");
+
+    // The alert bit doesn't hide the position by default
+    let positions = block_on(read_motor_positions(&bus, &ALL_MOTOR_IDS)).unwrap();
+    assert!(!positions[4].is_nan());
+    assert!(block_on(read_head_pose(&bus)).is_ok());
+
+    let result = block_on(read_motor_positions_checked(&bus, &ALL_MOTOR_IDS, true));
+    assert_eq!(result, Err(ReachyError::MotorStatusError { id: 14, status: StatusError::ALERT }));
+
+    let report = block_on(check_and_reboot(&bus)).unwrap();
+    assert_eq!(report.motors_checked, 9);
+    assert_eq!(report.motors_with_errors, vec![14]);
+    assert_eq!(report.motors_rebooted, vec![14]);
+    assert!(report.motors_no_response.is_empty());
+    assert_eq!(bus.register::<register::HardwareErrorStatus>(14), Some(0));
+
+    let report = block_on(check_and_reboot(&bus)).unwrap();
+    assert!(report.motors_with_errors.is_empty());
+    assert_eq!(report.motors_checked, 9);
+}
+
+// ----------------------------------------------------------------------------
+// Capture and Replay Tests
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_describe_instruction_packets() {
+    assert_eq!(describe_packets(&build_sync_current_position(&[11, 12])), vec!["SYNC_READ Present Position from [11, 12]"]);
+    assert_eq!(describe_packets(&build_write_packet(11, address::GOAL_POSITION, &2048i32.to_le_bytes())), vec!["WRITE ID 11 Goal Position = 2048"]);
+    assert_eq!(describe_packets(&build_sync_write_position(&[11, 12], &[2048, 1024])), vec!["SYNC_WRITE 11: Goal Position = 2048; 12: Goal Position = 1024"]);
+    assert_eq!(describe_packets(&build_ping_packet(BROADCAST_ID)), vec!["PING broadcast"]);
+    assert_eq!(describe_packets(&build_reboot_packet(14)), vec!["REBOOT ID 14"]);
+
+    // Two packets in one chunk, with garbage and a corrupted packet
+    let before = rejected_packet_count();
+    let mut corrupted = build_reboot_packet(15);
+    *corrupted.last_mut().unwrap() ^= 0xFF;
+    let data = [&[0x00, 0x42][..], &build_reboot_packet(11), &corrupted, &build_ping_packet(12)].concat();
+    assert_eq!(describe_packets(&data), vec!["REBOOT ID 11", "PING ID 12"]);
+    assert_eq!(rejected_packet_count(), before, "Offline decoding doesn't count rejects");
+}
+
+#[test]
+fn test_describe_status_packets() {
+    let request = build_sync_current_position(&[11, 12]);
+    let response = [build_status_packet(11, 0, &2048i32.to_le_bytes()), build_status_packet(12, 0x80, &1000i32.to_le_bytes())].concat();
+    assert_eq!(
+        describe_packets(&[request, response].concat()),
+        vec!["SYNC_READ Present Position from [11, 12]", "STATUS ID 11 Present Position = 2048", "STATUS ID 12 Present Position = 1000 (Alert)"]
+    );
+
+    let write = build_write_packet(11, address::GOAL_POSITION, &2048i32.to_le_bytes());
+    assert_eq!(describe_packets(&[write, build_status_packet(11, 0, &[])].concat())[1], "STATUS ID 11 (OK)");
+
+    let ping = [build_ping_packet(11), build_status_packet(11, 0, &[0xB0, 0x04, 46])].concat();
+    assert_eq!(describe_packets(&ping)[1], "STATUS ID 11 model 1200 (XL330-M288), firmware 46");
+
+    // Without the request, values can't be named
+    assert_eq!(describe_packets(&build_status_packet(11, 0, &[0x00, 0x08])), vec!["STATUS ID 11 [00 08]"]);
+
+    let fast = [
+        build_fast_sync_read::<register::PresentPosition>(&[11, 12]),
+        build_fast_sync_status_packet(&[(11, 0, &2048i32.to_le_bytes()), (12, 0, &1024i32.to_le_bytes())]),
+    ]
+    .concat();
+    assert_eq!(describe_packets(&fast)[1], "STATUS broadcast 11: Present Position = 2048; 12: Present Position = 1024");
+}
+
+#[test]
+fn test_capture_jsonl_roundtrip() {
+    let mut capture = Capture::new();
+    capture.record(Direction::Tx, 1000.0, &build_ping_packet(11));
+    capture.record(Direction::Rx, 1001.5, &build_status_packet(11, 0, &[0xB0, 0x04, 46]));
+    assert_eq!(capture.len(), 2);
+
+    let jsonl = capture.to_jsonl();
+    assert_eq!(jsonl.lines().count(), 2);
+    assert!(jsonl.starts_with(r#"{"t_ms":0.0,"dir":"tx","data":"fffffd000b03000119c6"}"#), "{}", jsonl);
+
+    let loaded = Capture::from_jsonl(&jsonl).unwrap();
+    assert_eq!(loaded.records().collect::<Vec<_>>(), capture.records().collect::<Vec<_>>());
+    assert_eq!(loaded.records().nth(1).unwrap().t_ms, 1.5);
+    assert_eq!(loaded.to_jsonl(), jsonl);
+
+    let bad = format!("{}{}\n", jsonl, r#"{"t_ms":3.0,"dir":"rx","data":"abc"}"#);
+    assert!(matches!(Capture::from_jsonl(&bad), Err(ReachyError::InvalidArgument(msg)) if msg.starts_with("Capture line 3:")));
+    assert!(Capture::from_jsonl("not json").is_err());
+}
+
+#[test]
+fn test_capture_limit_drops_oldest() {
+    let mut capture = Capture::with_limit(3);
+    for id in 11..16 {
+        capture.record(Direction::Tx, id as f64, &build_ping_packet(id));
+    }
+    assert_eq!(capture.len(), 3);
+    assert_eq!(capture.dropped(), 2);
+    assert_eq!(capture.records().next().unwrap().data, build_ping_packet(13));
+}
+
+#[test]
+fn test_tap_and_replay_bus() {
+    let bus = TapBus::new(faulty_bus(Faults { chunking: Chunking::Bytes(7), ..Faults::default() }));
+    let pose = block_on(read_head_pose(&bus)).unwrap();
+    let positions = block_on(read_motor_positions(&bus, &ALL_MOTOR_IDS)).unwrap();
+
+    let capture = bus.capture();
+    assert_eq!(capture.records().filter(|r| r.dir == Direction::Tx).count(), 2);
+    assert!(capture.records().filter(|r| r.dir == Direction::Rx).count() > 14, "Split replies are recorded as read");
+
+    let description = capture.describe();
+    let lines: Vec<&str> = description.lines().collect();
+    assert_eq!(lines.len(), 2 + 7 + 9, "{}", description);
+    assert!(lines[0].ends_with("TX  SYNC_READ Present Position from [10, 11, 12, 13, 14, 15, 16]"), "{}", lines[0]);
+    assert!(lines[1].contains("RX  STATUS ID 10 Present Position = 2048"), "{}", lines[1]);
+
+    // Replaying the exported capture gives the same results
+    let replay = ReplayBus::new(&Capture::from_jsonl(&capture.to_jsonl()).unwrap());
+    assert_eq!(replay.remaining_writes(), 2);
+    assert_eq!(block_on(read_head_pose(&replay)).unwrap(), pose);
+    let replayed = block_on(read_motor_positions(&replay, &ALL_MOTOR_IDS)).unwrap();
+    assert_eq!(format!("{:?}", replayed), format!("{:?}", positions));
+    assert_eq!(replay.remaining_writes(), 0);
+
+    // Diverging from the capture is an error
+    let replay = ReplayBus::new(&capture);
+    assert!(matches!(block_on(read_motor_positions(&replay, &HEAD_MOTOR_IDS)), Err(ReachyError::InvalidState(msg)) if msg.starts_with("Replay: expected")));
+    let replay = ReplayBus::new(&Capture::new());
+    assert!(matches!(block_on(replay.write(&build_ping_packet(11))), Err(ReachyError::InvalidState(_))));
+}
+
+#[test]
+fn test_replay_reproduces_timeouts() {
+    let bus = TapBus::new(faulty_bus(Faults { slow_ids: vec![(15, 200.0)], ..Faults::default() }));
+    let positions = block_on(read_motor_positions(&bus, &ALL_MOTOR_IDS)).unwrap();
+    assert!(positions[5].is_nan());
+
+    let replay = ReplayBus::new(&bus.take_capture());
+    let start = replay.now_ms();
+    let replayed = block_on(read_motor_positions(&replay, &ALL_MOTOR_IDS)).unwrap();
+    assert!(replayed[5].is_nan());
+    assert_eq!(replayed.iter().filter(|p| p.is_nan()).count(), 1);
+    assert!(replay.now_ms() - start >= 100.0, "Replay waits out the timeout");
+    assert!(bus.capture().is_empty());
+}
+
+// ----------------------------------------------------------------------------
+// Connection Health Tests
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_link_monitor_lifecycle() {
+    let mut link = LinkMonitor::new();
+    assert_eq!(link.state(), ConnectionState::Closed);
+    assert_eq!(link.activity(), None, "Data on a closed link changes nothing");
+    assert_eq!(link.lost(), None, "Nothing to lose");
+
+    assert_eq!(link.connecting(), Some(StateChange { previous: ConnectionState::Closed, current: ConnectionState::Connecting }));
+    assert_eq!(link.connecting(), None);
+    assert_eq!(link.lost(), None, "Errors while connecting don't close the link");
+    assert_eq!(link.opened(), Some(StateChange { previous: ConnectionState::Connecting, current: ConnectionState::Open }));
+    assert!(link.state().is_usable());
+
+    assert_eq!(link.lost(), Some(StateChange { previous: ConnectionState::Open, current: ConnectionState::Closed }));
+    assert!(!link.state().is_usable());
+    assert_eq!(link.lost(), None, "Lost only once");
+
+    // Reconnecting, then giving up
+    link.connecting();
+    assert_eq!(link.closed(), Some(StateChange { previous: ConnectionState::Connecting, current: ConnectionState::Closed }));
+}
+
+#[test]
+fn test_link_monitor_heartbeats() {
+    let mut link = LinkMonitor::new();
+    link.opened();
+
+    assert_eq!(link.heartbeat(false), None, "One miss is tolerated");
+    assert_eq!(link.heartbeat(true), None);
+    assert_eq!(link.missed_heartbeats(), 0);
+
+    assert_eq!(link.heartbeat(false), None);
+    assert_eq!(link.heartbeat(false), Some(StateChange { previous: ConnectionState::Open, current: ConnectionState::Degraded }));
+    assert!(link.state().is_usable(), "Degraded links still send requests");
+    assert_eq!(link.heartbeat(false), None);
+    assert_eq!(link.missed_heartbeats(), 3);
+
+    // Any answer restores the link
+    assert_eq!(link.activity(), Some(StateChange { previous: ConnectionState::Degraded, current: ConnectionState::Open }));
+    assert_eq!(link.missed_heartbeats(), 0);
+
+    link.heartbeat(false);
+    link.heartbeat(false);
+    assert_eq!(link.lost(), Some(StateChange { previous: ConnectionState::Degraded, current: ConnectionState::Closed }));
+    assert_eq!(link.heartbeat(false), None, "Closed links don't degrade");
+}
+
+#[test]
+fn test_connection_state_names() {
+    let names: Vec<&str> = [ConnectionState::Connecting, ConnectionState::Open, ConnectionState::Degraded, ConnectionState::Closed].iter().map(|s| s.as_str()).collect();
+    assert_eq!(names, vec!["connecting", "open", "degraded", "closed"]);
+    assert_eq!(ConnectionState::Degraded.to_string(), "degraded");
+}
+
+#[test]
+fn test_reconnect_policy_backoff() {
+    let policy = ReconnectPolicy::default();
+    let delays: Vec<u32> = (1..=7).map(|n| policy.delay(n)).collect();
+    assert_eq!(delays, vec![500, 1000, 2000, 4000, 8000, 10_000, 10_000]);
+    assert_eq!(policy.delay(u32::MAX), 10_000, "No overflow");
+    assert!(policy.allows(1000));
+
+    let policy = ReconnectPolicy { initial_delay_ms: 100, max_delay_ms: 250, max_attempts: Some(3) };
+    assert_eq!((1..=4).map(|n| policy.delay(n)).collect::<Vec<_>>(), vec![100, 200, 250, 250]);
+    assert!(policy.allows(3));
+    assert!(!policy.allows(4));
+}
+
+#[test]
+fn test_check_link_heartbeat() {
+    let bus = TapBus::new(SimulatedBus::new());
+    assert_eq!(block_on(check_link(&bus)), Ok(true));
+    assert_eq!(bus.capture().describe().lines().next().map(|l| l.ends_with("TX  READ ID 11 Hardware Error Status")), Some(true), "{}", bus.capture().describe());
+
+    // Silent motor: no answer, but not an error
+    bus.inner().set_faults(Faults { dropped_ids: vec![HEARTBEAT_MOTOR_ID], ..Faults::default() });
+    let start = bus.now_ms();
+    assert_eq!(block_on(check_link(&bus)), Ok(false));
+    assert!(bus.now_ms() - start >= 100.0);
+
+    // Other motors don't count
+    bus.inner().set_faults(Faults { dropped_ids: vec![12, 13, 14, 15, 16, 17, 18], ..Faults::default() });
+    assert_eq!(block_on(check_link(&bus)), Ok(true));
+
+    // Feeding the monitor: two silent heartbeats degrade the link
+    let mut link = LinkMonitor::new();
+    link.opened();
+    bus.inner().set_faults(Faults { delay_ms: 500.0, ..Faults::default() });
+    for _ in 0..2 {
+        let answered = block_on(check_link(&bus)).unwrap();
+        link.heartbeat(answered);
+    }
+    assert_eq!(link.state(), ConnectionState::Degraded);
+}
+
+// ----------------------------------------------------------------------------
+// Arbitration Tests
+// ----------------------------------------------------------------------------
+
+/// Simulated bus shared by concurrent tasks, whose reads yield to other tasks
+/// like real I/O does.
+struct SharedBus {
+    inner: SimulatedBus,
+    arbiter: Option<Arbiter>,
+}
+
+impl SharedBus {
+    fn new(arbitrated: bool) -> Self {
+        Self { inner: SimulatedBus::new(), arbiter: arbitrated.then(Arbiter::new) }
+    }
+}
+
+/// Future pending on its first poll, letting other tasks run.
+fn yield_now() -> impl Future<Output = ()> {
+    let mut yielded = false;
+    poll_fn(move |cx| {
+        if yielded {
+            return Poll::Ready(());
+        }
+        yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    })
+}
+
+impl MotorBus for SharedBus {
+    async fn write(&self, packet: &[u8]) -> Result<(), ReachyError> {
+        self.inner.write(packet).await
+    }
+
+    async fn read(&self) -> Result<Vec<u8>, ReachyError> {
+        yield_now().await;
+        self.inner.read().await
+    }
+
+    async fn read_timeout(&self, ms: u32) -> Result<Option<Vec<u8>>, ReachyError> {
+        yield_now().await;
+        self.inner.read_timeout(ms).await
+    }
+
+    async fn sleep(&self, ms: u32) {
+        self.inner.sleep(ms).await
+    }
+
+    fn now_ms(&self) -> f64 {
+        self.inner.now_ms()
+    }
+
+    fn io_policy(&self) -> IoPolicy {
+        self.inner.io_policy()
+    }
+
+    fn set_io_policy(&self, policy: IoPolicy) {
+        self.inner.set_io_policy(policy)
+    }
+
+    fn arbiter(&self) -> Option<&Arbiter> {
+        self.arbiter.as_ref()
+    }
+}
+
+#[test]
+fn test_priority_of_packets() {
+    assert_eq!(Priority::of(&build_sync_write_position(&[11], &[2048])), Priority::Command);
+    assert_eq!(Priority::of(&build_sync_write_torque(&ALL_MOTOR_IDS, false)), Priority::Command);
+    assert_eq!(Priority::of(&build_reboot_packet(11)), Priority::Command);
+    assert_eq!(Priority::of(&build_action_packet(BROADCAST_ID)), Priority::Command);
+    assert_eq!(Priority::of(&build_sync_current_position(&ALL_MOTOR_IDS)), Priority::Telemetry);
+    assert_eq!(Priority::of(&build_ping_packet(11)), Priority::Telemetry);
+    assert_eq!(Priority::of(&build_read_packet(11, address::PRESENT_TEMPERATURE, 1)), Priority::Telemetry);
+    assert_eq!(Priority::of(&[]), Priority::Telemetry);
+    assert!(Priority::Command > Priority::Telemetry);
+}
+
+#[test]
+fn test_arbiter_serves_commands_first() {
+    let arbiter = Arbiter::new();
+    let mut cx = Context::from_waker(Waker::noop());
+    let order = RefCell::new(Vec::new());
+    let task = |name: &'static str, priority| {
+        let (arbiter, order) = (&arbiter, &order);
+        async move {
+            let _turn = arbiter.acquire(priority).await;
+            order.borrow_mut().push(name);
+        }
+    };
+
+    let first = block_on(arbiter.acquire(Priority::Telemetry));
+    assert!(arbiter.is_busy());
+
+    let mut read_1 = pin!(task("read 1", Priority::Telemetry));
+    let mut write_1 = pin!(task("write 1", Priority::Command));
+    let mut read_2 = pin!(task("read 2", Priority::Telemetry));
+    let write_2 = pin!(task("write 2", Priority::Command));
+    assert!(read_1.as_mut().poll(&mut cx).is_pending());
+    assert!(write_1.as_mut().poll(&mut cx).is_pending());
+    assert!(read_2.as_mut().poll(&mut cx).is_pending());
+    assert_eq!(arbiter.waiting(), 3);
+
+    // A cancelled waiter leaves the queue
+    {
+        let mut cancelled = pin!(task("cancelled", Priority::Command));
+        assert!(cancelled.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(arbiter.waiting(), 4);
+    }
+    assert_eq!(arbiter.waiting(), 3);
+
+    drop(first);
+    assert!(!arbiter.is_busy());
+    block_on(async {
+        futures_util::future::join4(read_1, write_1, read_2, write_2).await;
+    });
+    assert_eq!(*order.borrow(), vec!["write 1", "write 2", "read 1", "read 2"]);
+    assert!(!arbiter.is_busy());
+    assert_eq!(arbiter.waiting(), 0);
+}
+
+/// Run a head pose read, a position read and a torque command concurrently,
+/// returning their results and the recorded traffic.
+fn run_concurrent_calls(arbitrated: bool) -> (Vec<Result<Vec<f32>, ReachyError>>, Capture) {
+    let bus = TapBus::new(SharedBus::new(arbitrated));
+    let torque = build_sync_write_torque(&ALL_MOTOR_IDS, true);
+    let (pose, positions, sent) = block_on(futures_util::future::join3(
+        read_head_pose(&bus),
+        read_motor_positions(&bus, &HEAD_MOTOR_IDS),
+        bus.send(&torque),
+    ));
+    sent.unwrap();
+    (vec![pose, positions], bus.capture())
+}
+
+#[test]
+fn test_concurrent_exchanges_are_serialized() {
+    let (results, capture) = run_concurrent_calls(true);
+    assert_eq!(results[0], block_on(read_head_pose(&SimulatedBus::new())));
+    assert!(results[1].as_ref().unwrap().iter().all(|p| !p.is_nan()));
+
+    // Each request gets all its replies before the next one is sent, and the
+    // command overtakes the waiting position read
+    let description = capture.describe();
+    let summary: Vec<String> = description.lines().map(|line| line.trim_start().split("  ").nth(2).unwrap().split(' ').next().unwrap().to_string()).collect();
+    let expected = [vec!["SYNC_READ"], vec!["STATUS"; 7], vec!["SYNC_WRITE"], vec!["SYNC_READ"], vec!["STATUS"; 6]].concat();
+    assert_eq!(summary, expected, "{}", description);
+
+    // Without arbitration, the requests interleave
+    let (_, capture) = run_concurrent_calls(false);
+    let writes: Vec<usize> = capture.records().enumerate().filter(|(_, r)| r.dir == Direction::Tx).map(|(i, _)| i).collect();
+    assert_eq!(writes, vec![0, 1, 2], "{}", capture.describe());
+}
+
+// ----------------------------------------------------------------------------
+// Limits Tests
+// ----------------------------------------------------------------------------
+
+const HEAD_AND_ANTENNA_IDS: [u8; 8] = [11, 12, 13, 14, 15, 16, 17, 18];
+
+fn radians(degrees: &[f32]) -> Vec<f32> {
+    degrees.iter().map(|d| d.to_radians()).collect()
+}
+
+#[test]
+fn test_default_limits_cover_all_motors() {
+    let limits = Limits::default();
+    assert_eq!(limits.policy, LimitPolicy::Clamp);
+    for id in ALL_MOTOR_IDS {
+        assert!(limits.joint_range(id).is_some(), "No limits for motor {}", id);
+    }
+    assert_eq!(limits.joint_range(11), Some(("head_1", [-160.0, 160.0])));
+    // The antenna cap is tighter than the motor's own range
+    assert_eq!(limits.joint_range(17), Some(("l_antenna", [-150.0, 150.0])));
+    assert_eq!(limits.joint_range(42), None);
+
+    assert_eq!(Limits::from_json(&limits.to_json()), Ok(limits.clone()));
+    assert_eq!(Limits::from_json(DEFAULT_LIMITS_JSON), Ok(limits));
+}
+
+#[test]
+fn test_limits_clamp_joints() {
+    let limits = Limits::default();
+
+    let mut angles = radians(&[0.0, 10.0, -20.0, 30.0, -40.0, 50.0, 45.0, -45.0]);
+    let original = angles.clone();
+    assert_eq!(limits.enforce_joints(&HEAD_AND_ANTENNA_IDS, &mut angles), Ok(false));
+    assert_eq!(angles, original);
+
+    let mut angles = radians(&[170.0, 0.0, -170.0, 0.0, 0.0, 0.0, 170.0, -179.0]);
+    assert_eq!(limits.enforce_joints(&HEAD_AND_ANTENNA_IDS, &mut angles), Ok(true));
+    let degrees: Vec<f32> = angles.iter().map(|r| r.to_degrees().round()).collect();
+    assert_eq!(degrees, vec![160.0, 0.0, -160.0, 0.0, 0.0, 0.0, 150.0, -150.0]);
+
+    // Motors without limits are passed through
+    let mut angles = radians(&[300.0]);
+    assert_eq!(limits.enforce_joints(&[42], &mut angles), Ok(false));
+}
+
+#[test]
+fn test_limits_reject_joints() {
+    let limits = Limits { policy: LimitPolicy::Reject, ..Limits::default() };
+
+    let mut angles = radians(&[20.0, 170.0]);
+    let original = angles.clone();
+    let err = limits.enforce_joints(&[17, 18], &mut angles).unwrap_err();
+    match err {
+        ReachyError::LimitExceeded { limit, value, min, max } => {
+            assert_eq!(limit, "r_antenna");
+            assert!((value - 170.0).abs() < 1e-3);
+            assert_eq!((min, max), (-150.0, 150.0));
+        }
+        e => panic!("Expected LimitExceeded, got {:?}", e),
+    }
+    assert_eq!(angles, original);
+
+    // Not a number can't be clamped either
+    let mut angles = [f32::NAN];
+    assert!(matches!(Limits::default().enforce_joints(&[11], &mut angles), Err(ReachyError::InvalidArgument(_))));
+}
+
+#[test]
+fn test_limits_pose() {
+    let mut limits = Limits::default();
+
+    let mut pose = [10.0, -20.0, 5.0, 15.0, -30.0, 90.0];
+    assert_eq!(limits.enforce_pose(&mut pose), Ok(false));
+
+    let mut pose = [80.0, -20.0, -60.0, 45.0, -50.0, 90.0];
+    assert_eq!(limits.enforce_pose(&mut pose), Ok(true));
+    assert_eq!(pose, [50.0, -20.0, -40.0, 40.0, -40.0, 90.0]);
+
+    limits.policy = LimitPolicy::Reject;
+    let mut pose = [0.0, 0.0, 0.0, 0.0, 41.0, 0.0];
+    assert_eq!(
+        limits.enforce_pose(&mut pose),
+        Err(ReachyError::LimitExceeded { limit: "pitch".into(), value: 41.0, min: -40.0, max: 40.0 })
+    );
+}
+
+#[test]
+fn test_limits_body_yaw() {
+    let mut limits = Limits::default();
+    assert_eq!(limits.joint_range(10), Some(("body_yaw", [-160.0, 160.0])));
+    assert_eq!(limits.max_relative_body_yaw_deg, 65.0);
+
+    assert_eq!(limits.enforce_body_yaw(30.0, 0.0), Ok(30.0));
+    // At most 65° away from the head
+    assert_eq!(limits.enforce_body_yaw(100.0, 0.0), Ok(65.0));
+    assert_eq!(limits.enforce_body_yaw(-100.0, 90.0), Ok(25.0));
+    // Within the motor's range
+    assert_eq!(limits.enforce_body_yaw(175.0, 170.0), Ok(160.0));
+    // Head out of the body's reach: as close as it gets
+    limits.max_relative_body_yaw_deg = 10.0;
+    assert_eq!(limits.enforce_body_yaw(0.0, 175.0), Ok(160.0));
+    assert_eq!(limits.enforce_body_yaw(0.0, -175.0), Ok(-160.0));
+
+    let limits = Limits { policy: LimitPolicy::Reject, ..Limits::default() };
+    assert_eq!(
+        limits.enforce_body_yaw(100.0, 0.0),
+        Err(ReachyError::LimitExceeded { limit: "body_yaw".into(), value: 100.0, min: -65.0, max: 65.0 })
+    );
+}
+
+#[test]
+fn test_limits_json_validation() {
+    // Policy defaults to clamp
+    let json = r#"{"joints": [{"id": 11, "name": "head_1", "min_deg": -10, "max_deg": 10}], "position_mm": {"x": [-1, 1], "y": [-1, 1], "z": [-1, 1]}, "orientation_deg": {"roll": 5, "pitch": 5, "yaw": 5}, "max_antenna_deg": 90}"#;
+    let limits = Limits::from_json(json).unwrap();
+    assert_eq!(limits.policy, LimitPolicy::Clamp);
+    assert_eq!(limits.joint_range(11), Some(("head_1", [-10.0, 10.0])));
+    // Antennas stay capped without a per-motor entry
+    assert_eq!(limits.joint_range(18), Some(("r_antenna", [-90.0, 90.0])));
+    assert_eq!(limits.max_relative_body_yaw_deg, 65.0);
+
+    let empty_range = json.replace(r#""min_deg": -10"#, r#""min_deg": 20"#);
+    assert!(matches!(Limits::from_json(&empty_range), Err(ReachyError::InvalidArgument(_))));
+    let negative_cone = json.replace(r#""roll": 5"#, r#""roll": -5"#);
+    assert!(matches!(Limits::from_json(&negative_cone), Err(ReachyError::InvalidArgument(_))));
+    assert!(matches!(Limits::from_json("{}"), Err(ReachyError::InvalidArgument(_))));
+
+    assert_eq!(LimitPolicy::from_name("reject"), Ok(LimitPolicy::Reject));
+    assert_eq!(LimitPolicy::Clamp.as_str(), "clamp");
+    assert!(LimitPolicy::from_name("ignore").is_err());
+}
+
 // ============================================================================
 // WebSocket Integration Tests
 // ============================================================================
 //
-// These tests require a running Reachy Mini server at ws://127.0.0.1:8000
+// These tests require a running Reachy Mini server at ws://127.0.0.1:8000,
+// or the simulator: cargo run --features sim-server --bin reachy-mini-sim-server
 // Run with: cargo test --test app websocket -- --ignored
 
 use std::net::TcpStream;
@@ -617,7 +2532,7 @@ use tungstenite::{connect, Message};
 
 const WS_URL: &str = "ws://127.0.0.1:8000/api/move/ws/raw/write";
 const HEAD_MOTOR_IDS: [u8; 6] = [11, 12, 13, 14, 15, 16];
-const ALL_MOTOR_IDS: [u8; 8] = [11, 12, 13, 14, 15, 16, 17, 18];
+const ALL_MOTOR_IDS: [u8; 9] = [10, 11, 12, 13, 14, 15, 16, 17, 18];
 
 /// Helper to check if WebSocket server is available
 fn is_server_available() -> bool {